@@ -19,7 +19,8 @@ CREATE TABLE `bot_admins` (
   `perm_id` int(10) UNSIGNED NOT NULL,
   `server_id` bigint(20) UNSIGNED NOT NULL,
   `user_id` bigint(20) UNSIGNED NOT NULL,
-  `floppadmin` tinyint(1) NOT NULL DEFAULT '0'
+  `floppadmin` tinyint(1) NOT NULL DEFAULT '0',
+  `expires_at` timestamp NULL DEFAULT NULL
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
 
 -- --------------------------------------------------------
@@ -36,6 +37,86 @@ CREATE TABLE `bug_reports` (
   `status` enum('closed','forgevanilla','resolved','low','medium','high','critical') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'medium',
   `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
   `category` enum('fa_renewed','fa_legacy','sa_renewed','sa_legacy') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'fa_renewed',
+  `resolved_at` timestamp NULL DEFAULT NULL,
+  `status_since` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `guild_id` bigint(20) UNSIGNED NOT NULL DEFAULT '325180225979809792',
+  `merged_into` int(11) DEFAULT NULL,
+  `reporter_id` bigint(20) UNSIGNED DEFAULT NULL,
+  `fixed_in` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `saved_author` varchar(64) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `saved_content` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `saved_attachment` varchar(512) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `snoozed_until` timestamp NULL DEFAULT NULL,
+  `summary` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `has_attachment` tinyint(1) DEFAULT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__archive`
+--
+
+CREATE TABLE `bug_reports__archive` (
+  `bug_id` int(11) NOT NULL,
+  `channel_id` bigint(20) NOT NULL,
+  `message_id` bigint(20) NOT NULL,
+  `title` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `status` enum('closed','forgevanilla','resolved','low','medium','high','critical') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'medium',
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `category` enum('fa_renewed','fa_legacy','sa_renewed','sa_legacy') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL DEFAULT 'fa_renewed',
+  `resolved_at` timestamp NULL DEFAULT NULL,
+  `status_since` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `guild_id` bigint(20) UNSIGNED NOT NULL DEFAULT '325180225979809792',
+  `merged_into` int(11) DEFAULT NULL,
+  `reporter_id` bigint(20) UNSIGNED DEFAULT NULL,
+  `fixed_in` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `saved_author` varchar(64) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `saved_content` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `saved_attachment` varchar(512) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `snoozed_until` timestamp NULL DEFAULT NULL,
+  `summary` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `has_attachment` tinyint(1) DEFAULT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__history`
+--
+
+CREATE TABLE `bug_reports__history` (
+  `history_id` int(10) UNSIGNED NOT NULL,
+  `bug_id` int(10) UNSIGNED NOT NULL,
+  `old_status` enum('closed','forgevanilla','resolved','low','medium','high','critical') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `new_status` enum('closed','forgevanilla','resolved','low','medium','high','critical') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `old_title` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `new_title` tinytext CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `changed_by` bigint(20) UNSIGNED DEFAULT NULL,
+  `timestamp` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__label_subscriptions`
+--
+
+CREATE TABLE `bug_reports__label_subscriptions` (
+  `guild_id` bigint(20) UNSIGNED NOT NULL,
+  `channel_id` bigint(20) UNSIGNED NOT NULL,
+  `label` varchar(20) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__labels`
+--
+
+CREATE TABLE `bug_reports__labels` (
+  `bug_id` int(10) UNSIGNED NOT NULL,
+  `label` varchar(20) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
 
 -- --------------------------------------------------------
@@ -65,6 +146,32 @@ CREATE TABLE `bug_reports__notifications` (
 
 -- --------------------------------------------------------
 
+--
+-- Table structure for table `bug_reports__watchers`
+--
+
+CREATE TABLE `bug_reports__watchers` (
+  `watch_id` int(10) UNSIGNED NOT NULL,
+  `bug_id` int(10) UNSIGNED NOT NULL,
+  `user_id` bigint(20) UNSIGNED NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `bug_reports__webhooks`
+--
+
+CREATE TABLE `bug_reports__webhooks` (
+  `server_id` bigint(20) UNSIGNED NOT NULL,
+  `name` varchar(32) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `url` varchar(256) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `username` varchar(80) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL,
+  `avatar_url` varchar(512) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci DEFAULT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
 --
 -- Table structure for table `channel_blacklist`
 --
@@ -102,6 +209,18 @@ CREATE TABLE `floppa_images` (
 
 -- --------------------------------------------------------
 
+--
+-- Table structure for table `guild_settings`
+--
+
+CREATE TABLE `guild_settings` (
+  `server_id` bigint(20) UNSIGNED NOT NULL,
+  `setting_key` varchar(64) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `setting_value` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
 --
 -- Table structure for table `list_guilds`
 --
@@ -124,6 +243,21 @@ CREATE TABLE `lotr_mod_bot_prefix` (
 
 -- --------------------------------------------------------
 
+--
+-- Table structure for table `mc_player_alerts`
+--
+
+CREATE TABLE `mc_player_alerts` (
+  `server_id` bigint(20) UNSIGNED NOT NULL,
+  `threshold` int(10) UNSIGNED NOT NULL,
+  `direction` enum('above','below') CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `channel_id` bigint(20) UNSIGNED NOT NULL,
+  `once` tinyint(1) NOT NULL DEFAULT '0',
+  `armed` tinyint(1) NOT NULL DEFAULT '0'
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
 --
 -- Table structure for table `mc_server_ip`
 --
@@ -136,6 +270,18 @@ CREATE TABLE `mc_server_ip` (
 
 -- --------------------------------------------------------
 
+--
+-- Table structure for table `mc_server_list`
+--
+
+CREATE TABLE `mc_server_list` (
+  `server_id` bigint(20) UNSIGNED NOT NULL,
+  `name` varchar(64) CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL,
+  `mc_ip` text CHARACTER SET utf8mb4 COLLATE utf8mb4_0900_ai_ci NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
 --
 -- Table structure for table `qa__channels`
 --
@@ -188,6 +334,19 @@ CREATE TABLE `roles__aliases` (
 
 -- --------------------------------------------------------
 
+--
+-- Table structure for table `server_status_log`
+--
+
+CREATE TABLE `server_status_log` (
+  `log_id` int(10) UNSIGNED NOT NULL,
+  `server_id` bigint(20) UNSIGNED NOT NULL,
+  `checked_at` datetime NOT NULL,
+  `online` tinyint(1) NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;
+
+-- --------------------------------------------------------
+
 --
 -- Table structure for table `user_blacklist`
 --
@@ -212,7 +371,37 @@ ALTER TABLE `bot_admins`
 -- Indexes for table `bug_reports`
 --
 ALTER TABLE `bug_reports`
-  ADD PRIMARY KEY (`bug_id`);
+  ADD PRIMARY KEY (`bug_id`),
+  ADD KEY `guild_id` (`guild_id`),
+  ADD KEY `status_timestamp` (`status`,`timestamp`);
+
+--
+-- Indexes for table `bug_reports__archive`
+--
+ALTER TABLE `bug_reports__archive`
+  ADD PRIMARY KEY (`bug_id`),
+  ADD KEY `guild_id` (`guild_id`);
+
+--
+-- Indexes for table `bug_reports__history`
+--
+ALTER TABLE `bug_reports__history`
+  ADD PRIMARY KEY (`history_id`),
+  ADD KEY `bug_id` (`bug_id`);
+
+--
+-- Indexes for table `bug_reports__label_subscriptions`
+--
+ALTER TABLE `bug_reports__label_subscriptions`
+  ADD PRIMARY KEY (`channel_id`,`label`),
+  ADD KEY `guild_label` (`guild_id`,`label`);
+
+--
+-- Indexes for table `bug_reports__labels`
+--
+ALTER TABLE `bug_reports__labels`
+  ADD PRIMARY KEY (`bug_id`,`label`),
+  ADD KEY `label` (`label`);
 
 --
 -- Indexes for table `bug_reports__links`
@@ -226,6 +415,19 @@ ALTER TABLE `bug_reports__links`
 ALTER TABLE `bug_reports__notifications`
   ADD PRIMARY KEY (`notification_id`);
 
+--
+-- Indexes for table `bug_reports__watchers`
+--
+ALTER TABLE `bug_reports__watchers`
+  ADD PRIMARY KEY (`watch_id`),
+  ADD UNIQUE KEY `bug_user` (`bug_id`,`user_id`);
+
+--
+-- Indexes for table `bug_reports__webhooks`
+--
+ALTER TABLE `bug_reports__webhooks`
+  ADD PRIMARY KEY (`server_id`,`name`);
+
 --
 -- Indexes for table `channel_blacklist`
 --
@@ -244,6 +446,12 @@ ALTER TABLE `custom_commands`
 ALTER TABLE `floppa_images`
   ADD PRIMARY KEY (`id`);
 
+--
+-- Indexes for table `guild_settings`
+--
+ALTER TABLE `guild_settings`
+  ADD PRIMARY KEY (`server_id`,`setting_key`);
+
 --
 -- Indexes for table `list_guilds`
 --
@@ -256,12 +464,24 @@ ALTER TABLE `list_guilds`
 ALTER TABLE `lotr_mod_bot_prefix`
   ADD PRIMARY KEY (`server_id`);
 
+--
+-- Indexes for table `mc_player_alerts`
+--
+ALTER TABLE `mc_player_alerts`
+  ADD PRIMARY KEY (`server_id`,`threshold`,`direction`);
+
 --
 -- Indexes for table `mc_server_ip`
 --
 ALTER TABLE `mc_server_ip`
   ADD PRIMARY KEY (`server_id`);
 
+--
+-- Indexes for table `mc_server_list`
+--
+ALTER TABLE `mc_server_list`
+  ADD PRIMARY KEY (`server_id`,`name`);
+
 --
 -- Indexes for table `qa__channels`
 --
@@ -286,6 +506,13 @@ ALTER TABLE `roles`
 ALTER TABLE `roles__aliases`
   ADD PRIMARY KEY (`alias_uid`);
 
+--
+-- Indexes for table `server_status_log`
+--
+ALTER TABLE `server_status_log`
+  ADD PRIMARY KEY (`log_id`),
+  ADD KEY `server_checked_at` (`server_id`,`checked_at`);
+
 --
 -- Indexes for table `user_blacklist`
 --
@@ -308,6 +535,12 @@ ALTER TABLE `bot_admins`
 ALTER TABLE `bug_reports`
   MODIFY `bug_id` int(11) NOT NULL AUTO_INCREMENT;
 
+--
+-- AUTO_INCREMENT for table `bug_reports__history`
+--
+ALTER TABLE `bug_reports__history`
+  MODIFY `history_id` int(10) UNSIGNED NOT NULL AUTO_INCREMENT;
+
 --
 -- AUTO_INCREMENT for table `bug_reports__links`
 --
@@ -320,6 +553,12 @@ ALTER TABLE `bug_reports__links`
 ALTER TABLE `bug_reports__notifications`
   MODIFY `notification_id` int(10) UNSIGNED NOT NULL AUTO_INCREMENT;
 
+--
+-- AUTO_INCREMENT for table `bug_reports__watchers`
+--
+ALTER TABLE `bug_reports__watchers`
+  MODIFY `watch_id` int(10) UNSIGNED NOT NULL AUTO_INCREMENT;
+
 --
 -- AUTO_INCREMENT for table `channel_blacklist`
 --
@@ -356,6 +595,12 @@ ALTER TABLE `qa__moderators`
 ALTER TABLE `roles__aliases`
   MODIFY `alias_uid` int(11) NOT NULL AUTO_INCREMENT;
 
+--
+-- AUTO_INCREMENT for table `server_status_log`
+--
+ALTER TABLE `server_status_log`
+  MODIFY `log_id` int(10) UNSIGNED NOT NULL AUTO_INCREMENT;
+
 --
 -- AUTO_INCREMENT for table `user_blacklist`
 --
@@ -53,6 +53,7 @@ pub async fn get_project_info(ctx: &Context, id: u64) -> CommandResult<CurseProj
 
     let rclient = get_reqwest_client!(ctx);
     let req = format!("{}{}", CURSE_API, id);
+    let _timer = crate::metrics::time_api("curseforge");
     let res = rclient
         .get(&req)
         .header("accept", "application/json")
@@ -32,6 +32,7 @@ pub async fn google_search(ctx: &Context, query: &str, wiki: &Wikis) -> Option<[
         ("siteSearch", wiki.site()),
     ];
 
+    let _timer = crate::metrics::time_api("google");
     let res_body = rclient
         .get(GOOGLE_API)
         .query(&req)
@@ -1,40 +1,826 @@
+use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use serenity::client::Context;
+use serenity::futures::future::{BoxFuture, FutureExt, Shared};
+use serenity::prelude::TypeMapKey;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::net::UdpSocket;
 
-use crate::constants::MINECRAFT_API;
+use crate::api::proxy::{Proxy, SocksUdpSocket};
+use crate::api::ReqwestClient;
+use crate::constants::{LEGACY_QUERY_TIMEOUT, MINECRAFT_API, MINECRAFT_API_TIMEOUT};
 use crate::get_reqwest_client;
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Default port of a Minecraft server
+pub const DEFAULT_PORT: u16 = 25565;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Description {
     pub raw: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub uuid: Option<String>,
+}
+
+impl Player {
+    pub fn new(name: String) -> Self {
+        Self { name, uuid: None }
+    }
+}
+
+/// Player list as returned by the status API, with the UUIDs in a separate map
+#[derive(Deserialize)]
+struct RawPlayerList {
+    online: u32,
+    max: u32,
+    list: Option<Vec<String>>,
+    #[serde(default)]
+    uuid: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "RawPlayerList")]
 pub struct PlayerList {
     pub online: u32,
     pub max: u32,
-    pub list: Option<Vec<String>>,
+    pub list: Option<Vec<Player>>,
+}
+
+impl From<RawPlayerList> for PlayerList {
+    fn from(mut raw: RawPlayerList) -> Self {
+        Self {
+            online: raw.online,
+            max: raw.max,
+            list: raw.list.map(|names| {
+                names
+                    .into_iter()
+                    .map(|name| Player {
+                        uuid: raw.uuid.remove(&name),
+                        name,
+                    })
+                    .collect()
+            }),
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MinecraftServer {
     pub online: bool,
     pub motd: Description,
     pub players: PlayerList,
+    /// Base64-encoded server favicon, if the server reports one
+    pub icon: Option<String>,
+    pub version: Option<String>,
+    pub software: Option<String>,
+    /// Mods reported by modded (Forge) servers
+    pub mods: Option<ModList>,
+    /// IP the status API reached the server at, after following SRV records
+    #[serde(default)]
+    pub ip: Option<String>,
+    /// Port the status API reached the server on, from the SRV record if any
+    #[serde(default)]
+    pub port: Option<u16>,
 }
 
-pub async fn get_server_status(ctx: &Context, ip: &str) -> Option<MinecraftServer> {
-    let rclient = get_reqwest_client!(ctx);
+impl MinecraftServer {
+    /// Address the status API reached the server at, which differs from the
+    /// queried one for hosts with an SRV record. `None` if it was not reported.
+    pub fn resolved_address(&self) -> Option<ServerAddress> {
+        let ip = self.ip.as_deref().filter(|ip| !ip.is_empty())?;
+        Some(ServerAddress {
+            host: ip.to_lowercase(),
+            port: self.port.unwrap_or(DEFAULT_PORT),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModList {
+    #[serde(default)]
+    pub names: Vec<String>,
+    /// `<mod id> <version>` entries, either as a list or as an index map
+    #[serde(default)]
+    pub raw: serde_json::Value,
+}
+
+impl ModList {
+    fn raw_entries(&self) -> Vec<&str> {
+        match &self.raw {
+            serde_json::Value::Array(entries) => {
+                entries.iter().filter_map(|e| e.as_str()).collect()
+            }
+            serde_json::Value::Object(entries) => {
+                entries.values().filter_map(|e| e.as_str()).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.names.len().max(self.raw_entries().len())
+    }
+
+    /// Version of the LOTR mod, if the server lists it
+    pub fn lotr_version(&self) -> Option<&str> {
+        self.raw_entries().into_iter().find_map(|entry| {
+            let (id, version) = entry.trim().split_once(' ')?;
+            if id.eq_ignore_ascii_case("lotr") {
+                Some(version.trim())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Normalized server address, used to deduplicate status queries
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ServerAddress {
+    pub host: String,
+    pub port: u16,
+}
+
+impl std::fmt::Display for ServerAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+impl ServerAddress {
+    /// Lowercases the host and applies the default port, so that
+    /// `Example.com` and `example.com:25565` are the same address.
+    pub fn normalize(ip: &str) -> Self {
+        let ip = ip.trim().to_lowercase();
+
+        let (host, port) = if let Some(rest) = ip.strip_prefix('[') {
+            // bracketed IPv6 literal, with an optional port
+            match rest.split_once(']') {
+                Some((host, port)) => (
+                    host.to_string(),
+                    port.strip_prefix(':').and_then(|p| p.parse().ok()),
+                ),
+                None => (rest.to_string(), None),
+            }
+        } else if ip.matches(':').count() > 1 {
+            // bare IPv6 literal: cannot have a port
+            (ip.clone(), None)
+        } else if let Some((host, port)) = ip.rsplit_once(':') {
+            (host.to_string(), port.parse().ok())
+        } else {
+            (ip.clone(), None)
+        };
+
+        Self {
+            host: host.trim_end_matches('.').to_string(),
+            port: port.unwrap_or(DEFAULT_PORT),
+        }
+    }
+
+    /// Checks that `ip` is a host name or an IP literal, with an optional port.
+    /// The error completes the sentence "The server IP ...".
+    pub fn validate(ip: &str) -> Result<(), &'static str> {
+        let ip = ip.trim();
+        if ip.is_empty() {
+            return Err("must not be empty");
+        }
+        if ip.contains("://") {
+            return Err("must not start with a scheme like `https://`");
+        }
+        if ip.chars().any(|c| c.is_whitespace() || c == '/') {
+            return Err("must not contain spaces or slashes");
+        }
+
+        let parse_port = |port: &str| match port.parse::<u16>() {
+            Ok(port) if port != 0 => Ok(()),
+            _ => Err("has an invalid port"),
+        };
+        let (host, port) = if let Some(rest) = ip.strip_prefix('[') {
+            let (host, port) = rest.split_once(']').ok_or("has an unclosed `[`")?;
+            if host.parse::<std::net::Ipv6Addr>().is_err() {
+                return Err("is not a valid IPv6 address");
+            }
+            return match port {
+                "" => Ok(()),
+                port => parse_port(port.strip_prefix(':').ok_or("has an invalid port")?),
+            };
+        } else if ip.matches(':').count() > 1 {
+            return match ip.parse::<std::net::Ipv6Addr>() {
+                Ok(_) => Ok(()),
+                Err(_) => Err("is not a valid IPv6 address"),
+            };
+        } else {
+            match ip.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (ip, None),
+            }
+        };
+        if let Some(port) = port {
+            parse_port(port)?;
+        }
+
+        if host.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return match host.parse::<std::net::Ipv4Addr>() {
+                Ok(_) => Ok(()),
+                Err(_) => Err("is not a valid IPv4 address"),
+            };
+        }
+        let name = host.strip_suffix('.').unwrap_or(host);
+        let valid_name = name.len() <= 253
+            && name.split('.').all(|label| {
+                (1..=63).contains(&label.len())
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            });
+        if !valid_name {
+            return Err("is not a valid host name");
+        }
+        Ok(())
+    }
+
+    /// Address sent to the status API. The default port is omitted so that
+    /// the API still resolves SRV records for the host.
+    pub fn query_string(&self) -> String {
+        if self.port == DEFAULT_PORT {
+            if self.host.contains(':') {
+                format!("[{}]", self.host)
+            } else {
+                self.host.clone()
+            }
+        } else {
+            self.to_string()
+        }
+    }
+}
+
+/// Reasons a server status could not be retrieved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerStatusError {
+    /// The server host could not be resolved
+    Dns,
+    /// The host was resolved, but the server did not answer
+    Offline,
+    /// The status API did not answer in time
+    Timeout,
+    /// The status API could not be reached
+    Connection,
+    /// The status API answered with an error or an invalid response
+    Upstream,
+}
+
+impl std::fmt::Display for ServerStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ServerStatusError::Dns => write!(f, "Could not resolve the server host"),
+            ServerStatusError::Offline => write!(f, "Server offline"),
+            ServerStatusError::Timeout => write!(f, "The status API timed out"),
+            ServerStatusError::Connection => write!(f, "Could not connect to the status API"),
+            ServerStatusError::Upstream => write!(f, "The status API returned an error"),
+        }
+    }
+}
 
-    let req = format!("{}{}", MINECRAFT_API, ip);
-    let res = rclient.get(&req).send().await.ok()?.text().await.ok()?;
-    if let Ok(server) = serde_json::from_str::<MinecraftServer>(&res) {
-        if server.online {
-            Some(server)
+impl std::error::Error for ServerStatusError {}
+
+impl ServerStatusError {
+    /// Whether retrying later might give a different result
+    pub fn is_transient(self) -> bool {
+        !matches!(self, ServerStatusError::Dns | ServerStatusError::Offline)
+    }
+}
+
+impl From<reqwest::Error> for ServerStatusError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            ServerStatusError::Timeout
+        } else if error.is_connect() {
+            ServerStatusError::Connection
         } else {
-            None
+            ServerStatusError::Upstream
         }
+    }
+}
+
+pub type ServerStatus = Result<MinecraftServer, ServerStatusError>;
+
+type StatusRequest = Shared<BoxFuture<'static, ServerStatus>>;
+
+/// In-flight status queries, keyed by normalized address, so that concurrent
+/// queries for the same server share a single upstream request.
+#[derive(Clone)]
+pub struct ServerStatusRequests(Arc<DashMap<ServerAddress, StatusRequest>>);
+
+impl TypeMapKey for ServerStatusRequests {
+    type Value = Self;
+}
+
+impl std::ops::Deref for ServerStatusRequests {
+    type Target = DashMap<ServerAddress, StatusRequest>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Default for ServerStatusRequests {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServerStatusRequests {
+    pub fn new() -> Self {
+        Self(Arc::new(DashMap::new()))
+    }
+}
+
+async fn fetch_server_status(rclient: &ReqwestClient, address: &ServerAddress) -> ServerStatus {
+    let req = format!("{}{}", MINECRAFT_API, address.query_string());
+    let _timer = crate::metrics::time_api("minecraft");
+    let res = rclient
+        .get(&req)
+        .timeout(MINECRAFT_API_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let value: serde_json::Value =
+        serde_json::from_str(&res).map_err(|_| ServerStatusError::Upstream)?;
+
+    if value["online"].as_bool().unwrap_or_default() {
+        serde_json::from_value(value).map_err(|_| ServerStatusError::Upstream)
+    } else if value["ip"].as_str().unwrap_or_default().is_empty() {
+        // the API only fills in the ip when the host could be resolved
+        Err(ServerStatusError::Dns)
     } else {
-        None
+        Err(ServerStatusError::Offline)
+    }
+}
+
+async fn query_server_status(
+    rclient: ReqwestClient,
+    requests: ServerStatusRequests,
+    address: ServerAddress,
+) -> ServerStatus {
+    let server = fetch_server_status(&rclient, &address).await;
+
+    // the request is done: the next query for this address will be a new one
+    requests.remove(&address);
+
+    server
+}
+
+pub async fn get_server_status(ctx: &Context, ip: &str) -> ServerStatus {
+    let rclient = get_reqwest_client!(ctx);
+    let requests = {
+        let data_read = ctx.data.read().await;
+        data_read
+            .get::<ServerStatusRequests>()
+            .expect("Expected a server status request map in the type map")
+            .clone()
+    };
+
+    let address = ServerAddress::normalize(ip);
+
+    let request = requests
+        .entry(address.clone())
+        .or_insert_with(|| {
+            query_server_status(rclient, requests.clone(), address)
+                .boxed()
+                .shared()
+        })
+        .clone();
+
+    request.await
+}
+
+/// Magic bytes starting every legacy query packet
+const QUERY_MAGIC: [u8; 2] = [0xFE, 0xFD];
+const QUERY_HANDSHAKE: u8 = 9;
+const QUERY_STAT: u8 = 0;
+/// Only the lower 4 bits of each byte are used by the server
+const QUERY_SESSION_ID: [u8; 4] = [0x01, 0x02, 0x03, 0x04];
+/// Padding between the stat packet header and the key/value section
+const QUERY_PADDING: usize = 11;
+
+fn parse_challenge_token(response: &[u8]) -> Option<i32> {
+    // packet type, session id, then the null-terminated token as a decimal string
+    let token = response.get(5..)?.split(|&b| b == 0).next()?;
+    std::str::from_utf8(token).ok()?.parse().ok()
+}
+
+fn parse_full_stat(response: &[u8]) -> Option<MinecraftServer> {
+    if response.first() != Some(&QUERY_STAT) {
+        return None;
+    }
+    let mut fields = response
+        .get(5 + QUERY_PADDING..)?
+        .split(|&b| b == 0)
+        .map(String::from_utf8_lossy);
+
+    let mut values = HashMap::new();
+    loop {
+        let key = fields.next()?;
+        if key.is_empty() {
+            break;
+        }
+        values.insert(key, fields.next()?);
+    }
+
+    let mut players = Vec::new();
+    if fields.next().as_deref() == Some("\u{1}player_") {
+        fields.next();
+        players.extend(
+            fields
+                .take_while(|name| !name.is_empty())
+                .map(|name| Player::new(name.into_owned())),
+        );
+    }
+
+    Some(MinecraftServer {
+        online: true,
+        motd: Description {
+            raw: vec![values.get("hostname")?.to_string()],
+        },
+        players: PlayerList {
+            online: values.get("numplayers")?.parse().ok()?,
+            max: values.get("maxplayers")?.parse().ok()?,
+            list: Some(players),
+        },
+        icon: None,
+        version: values.get("version").map(|version| version.to_string()),
+        software: None,
+        mods: None,
+        ip: None,
+        port: None,
+    })
+}
+
+/// How the status API reached a server, from the debug part of its response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerDiagnostics {
+    /// Target of the `_minecraft._tcp` SRV record, if the host has one
+    pub srv_target: Option<String>,
+    /// Address the API resolved the server to, if it could
+    pub resolved: Option<String>,
+    /// Whether the server answered the status ping
+    pub ping: bool,
+    pub players: Option<(u32, u32)>,
+}
+
+impl ServerDiagnostics {
+    fn from_response(value: &serde_json::Value) -> Self {
+        let srv_target = value["debug"]["dns"]["srv"]
+            .as_array()
+            .and_then(|records| records.first())
+            .and_then(|record| {
+                Some(format!(
+                    "{}:{}",
+                    record["target"].as_str()?.trim_end_matches('.'),
+                    record["port"].as_u64()?
+                ))
+            })
+            // older responses only tell whether an SRV record was used
+            .or_else(|| {
+                if value["debug"]["srv"].as_bool().unwrap_or_default() {
+                    Some(format!(
+                        "{}:{}",
+                        value["hostname"].as_str().unwrap_or("?"),
+                        value["port"].as_u64().unwrap_or(DEFAULT_PORT.into())
+                    ))
+                } else {
+                    None
+                }
+            });
+        let resolved = value["ip"].as_str().filter(|ip| !ip.is_empty()).map(|ip| {
+            match value["port"].as_u64() {
+                Some(port) if ip.contains(':') => format!("[{}]:{}", ip, port),
+                Some(port) => format!("{}:{}", ip, port),
+                None => ip.to_string(),
+            }
+        });
+        let players = value["players"]["online"]
+            .as_u64()
+            .zip(value["players"]["max"].as_u64())
+            .map(|(online, max)| (online as u32, max as u32));
+        Self {
+            srv_target,
+            resolved,
+            ping: value["online"].as_bool().unwrap_or_default(),
+            players,
+        }
+    }
+}
+
+/// Queries the status API for the [`ServerDiagnostics`] of a server. Unlike
+/// [`get_server_status`], the query is not shared with concurrent ones.
+pub async fn get_server_diagnostics(
+    ctx: &Context,
+    ip: &str,
+) -> Result<ServerDiagnostics, ServerStatusError> {
+    let rclient = get_reqwest_client!(ctx);
+    let req = format!(
+        "{}{}",
+        MINECRAFT_API,
+        ServerAddress::normalize(ip).query_string()
+    );
+    let _timer = crate::metrics::time_api("minecraft");
+    let res = rclient
+        .get(&req)
+        .timeout(MINECRAFT_API_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let value: serde_json::Value =
+        serde_json::from_str(&res).map_err(|_| ServerStatusError::Upstream)?;
+    Ok(ServerDiagnostics::from_response(&value))
+}
+
+/// Socket for a [legacy query][legacy_query], direct or through a proxy
+enum QuerySocket {
+    Direct(UdpSocket),
+    Socks(SocksUdpSocket),
+}
+
+impl QuerySocket {
+    async fn connect(host: &str, port: u16, proxy: Option<&Proxy>) -> std::io::Result<Self> {
+        if let Some(proxy) = proxy {
+            return Ok(Self::Socks(
+                SocksUdpSocket::connect(proxy, host, port).await?,
+            ));
+        }
+
+        let address = tokio::net::lookup_host((host, port))
+            .await?
+            .next()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "unknown host"))?;
+        let socket = UdpSocket::bind(if address.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        })
+        .await?;
+        socket.connect(address).await?;
+        Ok(Self::Direct(socket))
+    }
+
+    async fn send(&self, data: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Direct(socket) => socket.send(data).await,
+            Self::Socks(socket) => socket.send(data).await,
+        }
+    }
+
+    async fn recv(&self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Direct(socket) => socket.recv(buffer).await,
+            Self::Socks(socket) => socket.recv(buffer).await,
+        }
+    }
+}
+
+/// Queries a server directly with the legacy UDP query protocol, for servers
+/// that disabled the status ping but enabled `enable-query`.
+///
+/// Behind a [proxy][crate::api::proxy], the query is relayed by the proxy,
+/// which must be a SOCKS5 one.
+pub async fn legacy_query(host: &str, port: u16, proxy: Option<&Proxy>) -> Option<MinecraftServer> {
+    let query = async {
+        let socket = QuerySocket::connect(host, port, proxy).await.ok()?;
+
+        let mut buffer = vec![0; u16::MAX as usize];
+
+        let mut handshake = QUERY_MAGIC.to_vec();
+        handshake.push(QUERY_HANDSHAKE);
+        handshake.extend(QUERY_SESSION_ID);
+        socket.send(&handshake).await.ok()?;
+        let len = socket.recv(&mut buffer).await.ok()?;
+        let token = parse_challenge_token(&buffer[..len])?;
+
+        let mut stat = QUERY_MAGIC.to_vec();
+        stat.push(QUERY_STAT);
+        stat.extend(QUERY_SESSION_ID);
+        stat.extend(token.to_be_bytes());
+        // the padding asks for the full stat, with the player list
+        stat.extend([0; 4]);
+        socket.send(&stat).await.ok()?;
+        let len = socket.recv(&mut buffer).await.ok()?;
+        parse_full_stat(&buffer[..len])
+    };
+
+    tokio::time::timeout(LEGACY_QUERY_TIMEOUT, query)
+        .await
+        .ok()?
+}
+
+/// Same as [`get_server_status`], but confirms offline servers with the
+/// [legacy query protocol][legacy_query] when a query port is known.
+pub async fn get_server_status_with_fallback(
+    ctx: &Context,
+    ip: &str,
+    query_port: Option<u16>,
+) -> ServerStatus {
+    match (get_server_status(ctx, ip).await, query_port) {
+        (Err(ServerStatusError::Offline), Some(query_port)) => {
+            let address = ServerAddress::normalize(ip);
+            let rclient = get_reqwest_client!(ctx);
+            legacy_query(&address.host, query_port, rclient.proxy())
+                .await
+                .ok_or(ServerStatusError::Offline)
+        }
+        (status, _) => status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_challenge_token, parse_full_stat, ServerAddress, ServerDiagnostics};
+
+    #[test]
+    fn test_server_diagnostics() {
+        let response = serde_json::json!({
+            "online": true,
+            "ip": "203.0.113.7",
+            "port": 25570,
+            "hostname": "play.example.com",
+            "players": { "online": 3, "max": 20 },
+            "debug": {
+                "srv": true,
+                "dns": {
+                    "srv": [{ "target": "mc.example.com.", "port": 25570 }],
+                    "a": [{ "host": "mc.example.com", "ip": "203.0.113.7" }]
+                }
+            }
+        });
+        assert_eq!(
+            ServerDiagnostics::from_response(&response),
+            ServerDiagnostics {
+                srv_target: Some("mc.example.com:25570".into()),
+                resolved: Some("203.0.113.7:25570".into()),
+                ping: true,
+                players: Some((3, 20)),
+            }
+        );
+
+        // unknown host: nothing resolved, no ping
+        let response = serde_json::json!({
+            "online": false,
+            "ip": "",
+            "port": 25565,
+            "debug": { "srv": false, "dns": { "error": { "a": "DNS request failed" } } }
+        });
+        assert_eq!(
+            ServerDiagnostics::from_response(&response),
+            ServerDiagnostics {
+                srv_target: None,
+                resolved: None,
+                ping: false,
+                players: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_normalize_address() {
+        assert_eq!(
+            ServerAddress::normalize("Example.com"),
+            ServerAddress::normalize("example.com:25565")
+        );
+        assert_eq!(
+            ServerAddress::normalize("play.example.com:25566").to_string(),
+            "play.example.com:25566"
+        );
+        assert_eq!(
+            ServerAddress::normalize("[::1]:25570").to_string(),
+            "[::1]:25570"
+        );
+        assert_eq!(ServerAddress::normalize("::1").to_string(), "[::1]:25565");
+        assert_eq!(
+            ServerAddress::normalize("example.com:25565").query_string(),
+            "example.com"
+        );
+    }
+
+    #[test]
+    fn test_validate_address() {
+        for ip in [
+            "example.com",
+            "Play.Example.com:25566",
+            "mc_server.example.com.",
+            "localhost",
+            "127.0.0.1",
+            "127.0.0.1:25565",
+            "::1",
+            "2001:db8::1",
+            "[2001:db8::1]",
+            "[2001:db8::1]:25570",
+            " example.com ",
+        ] {
+            assert_eq!(ServerAddress::validate(ip), Ok(()), "{}", ip);
+        }
+        for ip in [
+            "",
+            "https://example.com",
+            "example .com",
+            "example.com/map",
+            "example.com:",
+            "example.com:0",
+            "example.com:70000",
+            "-example.com",
+            "example..com",
+            "ex@mple.com",
+            "256.1.1.1",
+            "1.2.3",
+            "[2001:db8::1",
+            "[2001:db8::1]25570",
+            "[example.com]:25565",
+            "2001:db8::g",
+        ] {
+            assert!(ServerAddress::validate(ip).is_err(), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn test_legacy_query_parsing() {
+        assert_eq!(
+            parse_challenge_token(b"\x09\x01\x02\x03\x049513307\0"),
+            Some(9513307)
+        );
+        assert_eq!(
+            parse_challenge_token(b"\x09\x01\x02\x03\x04-42\0"),
+            Some(-42)
+        );
+        assert_eq!(parse_challenge_token(b"\x09\x01"), None);
+
+        let mut packet = b"\x00\x01\x02\x03\x04splitnum\x00\x80\x00".to_vec();
+        packet.extend_from_slice(
+            b"hostname\0A LOTR server\0gametype\0SMP\0version\01.7.10\0\
+numplayers\02\0maxplayers\020\0\0\x01player_\0\0Frodo\0Sam\0\0",
+        );
+        let server = parse_full_stat(&packet).unwrap();
+        assert_eq!(server.motd.raw, vec!["A LOTR server".to_string()]);
+        assert_eq!(server.players.online, 2);
+        assert_eq!(server.players.max, 20);
+        assert_eq!(
+            server.players.list,
+            Some(vec![
+                Player::new("Frodo".to_string()),
+                Player::new("Sam".to_string())
+            ])
+        );
+        assert_eq!(server.version.as_deref(), Some("1.7.10"));
+
+        assert!(parse_full_stat(b"\x00\x01\x02").is_none());
+    }
+
+    #[test]
+    fn test_player_uuids() {
+        let players: PlayerList = serde_json::from_str(
+            r#"{"online": 2, "max": 20, "list": ["Frodo", "Sam"],
+            "uuid": {"Frodo": "0f5e1b1c-2f5a-4c2e-9d6e-3c3b1e2a9f10"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            players.list,
+            Some(vec![
+                Player {
+                    name: "Frodo".to_string(),
+                    uuid: Some("0f5e1b1c-2f5a-4c2e-9d6e-3c3b1e2a9f10".to_string()),
+                },
+                Player::new("Sam".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_mod_list() {
+        let mods: ModList = serde_json::from_str(
+            r#"{"names": ["mcp", "FML", "Forge", "lotr"],
+            "raw": {"0": "mcp 9.05", "1": "FML 7.10.99.99", "2": "Forge 10.13.4.1614", "3": "lotr Update v36.15"}}"#,
+        )
+        .unwrap();
+        assert_eq!(mods.count(), 4);
+        assert_eq!(mods.lotr_version(), Some("Update v36.15"));
+
+        let mods: ModList =
+            serde_json::from_str(r#"{"names": ["FML"], "raw": ["FML 7.10.99.99"]}"#).unwrap();
+        assert_eq!(mods.count(), 1);
+        assert_eq!(mods.lotr_version(), None);
     }
 }
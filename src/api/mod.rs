@@ -3,12 +3,15 @@
 pub mod curseforge;
 pub mod google;
 pub mod minecraft;
+pub mod proxy;
 pub mod wiki;
 
 use serenity::prelude::TypeMapKey;
 
+use proxy::Proxy;
+
 #[derive(Debug, Clone)]
-pub struct ReqwestClient(reqwest::Client);
+pub struct ReqwestClient(reqwest::Client, Option<Proxy>);
 
 impl TypeMapKey for ReqwestClient {
     type Value = Self;
@@ -30,14 +33,25 @@ impl Default for ReqwestClient {
 
 impl ReqwestClient {
     pub fn new() -> Self {
+        Self::with_proxy(None)
+    }
+
+    /// Sends all the requests through `proxy`, if any
+    pub fn with_proxy(proxy: Option<Proxy>) -> Self {
+        let mut builder = reqwest::Client::builder().use_rustls_tls();
+        if let Some(proxy) = &proxy {
+            builder = builder.proxy(proxy.reqwest_proxy());
+        }
         Self(
-            reqwest::Client::builder()
-                .use_rustls_tls()
-                .build()
-                .expect("Could not build the reqwest client"),
+            builder.build().expect("Could not build the reqwest client"),
+            proxy,
         )
     }
 
+    pub fn proxy(&self) -> Option<&Proxy> {
+        self.1.as_ref()
+    }
+
     pub fn inner(&self) -> reqwest::Client {
         self.0.clone()
     }
@@ -17,6 +17,7 @@ pub async fn search(
     wiki: &Wikis,
 ) -> Option<GenericPage> {
     let rclient = get_reqwest_client!(ctx);
+    let _timer = crate::metrics::time_api("wiki");
 
     println!("wiki search: \"{}\" on {:?} ({})", query, wiki, namespace);
 
@@ -100,6 +101,7 @@ pub async fn search(
 
 pub async fn random(ctx: &Context, wiki: &Wikis) -> Option<GenericPage> {
     let rclient = get_reqwest_client!(ctx);
+    let _timer = crate::metrics::time_api("wiki");
 
     let req = [
         ("format", "json"),
@@ -11,14 +11,31 @@
 //! [`is_admin`] checks wether the user is either the owner, a bot admin,
 //! or has the [`struct@MANAGE_BOT_PERMS`] permissions.
 //!
+//! [`is_permanent_admin`] is [`is_admin`] without the temporary grants, for
+//! the commands that manage the bot admins.
+//!
+//! [`is_bug_reporter`] lets the members with the guild's bug reporter role
+//! report bugs, along with the admins.
+//!
+//! [`not_in_maintenance`] is put on the commands that write to the database,
+//! and refuses them while the bot is in [`Maintenance`] mode.
+//!
 //! [`is_minecraft_server`] checks wether there is a server IP registered
-//! with the guild. It fails if there is none, but is bypassed by bot
-//! admins.
+//! with the guild, or the guild passes its IP to each command instead. It
+//! fails otherwise, but is bypassed by bot admins.
 //!
 //! The [`dispatch_error_hook`] deals with the checks that fail and warns
 //! the user and/or log the error accordingly.
 //!
-//! The [`after_hook`] logs any command error to the bot console.
+//! The [`after_hook`] logs any command error to the bot console. Failed checks
+//! never reach it, so it only reports actual errors.
+//!
+//! [`not_owner_hook`] exempts the owner from the rate limit of the bucket it
+//! is attached to.
+//!
+//! The [`before_hook`] refuses the commands turned off through the
+//! [`CommandGate`], so that a misbehaving command can be disabled without a
+//! new release.
 
 use serenity::framework::standard::{
     macros::{check, hook},
@@ -27,11 +44,16 @@ use serenity::framework::standard::{
 use serenity::futures::future::join;
 use serenity::model::prelude::*;
 use serenity::prelude::*;
+use std::collections::HashSet;
 
 use crate::constants::{EOA_DISCORD, MANAGE_BOT_PERMS, OWNER_ID};
-use crate::database::{blacklist::check_blacklist, config::get_minecraft_ip};
+use crate::database::{
+    blacklist::check_blacklist,
+    config::get_minecraft_ip,
+    settings::{get_bool_setting, get_setting, BUG_REPORTER_ROLE, DISABLE_IP_PERSISTENCE},
+};
 use crate::is_admin;
-use crate::utils::has_permission;
+use crate::utils::{has_permission, schedule_failure_deletion};
 
 #[check]
 #[name = "allowed_blacklist"]
@@ -126,24 +148,55 @@ pub async fn user_blacklist(ctx: &Context, msg: &Message) -> Result<(), Reason>
 #[check]
 #[name = "is_admin"]
 pub async fn is_admin(ctx: &Context, msg: &Message) -> Result<(), Reason> {
+    if is_admin_user(ctx, msg.guild_id.unwrap_or_default(), msg.author.id).await {
+        Ok(())
+    } else {
+        Err(Reason::User(
+            "You need to be a server admin to use this command!".into(),
+        ))
+    }
+}
+
+/// Like [`is_admin`], but a [temporary grant][crate::commands::admin::admin_grant]
+/// is not enough: otherwise a temporary admin could make themselves permanent
+#[check]
+#[name = "is_permanent_admin"]
+pub async fn is_permanent_admin(ctx: &Context, msg: &Message) -> Result<(), Reason> {
     let server_id = msg.guild_id.unwrap_or_default();
     if msg.author.id == OWNER_ID
-        || is_admin!(ctx, msg)
+        || crate::database::admin_data::is_permanent_admin(ctx, server_id, msg.author.id)
+            .await
+            .unwrap_or_default()
         || has_permission(ctx, server_id, msg.author.id, MANAGE_BOT_PERMS).await
     {
         Ok(())
     } else {
-        Err(Reason::User("You are not an admin on this server!".into()))
+        Err(Reason::User(
+            "You need to be a permanent server admin to use this command!".into(),
+        ))
     }
 }
 
+/// Whether a user can manage the bot in a guild, see [`is_admin`]. Used where
+/// checks can't run, like slash commands.
+pub async fn is_admin_user(ctx: &Context, guild_id: GuildId, user_id: UserId) -> bool {
+    user_id == OWNER_ID
+        || is_admin!(ctx, guild_id, user_id)
+        || has_permission(ctx, guild_id, user_id, MANAGE_BOT_PERMS).await
+}
+
+/// Passes in direct messages, where commands that need a guild are already
+/// rejected by `#[only_in(guilds)]`
 #[check]
 #[name = "is_minecraft_server"]
 pub async fn is_minecraft_server(ctx: &Context, msg: &Message) -> Result<(), Reason> {
-    let server_id = msg
-        .guild_id
-        .ok_or_else(|| Reason::Log("Not in a guild".into()))?;
-    if get_minecraft_ip(ctx, server_id).await.is_some() {
+    let server_id = match msg.guild_id {
+        Some(server_id) => server_id,
+        None => return Ok(()),
+    };
+    if get_minecraft_ip(ctx, server_id).await.is_some()
+        || get_bool_setting(ctx, server_id, DISABLE_IP_PERSISTENCE).await
+    {
         Ok(())
     } else if is_admin!(ctx, msg)
         || msg.author.id == OWNER_ID
@@ -156,6 +209,45 @@ pub async fn is_minecraft_server(ctx: &Context, msg: &Message) -> Result<(), Rea
     }
 }
 
+/// Passes for the [admins][is_admin], and for the members with the guild's
+/// [`BUG_REPORTER_ROLE`] when it is set
+#[check]
+#[name = "is_bug_reporter"]
+pub async fn is_bug_reporter(ctx: &Context, msg: &Message) -> Result<(), Reason> {
+    let guild_id = msg.guild_id.unwrap_or_default();
+    if is_admin_user(ctx, guild_id, msg.author.id).await {
+        return Ok(());
+    }
+    let role_id = match get_setting(ctx, guild_id, BUG_REPORTER_ROLE)
+        .await
+        .and_then(|role_id| role_id.parse().ok())
+    {
+        Some(role_id) => RoleId(role_id),
+        None => {
+            return Err(Reason::User(
+                "You need to be a server admin to use this command!".into(),
+            ))
+        }
+    };
+    if msg
+        .author
+        .has_role(ctx, guild_id, role_id)
+        .await
+        .unwrap_or_default()
+    {
+        Ok(())
+    } else {
+        let role_name = role_id
+            .to_role_cached(ctx)
+            .map(|role| role.name)
+            .unwrap_or_else(|| role_id.to_string());
+        Err(Reason::User(format!(
+            "You need the {} role to report bugs!",
+            role_name
+        )))
+    }
+}
+
 #[check]
 #[name = "is_lotr_discord"]
 pub async fn is_lotr_discord(_: &Context, msg: &Message) -> Result<(), Reason> {
@@ -168,6 +260,16 @@ pub async fn is_lotr_discord(_: &Context, msg: &Message) -> Result<(), Reason> {
     }
 }
 
+#[check]
+#[name = "not_in_maintenance"]
+pub async fn not_in_maintenance(ctx: &Context, _: &Message) -> Result<(), Reason> {
+    if in_maintenance(ctx).await {
+        Err(Reason::User("The bot is in maintenance mode.".into()))
+    } else {
+        Ok(())
+    }
+}
+
 #[hook]
 pub async fn dispatch_error_hook(
     ctx: &Context,
@@ -189,8 +291,10 @@ pub async fn dispatch_error_hook(
                     )
                     .await
                     {
-                        (Err(_), _) | (_, Err(_)) => println!("Error sending failure message"),
-                        _ => (),
+                        (Ok(reply), Ok(_)) => {
+                            schedule_failure_deletion(ctx, msg.guild_id, reply).await
+                        }
+                        _ => println!("Error sending failure message"),
                     }
                 }
                 Reason::UserAndLog { user, log } => {
@@ -211,6 +315,18 @@ pub async fn dispatch_error_hook(
                 _ => println!("(Unknown reason)"),
             }
         }
+        DispatchError::OnlyForOwners => {
+            println!(
+                "=== CHECK FAILED ===\nOwner-only command {} used by {:?}",
+                command_name, msg.author.id
+            );
+            if let Err(e) = msg
+                .reply(ctx, "Only the bot owner can use this command!")
+                .await
+            {
+                println!("Error sending owner-only warning: {:?}", e);
+            }
+        }
         DispatchError::OnlyForGuilds => {
             if let Err(e) = msg
                 .reply(ctx, "This command cannot be executed in DMs!")
@@ -222,7 +338,13 @@ pub async fn dispatch_error_hook(
         DispatchError::Ratelimited(rate_limit_info) => {
             if rate_limit_info.is_first_try {
                 if let Err(e) = msg
-                    .reply(ctx, "Wait a few seconds before using this command again!")
+                    .reply(
+                        ctx,
+                        format!(
+                            "Wait {} seconds before using this command again!",
+                            rate_limit_info.rate_limit.as_secs().max(1)
+                        ),
+                    )
                     .await
                 {
                     println!("Error sending ratelimited warning: {:?}", e)
@@ -234,6 +356,114 @@ pub async fn dispatch_error_hook(
     println!("=== END ===");
 }
 
+/// Commands turned off with the `DISABLED_COMMANDS` environment variable, or
+/// all the commands missing from `ENABLED_COMMANDS` when it is set.
+///
+/// Both variables are comma-separated lists of command names as declared in
+/// the code, e.g. `online,bug_status`: subcommands are gated by their own
+/// name. They are read once at startup.
+#[derive(Debug, Default)]
+pub struct CommandGate {
+    enabled: Option<HashSet<String>>,
+    disabled: HashSet<String>,
+}
+
+impl TypeMapKey for CommandGate {
+    type Value = Self;
+}
+
+impl CommandGate {
+    pub fn from_env() -> Self {
+        let gate = Self::new(
+            std::env::var("ENABLED_COMMANDS").ok().as_deref(),
+            std::env::var("DISABLED_COMMANDS").ok().as_deref(),
+        );
+        if let Some(enabled) = &gate.enabled {
+            println!("Only enabling the commands {:?}", enabled);
+        }
+        if !gate.disabled.is_empty() {
+            println!("Disabling the commands {:?}", gate.disabled);
+        }
+        gate
+    }
+
+    fn new(enabled: Option<&str>, disabled: Option<&str>) -> Self {
+        fn parse_list(list: &str) -> HashSet<String> {
+            list.split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_lowercase)
+                .collect()
+        }
+
+        Self {
+            enabled: enabled.map(parse_list),
+            disabled: disabled.map(parse_list).unwrap_or_default(),
+        }
+    }
+
+    pub fn is_enabled(&self, command_name: &str) -> bool {
+        let command_name = command_name.to_lowercase();
+        if self.disabled.contains(&command_name) {
+            return false;
+        }
+        match &self.enabled {
+            Some(enabled) => enabled.contains(&command_name),
+            None => true,
+        }
+    }
+}
+
+/// Maintenance mode, toggled by the owner with
+/// [`!maintenance`][crate::commands::admin::maintenance] while the database
+/// is migrated. The commands that only read keep working.
+pub struct Maintenance;
+
+impl TypeMapKey for Maintenance {
+    type Value = bool;
+}
+
+pub async fn in_maintenance(ctx: &Context) -> bool {
+    ctx.data
+        .read()
+        .await
+        .get::<Maintenance>()
+        .copied()
+        .unwrap_or_default()
+}
+
+#[hook]
+pub async fn before_hook(ctx: &Context, msg: &Message, command_name: &str) -> bool {
+    let enabled = match ctx.data.read().await.get::<CommandGate>() {
+        Some(gate) => gate.is_enabled(command_name),
+        None => true,
+    };
+    let refusal = if !enabled {
+        println!(
+            "Disabled command {} used by {:?}",
+            command_name, msg.author.id
+        );
+        "This command is temporarily disabled."
+    } else {
+        return true;
+    };
+    match join(
+        msg.reply(ctx, refusal),
+        msg.react(ctx, ReactionType::from('❌')),
+    )
+    .await
+    {
+        (Ok(reply), Ok(_)) => schedule_failure_deletion(ctx, msg.guild_id, reply).await,
+        _ => println!("Error sending disabled command message"),
+    }
+    false
+}
+
+#[hook]
+pub async fn not_owner_hook(_: &Context, msg: &Message) -> bool {
+    msg.author.id != OWNER_ID
+}
+
 #[hook]
 pub async fn after_hook(
     ctx: &Context,
@@ -241,6 +471,8 @@ pub async fn after_hook(
     cmd_name: &str,
     error: Result<(), CommandError>,
 ) {
+    crate::metrics::record_command(cmd_name, error.is_ok());
+
     if let Err(why) = error {
         println!(
             "=== ERROR REPORT ===
@@ -274,5 +506,46 @@ Content: {}
             },
             msg.content,
         );
+        crate::error_log::report_error(ctx, cmd_name, msg.guild_id, &msg.author, &why).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandGate;
+    use crate::commands::bug_reports::{BUG_COMMAND, BUG_STATUS_COMMAND, TRACK_COMMAND};
+    use crate::commands::servers::{ONLINE_COMMAND, SET_IP_COMMAND};
+    use serenity::framework::standard::Command;
+
+    #[test]
+    fn test_command_gate() {
+        let gate = CommandGate::new(None, None);
+        assert!(gate.is_enabled("online"));
+
+        let gate = CommandGate::new(None, Some("online, Bug_Status,,"));
+        assert!(!gate.is_enabled("online"));
+        assert!(!gate.is_enabled("bug_status"));
+        assert!(gate.is_enabled("bug"));
+
+        let gate = CommandGate::new(Some("help,online"), Some("online"));
+        assert!(gate.is_enabled("help"));
+        assert!(!gate.is_enabled("online"));
+        assert!(!gate.is_enabled("track"));
+    }
+
+    #[test]
+    fn test_write_commands_check_maintenance() {
+        let in_maintenance = |command: &Command| {
+            command
+                .options
+                .checks
+                .iter()
+                .any(|check| check.name == "not_in_maintenance")
+        };
+        assert!(in_maintenance(&TRACK_COMMAND));
+        assert!(in_maintenance(&BUG_STATUS_COMMAND));
+        assert!(in_maintenance(&SET_IP_COMMAND));
+        assert!(!in_maintenance(&BUG_COMMAND));
+        assert!(!in_maintenance(&ONLINE_COMMAND));
     }
 }
@@ -18,16 +18,33 @@
 //! prefix passed in as argument.
 //! - [`!admin add`][add] adds a new admin to the database.
 //! - [`!admin remove`][remove] removes a bot admin.
+//! - [`!admin revoke`][admin_revoke] ends a temporary admin grant early.
 //! - [`!blacklist`][blacklist] displays the blacklist, or adds the mentionned
 //! channel or users to the blacklist.
 //! - [`!announce`][announce] allows bot admin to post messages as the bot,
 //! useful for official announcements.
+//! - [`!autodelete`][autodelete] displays or toggles the automatic deletion of
+//! the bot's error messages.
+//! - [`!locale`][locale] displays or changes the language of the bot's most
+//! common responses.
+//! - [`!settings`][settings] displays all the settings of the server, or
+//! changes one of them.
+//! - [`!perms`][perms] lists the permissions the bot is missing in the
+//! current channel.
 //!
 //! # Owner-only commands
+//! - [`!admin grant`][admin_grant] makes a user bot admin for a number of
+//! minutes, e.g. for an event.
 //! - [`!floppadmin`][floppadmin] allows the owner to give access to the floppa
 //! database.
 //! - [`!listguilds`][listguilds] allows the owner to get a list of guilds
 //! the bot has been invited in.
+//! - [`!maintenance`][maintenance] displays or toggles the maintenance mode,
+//! which refuses the commands that write to the database.
+//! - [`!presence`][presence] changes the activity shown by the bot.
+//! - [`!errorlog`][errorlog] displays or changes the channel where unhandled
+//! errors are reported.
+//! - [`!doctor`][doctor] checks the bot's configuration.
 //!
 //! # About the blacklist
 //!
@@ -36,20 +53,37 @@
 use serenity::client::Context;
 use serenity::framework::standard::{macros::command, Args, CommandResult};
 use serenity::model::prelude::*;
+use serenity::prelude::TypeMapKey;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::check::*;
-use crate::constants::{BOT_ID, OWNER_ID};
+use crate::commands::servers::remove_status_board;
+use crate::constants::{
+    BOT_ID, DEFAULT_ACTIVITY, EOA_DISCORD, MAX_ADMIN_GRANT_MINUTES, OWNER_ID,
+    PRESENCE_REFRESH_INTERVAL, SHUTDOWN_TIMEOUT,
+};
 use crate::database::{
-    admin_data::{add_admin, get_admins, remove_admin},
+    admin_data::{add_admin, get_admins, grant_admin, remove_admin, revoke_admin},
     blacklist::{get_blacklist, update_blacklist},
-    config::{get_prefix, set_prefix, PrefixCache},
+    bug_reports::get_bug_statistics,
+    config::{delete_minecraft_ip, delete_minecraft_servers, get_prefix, set_prefix, PrefixCache},
     floppa::is_floppadmin,
+    settings::{
+        get_all_settings, get_bool_setting, remove_setting, set_setting, setting_key,
+        validate_setting, AUTO_DELETE_FAILURES, DISABLE_IP_PERSISTENCE, EDITABLE_SETTINGS,
+    },
+    DbError,
 };
-use crate::utils::NotInGuild;
-use crate::{failure, is_admin, success};
+use crate::error_log::ErrorLog;
+use crate::i18n::{get_locale, LOCALE, LOCALES};
+use crate::shutdown::get_shutdown;
+use crate::utils::{discord_timestamp, NotInGuild};
+use crate::{db_failure, failure, is_admin, success, t};
 
 #[command]
-#[checks(is_admin)]
+#[description = "Display or change the bot prefix for this server"]
+#[usage = "[new prefix]"]
+#[checks(is_admin, not_in_maintenance)]
 #[only_in(guilds)]
 #[sub_commands(cache)]
 pub async fn prefix(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
@@ -80,6 +114,310 @@ pub async fn prefix(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 }
 
 #[command]
+#[description = "Display or toggle the deletion of error messages"]
+#[usage = "[on|off]"]
+#[checks(is_admin, not_in_maintenance)]
+#[only_in(guilds)]
+pub async fn autodelete(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+    let enabled = match args.single::<String>().as_deref() {
+        Err(_) => {
+            let enabled = get_bool_setting(ctx, server_id, AUTO_DELETE_FAILURES).await;
+            msg.reply(
+                ctx,
+                format!(
+                    "Error messages are {} on this server",
+                    if enabled {
+                        "deleted after a few seconds"
+                    } else {
+                        "kept"
+                    }
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+        Ok("on" | "true" | "enable") => true,
+        Ok("off" | "false" | "disable") => false,
+        Ok(_) => {
+            failure!(ctx, msg, "The argument must be either `on` or `off`!");
+            return Ok(());
+        }
+    };
+
+    set_setting(ctx, server_id, AUTO_DELETE_FAILURES, &enabled.to_string()).await?;
+    success!(
+        ctx,
+        msg,
+        "Error messages will {} on this server",
+        if enabled {
+            "now be deleted after a few seconds"
+        } else {
+            "no longer be deleted"
+        }
+    );
+    Ok(())
+}
+
+#[command]
+#[description = "Display or change the language of the bot"]
+#[usage = "[language]"]
+#[checks(is_admin, not_in_maintenance)]
+#[only_in(guilds)]
+#[aliases("language")]
+pub async fn locale(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+    match args.single::<String>() {
+        Err(_) => {
+            msg.reply(
+                ctx,
+                format!(
+                    "The bot language here is `{}`. Available languages: `{}`",
+                    get_locale(ctx, msg.guild_id).await,
+                    LOCALES.join("`, `")
+                ),
+            )
+            .await?;
+        }
+        Ok(locale) => {
+            let locale = locale.to_lowercase();
+            if LOCALES.contains(&locale.as_str()) {
+                set_setting(ctx, server_id, LOCALE, &locale).await?;
+                success!(ctx, msg, "Set the bot language to `{}`", locale);
+            } else {
+                failure!(
+                    ctx,
+                    msg,
+                    "Unknown language `{}`. Available languages: `{}`",
+                    locale,
+                    LOCALES.join("`, `")
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Display or change the server settings"]
+#[usage = "[key] [value|reset]"]
+#[checks(is_admin, not_in_maintenance)]
+#[only_in(guilds)]
+#[aliases("setting")]
+pub async fn settings(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    let key = match args.single::<String>() {
+        Ok(key) => key,
+        Err(_) => {
+            let settings = match get_all_settings(ctx, server_id).await {
+                Some(settings) => settings,
+                None => {
+                    failure!(ctx, msg, t!(ctx, msg, "db.unavailable"));
+                    return Ok(());
+                }
+            };
+            let mut others: Vec<&String> = settings
+                .keys()
+                .filter(|key| !EDITABLE_SETTINGS.contains(&key.as_str()))
+                .collect();
+            others.sort_unstable();
+
+            let description = EDITABLE_SETTINGS
+                .into_iter()
+                .chain(others.into_iter().map(String::as_str))
+                .map(|key| match settings.get(key) {
+                    Some(value) => format!("`{}`: `{}`", key, value.replace('`', "'")),
+                    None => format!("`{}`: *default*", key),
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            msg.channel_id
+                .send_message(ctx, |m| {
+                    m.embed(|e| {
+                        e.title("Server settings");
+                        e.description(description);
+                        e.footer(|f| f.text("!settings <key> [<value> | reset]"))
+                    })
+                    .reference_message(msg)
+                })
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let key = match setting_key(&key) {
+        Some(key) => key,
+        None => {
+            failure!(
+                ctx,
+                msg,
+                "Unknown setting `{}`. Valid settings: `{}`, `status_colour.<status>`, \
+`status_marker.<status>`",
+                key,
+                EDITABLE_SETTINGS.join("`, `")
+            );
+            return Ok(());
+        }
+    };
+
+    match args.rest().trim() {
+        "" => {
+            let value = get_all_settings(ctx, server_id)
+                .await
+                .and_then(|mut settings| settings.remove(&key));
+            let reply = match value {
+                Some(value) => format!("`{}` is set to `{}`", key, value),
+                None => format!("`{}` is not set, the default is used", key),
+            };
+            msg.reply(ctx, reply).await?;
+        }
+        "reset" | "default" => {
+            remove_setting(ctx, server_id, &key).await?;
+            success!(ctx, msg, "Reset `{}` to its default value", key);
+        }
+        value => match validate_setting(&key, value) {
+            Ok(value) => {
+                let mut removed = Vec::new();
+                // the IPs stored until now would otherwise still be shown and
+                // polled, they are removed before the setting is stored so that
+                // a failure leaves it unset
+                if key == DISABLE_IP_PERSISTENCE && value == "true" {
+                    match delete_minecraft_ip(ctx, server_id).await {
+                        Ok(()) => removed.push("the stored server IP"),
+                        Err(DbError::NotFound) => (),
+                        Err(e) => {
+                            db_failure!(ctx, msg, e);
+                            return Err(e.into());
+                        }
+                    }
+                    match delete_minecraft_servers(ctx, server_id).await {
+                        Ok(()) => removed.push("the server list"),
+                        Err(DbError::NotFound) => (),
+                        Err(e) => {
+                            db_failure!(ctx, msg, e);
+                            return Err(e.into());
+                        }
+                    }
+                    if remove_status_board(ctx, server_id).await? {
+                        removed.push("the status board");
+                    }
+                }
+                set_setting(ctx, server_id, &key, &value).await?;
+                let mut reply = format!("Set `{}` to `{}`", key, value);
+                if !removed.is_empty() {
+                    reply.push_str(&format!(", and removed {}", removed.join(", ")));
+                }
+                success!(ctx, msg, reply);
+            }
+            Err(reason) => {
+                failure!(ctx, msg, "The value of `{}` {}.", key, reason);
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Permissions the bot needs in a channel, with what breaks without them
+const CHANNEL_PERMISSIONS: [(Permissions, &str, &str); 7] = [
+    (
+        Permissions::SEND_MESSAGES,
+        "Send Messages",
+        "the bot cannot reply at all",
+    ),
+    (
+        Permissions::EMBED_LINKS,
+        "Embed Links",
+        "bug reports, server status and help embeds are not shown",
+    ),
+    (
+        Permissions::ADD_REACTIONS,
+        "Add Reactions",
+        "no ✅/❌ reactions on commands",
+    ),
+    (
+        Permissions::USE_EXTERNAL_EMOJIS,
+        "Use External Emojis",
+        "no termite reaction on bug commands",
+    ),
+    (
+        Permissions::READ_MESSAGE_HISTORY,
+        "Read Message History",
+        "the bot cannot reply to messages",
+    ),
+    (
+        Permissions::ATTACH_FILES,
+        "Attach Files",
+        "announcement and custom command files are not sent",
+    ),
+    (
+        Permissions::SEND_MESSAGES_IN_THREADS,
+        "Send Messages in Threads",
+        "the bot cannot reply in threads",
+    ),
+];
+
+#[command]
+#[description = "List the permissions the bot is missing in this channel"]
+#[checks(is_admin)]
+#[only_in(guilds)]
+#[aliases("permissions")]
+pub async fn perms(ctx: &Context, msg: &Message) -> CommandResult {
+    let channel = msg.channel(ctx).await?.guild().ok_or(NotInGuild)?;
+    // threads use the permissions of their parent channel
+    let channel = match channel
+        .parent_id
+        .filter(|_| channel.thread_metadata.is_some())
+    {
+        Some(parent_id) => parent_id.to_channel(ctx).await?.guild().ok_or(NotInGuild)?,
+        None => channel,
+    };
+
+    let permissions = match channel.permissions_for_user(ctx, BOT_ID) {
+        Ok(permissions) => permissions,
+        Err(e) => {
+            println!(
+                "=== ERROR ===\nCould not compute the bot permissions in {}: {}\n=== END ===",
+                channel.id, e
+            );
+            failure!(
+                ctx,
+                msg,
+                "Could not compute the bot's permissions, try again in a moment."
+            );
+            return Ok(());
+        }
+    };
+
+    let missing: Vec<String> = CHANNEL_PERMISSIONS
+        .iter()
+        .filter(|(permission, _, _)| !permissions.contains(*permission))
+        .map(|(_, name, effect)| format!("❌ **{}**: {}", name, effect))
+        .collect();
+
+    // plain text, since the bot may not be allowed to send embeds
+    let content = if missing.is_empty() {
+        format!(
+            "✅ The bot has all the permissions it needs in {}.",
+            channel.mention()
+        )
+    } else {
+        format!(
+            "The bot is missing some permissions in {}:\n{}",
+            channel.mention(),
+            missing.join("\n")
+        )
+    };
+
+    if msg.reply(ctx, &content).await.is_err() {
+        // most likely because of the missing permissions
+        msg.author.dm(ctx, |m| m.content(&content)).await?;
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Log the prefix cache to the console"]
 #[owners_only]
 #[checks(is_admin)]
 async fn cache(ctx: &Context) -> CommandResult {
@@ -92,16 +430,30 @@ async fn cache(ctx: &Context) -> CommandResult {
 }
 
 #[command]
+#[description = "List, add or remove the bot admins"]
+#[usage = "[add|remove|grant|revoke <user>]"]
 #[only_in(guilds)]
 #[checks(allowed_blacklist)]
-#[sub_commands("add", "remove")]
+#[sub_commands("add", "remove", admin_grant, admin_revoke)]
 #[aliases("admins")]
 pub async fn admin(ctx: &Context, msg: &Message) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
 
     let admins = get_admins(ctx, server_id).await.unwrap_or_else(Vec::new);
 
-    let mut user_names: Vec<String> = admins.iter().map(|&id| id.mention().to_string()).collect();
+    let mut user_names: Vec<String> = admins
+        .iter()
+        .map(|&(id, expires_at)| match expires_at {
+            Some(expires_at) => {
+                format!(
+                    "{} (until {})",
+                    id.mention(),
+                    discord_timestamp(&expires_at, 'R')
+                )
+            }
+            None => id.mention().to_string(),
+        })
+        .collect();
     user_names.push(OWNER_ID.mention().to_string());
 
     let guild_name = server_id.to_partial_guild(ctx).await?.name;
@@ -118,8 +470,9 @@ pub async fn admin(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Make a user a bot admin"]
 #[only_in(guilds)]
-#[checks(is_admin)]
+#[checks(is_permanent_admin, not_in_maintenance)]
 pub async fn add(ctx: &Context, msg: &Message) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
 
@@ -141,8 +494,9 @@ pub async fn add(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Remove a bot admin"]
 #[only_in(guilds)]
-#[checks(is_admin)]
+#[checks(is_permanent_admin, not_in_maintenance)]
 pub async fn remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
 
@@ -169,8 +523,97 @@ pub async fn remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 }
 
 #[command]
+#[description = "Make a user a bot admin for some minutes"]
+#[checks(not_in_maintenance)]
 #[only_in(guilds)]
-#[checks(is_admin)]
+#[owners_only]
+#[aliases("grant")]
+pub async fn admin_grant(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    let user = match msg.mentions.iter().find(|&user| user.id != BOT_ID) {
+        Some(user) => user,
+        None => {
+            failure!(ctx, msg, "Usage: `!admin grant <user mention> <minutes>`");
+            return Ok(());
+        }
+    };
+    // the mention fails to parse
+    let minutes = match args.iter::<u32>().find_map(Result::ok) {
+        Some(minutes) if (1..=MAX_ADMIN_GRANT_MINUTES).contains(&minutes) => minutes,
+        _ => {
+            failure!(
+                ctx,
+                msg,
+                "You must specify a number of minutes between 1 and {}:  \
+`!admin grant <user mention> <minutes>`",
+                MAX_ADMIN_GRANT_MINUTES
+            );
+            return Ok(());
+        }
+    };
+
+    if user.id == OWNER_ID
+        || get_admins(ctx, server_id)
+            .await
+            .unwrap_or_default()
+            .contains(&(user.id, None))
+    {
+        failure!(
+            ctx,
+            msg,
+            "This user is already a permanent bot admin on this server!"
+        );
+        return Ok(());
+    }
+
+    let until = chrono::Utc::now() + chrono::Duration::minutes(minutes.into());
+    grant_admin(ctx, server_id, user.id, until.naive_utc()).await?;
+    success!(
+        ctx,
+        msg,
+        "{} is bot admin on this server until {} ({}).",
+        user.mention(),
+        discord_timestamp(&until, 'f'),
+        discord_timestamp(&until, 'R')
+    );
+    Ok(())
+}
+
+#[command]
+#[description = "Revoke a temporary bot admin grant"]
+#[only_in(guilds)]
+#[checks(is_permanent_admin, not_in_maintenance)]
+#[aliases("revoke")]
+pub async fn admin_revoke(ctx: &Context, msg: &Message) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    if let Some(user) = msg.mentions.iter().find(|&user| user.id != BOT_ID) {
+        if revoke_admin(ctx, server_id, user.id).await? {
+            success!(ctx, msg);
+        } else {
+            failure!(
+                ctx,
+                msg,
+                "This user has no temporary admin grant on this server! \
+Use  `!admin remove`  for permanent bot admins."
+            );
+        }
+    } else {
+        failure!(
+            ctx,
+            msg,
+            "Mention a user whose temporary admin grant you wish to end."
+        );
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Display or change the blacklist"]
+#[usage = "[user or channel]"]
+#[only_in(guilds)]
+#[checks(is_admin, not_in_maintenance)]
 pub async fn blacklist(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
     if args.is_empty() && msg.mentions.is_empty() {
@@ -207,6 +650,9 @@ pub async fn blacklist(ctx: &Context, msg: &Message, args: Args) -> CommandResul
 }
 
 #[command]
+#[description = "Allow a user to add floppa pictures"]
+#[usage = "<user>"]
+#[checks(not_in_maintenance)]
 #[owners_only]
 pub async fn floppadmin(ctx: &Context, msg: &Message) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
@@ -237,6 +683,7 @@ pub async fn floppadmin(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "List the guilds the bot is in"]
 #[only_in(dms)]
 #[owners_only]
 #[aliases("guilds")]
@@ -277,10 +724,259 @@ pub async fn listguilds(ctx: &Context) -> CommandResult {
 }
 
 #[command]
+#[description = "Display or toggle the maintenance mode"]
+#[usage = "[on|off]"]
+#[owners_only]
+pub async fn maintenance(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let enabled = match args.single::<String>().as_deref() {
+        Err(_) => {
+            msg.reply(
+                ctx,
+                if in_maintenance(ctx).await {
+                    "The bot is in maintenance mode."
+                } else {
+                    "The bot is not in maintenance mode."
+                },
+            )
+            .await?;
+            return Ok(());
+        }
+        Ok("on" | "true" | "enable") => true,
+        Ok("off" | "false" | "disable") => false,
+        Ok(_) => {
+            failure!(ctx, msg, "The argument must be either `on` or `off`!");
+            return Ok(());
+        }
+    };
+
+    ctx.data.write().await.insert::<Maintenance>(enabled);
+    println!(
+        "=== MAINTENANCE MODE {} ===",
+        if enabled { "ON" } else { "OFF" }
+    );
+    success!(
+        ctx,
+        msg,
+        "Maintenance mode {}",
+        if enabled {
+            "enabled: the commands that write to the database are refused."
+        } else {
+            "disabled."
+        }
+    );
+    Ok(())
+}
+
+#[command]
+#[description = "Display or change the error log channel"]
+#[usage = "[channel|off]"]
+#[owners_only]
+pub async fn errorlog(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let error_log = ctx
+        .data
+        .read()
+        .await
+        .get::<ErrorLog>()
+        .expect("Expected an error log in the type map")
+        .clone();
+
+    let channel = match args.single::<String>().as_deref() {
+        Err(_) => {
+            msg.reply(
+                ctx,
+                match error_log.channel() {
+                    Some(channel) => {
+                        format!("Unhandled errors are reported in {}.", channel.mention())
+                    }
+                    None => "Unhandled errors are not reported in a channel.".to_string(),
+                },
+            )
+            .await?;
+            return Ok(());
+        }
+        Ok("off" | "none" | "disable") => None,
+        Ok(channel) => {
+            match serenity::utils::parse_channel(channel).or_else(|| channel.parse().ok()) {
+                Some(channel) => Some(ChannelId(channel)),
+                None => {
+                    failure!(ctx, msg, "The argument must be a channel, or `off`!");
+                    return Ok(());
+                }
+            }
+        }
+    };
+
+    error_log.set_channel(channel);
+    match channel {
+        Some(channel) => success!(
+            ctx,
+            msg,
+            "Unhandled errors will be reported in {} until the next restart. Set \
+`ERROR_LOG_CHANNEL` to keep it.",
+            channel.mention()
+        ),
+        None => success!(
+            ctx,
+            msg,
+            "Unhandled errors won't be reported in a channel anymore."
+        ),
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Check the bot configuration"]
+#[owners_only]
+pub async fn doctor(ctx: &Context, msg: &Message) -> CommandResult {
+    msg.channel_id.broadcast_typing(ctx).await?;
+    let results = crate::doctor::run_checks(ctx).await;
+    let passed = results.iter().all(|result| result.passed);
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.colour(if passed {
+                    serenity::utils::colours::branding::GREEN
+                } else {
+                    serenity::utils::colours::branding::RED
+                });
+                e.title(if passed {
+                    "All the checks passed"
+                } else {
+                    "Some checks failed"
+                });
+                for result in &results {
+                    e.field(
+                        format!(
+                            "{} {}",
+                            if result.passed { "✅" } else { "❌" },
+                            result.name
+                        ),
+                        &result.details,
+                        false,
+                    );
+                }
+                e
+            })
+            .reference_message(msg)
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Activity shown by the bot, chosen with [`!presence`][presence]. It is
+/// applied again on every ready event, so that it survives reconnections.
+#[derive(Debug, Clone)]
+pub enum Presence {
+    Default,
+    Custom(ActivityType, String),
+    /// The number of open bugs, refreshed every [`PRESENCE_REFRESH_INTERVAL`]
+    OpenBugs,
+}
+
+impl TypeMapKey for Presence {
+    type Value = Self;
+}
+
+/// Sets the bot's activity to the chosen [`Presence`]
+pub async fn apply_presence(ctx: &Context) {
+    let presence = ctx
+        .data
+        .read()
+        .await
+        .get::<Presence>()
+        .cloned()
+        .unwrap_or(Presence::Default);
+    let activity = match presence {
+        Presence::Default => Activity::playing(DEFAULT_ACTIVITY),
+        Presence::Custom(ActivityType::Watching, text) => Activity::watching(text),
+        Presence::Custom(ActivityType::Listening, text) => Activity::listening(text),
+        Presence::Custom(_, text) => Activity::playing(text),
+        Presence::OpenBugs => match get_bug_statistics(ctx, EOA_DISCORD).await {
+            Some(counts) => Activity::watching(format!(
+                "{} open bugs",
+                counts.low + counts.medium + counts.high + counts.critical
+            )),
+            // keep the current count until the database is back
+            None => return,
+        },
+    };
+    ctx.set_activity(activity).await;
+}
+
+static PRESENCE_REFRESH_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Refreshes the open bug count of the [`Presence::OpenBugs`] activity
+pub fn start_presence_refresh(ctx: Context) {
+    if PRESENCE_REFRESH_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let shutdown = get_shutdown(&ctx).await;
+        let mut interval = tokio::time::interval(PRESENCE_REFRESH_INTERVAL);
+        while let Some(_work) = shutdown.tick(&mut interval).await {
+            if matches!(
+                ctx.data.read().await.get::<Presence>(),
+                Some(Presence::OpenBugs)
+            ) {
+                apply_presence(&ctx).await;
+            }
+        }
+    });
+}
+
+#[command]
+#[description = "Change the activity shown by the bot"]
+#[usage = "[playing|watching|listening] <text> | bugs | reset"]
+#[owners_only]
+pub async fn presence(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let presence = match args.current().map(str::to_lowercase).as_deref() {
+        None => {
+            failure!(
+                ctx,
+                msg,
+                "Usage: `!presence [playing|watching|listening] <text>`, `!presence bugs` or \
+`!presence reset`"
+            );
+            return Ok(());
+        }
+        Some("reset" | "default") => Presence::Default,
+        Some("bugs") if args.len() == 1 => Presence::OpenBugs,
+        Some(kind @ ("playing" | "watching" | "listening")) if args.len() > 1 => {
+            let kind = match kind {
+                "watching" => ActivityType::Watching,
+                "listening" => ActivityType::Listening,
+                _ => ActivityType::Playing,
+            };
+            args.advance();
+            Presence::Custom(kind, args.rest().to_string())
+        }
+        Some(_) => Presence::Custom(ActivityType::Playing, args.rest().to_string()),
+    };
+    if let Presence::Custom(_, text) = &presence {
+        if text.chars().count() > 128 {
+            failure!(ctx, msg, "The activity must be 128 characters or less.");
+            return Ok(());
+        }
+    }
+
+    ctx.data.write().await.insert::<Presence>(presence);
+    apply_presence(ctx).await;
+    success!(ctx, msg, "Updated the bot's activity.");
+    Ok(())
+}
+
+#[command]
+#[description = "Shut the bot down gracefully"]
 #[only_in(dms)]
 #[owners_only]
 pub async fn shutdown(ctx: &Context) -> CommandResult {
     println!("=== SHUTTING DOWN CURRENT SHARD ===");
+    if !get_shutdown(ctx).await.shutdown(SHUTDOWN_TIMEOUT).await {
+        println!("Some background work did not complete in time");
+    }
     ctx.shard.shutdown_clean();
     Ok(())
 }
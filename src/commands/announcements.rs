@@ -85,6 +85,8 @@ async fn announcement_error_handler(
 }
 
 #[command]
+#[description = "Make the bot send a message to a channel"]
+#[usage = "<channel> <json message>"]
 #[only_in(guilds)]
 #[checks(is_admin)]
 #[sub_commands("edit")]
@@ -142,6 +144,7 @@ Content: {:?}
 }
 
 #[command]
+#[description = "Edit a message posted by the bot"]
 #[checks(is_admin)]
 #[only_in(guilds)]
 pub async fn edit(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
@@ -1,25 +1,66 @@
+use chrono::{NaiveDate, Utc};
 use serenity::client::Context;
-use serenity::collector::CollectComponentInteraction;
-use serenity::framework::standard::{macros::command, Args, CommandResult};
+use serenity::collector::{CollectComponentInteraction, CollectReply};
+use serenity::framework::standard::{
+    macros::command, Args, CommandError, CommandResult, Delimiter,
+};
+use serenity::http::error::{DiscordJsonError, ErrorResponse};
 use serenity::model::{
-    interactions::message_component::{ButtonStyle, MessageComponentInteraction},
+    interactions::{
+        application_command::{
+            ApplicationCommandInteraction, ApplicationCommandInteractionDataOption,
+            ApplicationCommandOptionType,
+        },
+        message_component::{ButtonStyle, MessageComponentInteraction},
+    },
     prelude::*,
 };
 use serenity::prelude::*;
+use serenity::utils::parse_channel;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use crate::check::*;
-use crate::constants::{EOA_DISCORD, MANAGE_BOT_PERMS, OWNER_ID};
-use crate::database::admin_data::is_admin_function;
+use crate::constants::{
+    AUTO_CLOSE_INTERVAL, BOT_ID, BUG_DISCUSSION_MESSAGES, BUG_INDEX_LIMIT, BUG_INDEX_TITLE_WIDTH,
+    DIGEST_INTERVAL, EOA_DISCORD, LEADERBOARD_SIZE, LINK_AUDIT_DELAY, MAX_CSV_FILE_SIZE,
+    MAX_LABEL_LENGTH, MAX_LOOKBACK_DAYS, MAX_WEBHOOK_NAME_LENGTH, OWNER_ID, SNOOZE_CHECK_INTERVAL,
+};
 use crate::database::bug_reports::{
-    add_bug_report, add_link, add_notified_user, change_bug_status, change_category, change_title,
-    get_bug_from_id, get_bug_list, get_bug_statistics, get_notifications_for_user,
-    get_notified_users, is_notified_user, remove_link, BugCategory, BugOrder, BugStatus,
+    add_bug_report, add_label, add_label_subscription, add_link, add_notified_user, archive_bugs,
+    change_bug_status, change_category, change_summary, change_title, close_resolved_bug,
+    count_guild_bugs, get_all_links, get_bug_digest, get_bug_from_id, get_bug_list,
+    get_bug_snapshot, get_bug_statistics, get_fixed_bugs, get_label_counts,
+    get_label_subscriptions, get_notifications_for_user, get_notified_users, get_open_label_counts,
+    get_recent_status_changes, get_resolved_bugs_before, get_resolved_bugs_since, get_stale_bugs,
+    get_top_reporters, get_webhook, get_webhooks, import_bugs, is_notified_user, normalize_label,
+    remove_label, remove_label_subscription, remove_link, remove_webhook, reset_guild_bugs,
+    set_fixed_in, set_webhook, snooze_bug, take_expired_snoozes, triage_bug, update_saved_message,
+    BugCategory, BugListFilter, BugOrder, BugReport, BugStatus, BugWebhook, ImportedBug,
+    PartialBugReport, SavedMessage, StatusMarkers,
+};
+use crate::database::settings::{
+    get_branding, get_bug_prefix, get_guilds_with_setting, get_setting, parse_bug_id,
+    remove_setting, set_setting, status_colour, status_marker, validate_marker, validate_setting,
+    BugPrefix, ARCHIVE_BUG_THREADS, AUTO_CLOSE_DAYS, BRANDING_ICON, BRANDING_NAME, BUG_PREFIX,
+    DIGEST_CHANNEL,
+};
+use crate::database::{utc, DbError};
+use crate::shutdown::get_shutdown;
+use crate::utils::{
+    confirm, discord_timestamp, sanitize_echo, InteractionEasyResponse, ModVersion, NotInGuild,
 };
-use crate::failure;
+use crate::{db_failure, failure, get_reqwest_client, retry_discord, success, t};
 
 pub const TERMITE_EMOJI: EmojiId = EmojiId(938135367486410792);
 
+/// Discord JSON error code for a deleted channel
+pub const UNKNOWN_CHANNEL: isize = 10003;
+/// Discord JSON error code for a deleted message
+pub const UNKNOWN_MESSAGE: isize = 10008;
+/// Discord JSON error code for a deleted webhook
+const UNKNOWN_WEBHOOK: isize = 10015;
+
 macro_rules! termite {
     ($ctx:ident, $msg:ident) => {{
         $msg.react(
@@ -39,7 +80,14 @@ macro_rules! termite_success {
         termite!($ctx, $msg);
     };
     ($ctx:ident, $msg:ident, $single_message:expr) => {{
-        $msg.reply($ctx, $single_message).await?;
+        let content = $single_message;
+        $msg.channel_id
+            .send_message($ctx, |m| {
+                m.content(content)
+                    .reference_message($msg)
+                    .allowed_mentions(|a| a.empty_parse())
+            })
+            .await?;
         termite!($ctx, $msg);
     }};
     ($ctx:ident, $msg:ident, $($success:tt)*) => {{
@@ -47,35 +95,125 @@ macro_rules! termite_success {
     }};
 }
 
+macro_rules! bug_age {
+    ($bug:expr) => {{
+        let now = chrono::Utc::now();
+        format!(
+            "Reported {} ago • {} for {}",
+            crate::utils::format_duration(now.signed_duration_since($bug.timestamp)),
+            $bug.status,
+            crate::utils::format_duration(now.signed_duration_since($bug.status_since)),
+        )
+    }};
+}
+
+/// Title and description of an embed, shortened to fit in an embed field
+fn embed_summary(embed: &Embed) -> Option<String> {
+    let summary = match (&embed.title, &embed.description) {
+        (Some(title), Some(description)) => format!("**{}**\n{}", title, description),
+        (Some(text), None) | (None, Some(text)) => text.clone(),
+        (None, None) => return None,
+    };
+    if summary.chars().count() > 1024 {
+        Some(summary.chars().take(1021).chain("...".chars()).collect())
+    } else {
+        Some(summary)
+    }
+}
+
 macro_rules! create_bug_embed {
-    ($bug:expr, $linked_message:expr) => {
+    ($bug:expr, $linked_message:expr, $colour:expr, $markers:expr, $branding:expr, $prefix:expr) => {
         |e| {
-            e.author(|a| {
-                a.name("Eras of Arda Bugtracker");
-                a.icon_url(crate::constants::TERMITE_IMAGE);
-                a
-            });
-            e.colour($bug.status.colour());
+            e.author(|a| $branding.author(a));
+            e.colour($colour);
             e.title(format!(
-                "{} EoA-{}: {} [{}]",
-                $bug.status.marker(),
-                $bug.bug_id,
+                "{} {}: {} [{}]",
+                $markers.get($bug.status),
+                $prefix.id($bug.bug_id),
                 $bug.title,
                 $bug.category
             ));
+            if let Some(summary) = &$bug.summary {
+                e.description(summary);
+            }
             if let Ok(ref message) = $linked_message {
-                e.description(&message.content);
+                if $bug.summary.is_none() {
+                    e.description(&message.content);
+                }
                 if let Some(image) = message.attachments.get(0) {
                     e.image(&image.url);
                 }
+                if let Some(embed) = message.embeds.get(0) {
+                    let name = if message.embeds.len() == 1 {
+                        "Original message embed".to_string()
+                    } else {
+                        format!("Original message embeds (1/{})", message.embeds.len())
+                    };
+                    e.field(
+                        name,
+                        embed_summary(embed)
+                            .unwrap_or_else(|| format!("[See message]({})", message.link())),
+                        false,
+                    );
+                }
+                e.footer(|f| {
+                    f.text(format!(
+                        "Status: {} • Submitted by {}\n{}",
+                        $bug.status,
+                        &message.author.name,
+                        bug_age!($bug)
+                    ))
+                });
+            } else if let Some(saved) = &$bug.saved_message {
+                // the original message was deleted, or is out of reach
+                if $bug.summary.is_none() {
+                    e.description(&saved.content);
+                }
+                if let Some(url) = &saved.attachment_url {
+                    e.image(url);
+                }
                 e.footer(|f| {
                     f.text(format!(
-                        "Status: {} • Submitted by {}",
-                        $bug.status, &message.author.name
+                        "Status: {} • Submitted by {} (saved copy)\n{}",
+                        $bug.status,
+                        saved.author,
+                        bug_age!($bug)
                     ))
                 });
             } else {
-                e.footer(|f| f.text(format!("Status: {}", $bug.status)));
+                e.footer(|f| f.text(format!("Status: {}\n{}", $bug.status, bug_age!($bug))));
+            }
+            if let Some(merged_into) = $bug.merged_into {
+                e.field(
+                    "Merged",
+                    format!("Merged into {}", $prefix.id(merged_into)),
+                    false,
+                );
+            }
+            if $bug.archived {
+                e.field(
+                    "Archived",
+                    "This bug is archived, it can't be changed anymore.",
+                    false,
+                );
+            }
+            if let Some(until) = $bug.snoozed_until.filter(|until| *until > Utc::now()) {
+                e.field(
+                    "Snoozed",
+                    format!("Snoozed until {}", discord_timestamp(&until, 'R')),
+                    false,
+                );
+            }
+            if !$bug.labels.is_empty() {
+                e.field(
+                    "Labels",
+                    $bug.labels
+                        .iter()
+                        .map(|label| format!("`{}`", label))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    false,
+                );
             }
             if !$bug.links.is_empty() {
                 e.field(
@@ -95,44 +233,70 @@ macro_rules! create_bug_embed {
     };
 }
 
+/// Fetches the message a bug was reported from. Messages fetched over HTTP have
+/// no guild id, which [`Message::link`] needs for the jump URL.
+async fn get_linked_message(ctx: &Context, bug: &BugReport) -> serenity::Result<Message> {
+    let mut message = bug.channel_id.message(ctx, bug.message_id).await?;
+    message.guild_id = Some(bug.guild_id);
+    Ok(message)
+}
+
 pub async fn notify_users(
     ctx: &Context,
     bug_id: u64,
+    guild_id: GuildId,
     message: impl std::fmt::Display,
 ) -> CommandResult {
+    if notifications_paused(ctx).await {
+        return Ok(());
+    }
+
+    let (_, errors) = dispatch_notification(ctx, bug_id, guild_id, message).await?;
+    match errors.into_iter().next() {
+        Some(e) => Err(e.into()),
+        None => Ok(()),
+    }
+}
+
+/// Sends a notification to the subscribers of a bug, even if notifications
+/// are paused. Returns the number of subscribers notified, and the errors of
+/// the DMs that could not be sent.
+async fn dispatch_notification(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    message: impl std::fmt::Display,
+) -> Result<(usize, Vec<SerenityError>), CommandError> {
     let notified_users = get_notified_users(ctx, bug_id).await?;
     if notified_users.is_empty() {
-        return Ok(());
+        return Ok((0, Vec::new()));
     }
 
-    let bug = get_bug_from_id(ctx, bug_id).await?;
+    let bug = get_bug_from_id(ctx, bug_id, guild_id).await?;
 
-    let mut res = Ok(());
-    let linked_message = bug
-        .channel_id
-        .message(ctx, bug.message_id)
-        .await
-        .map(|mut m| {
-            m.guild_id = Some(EOA_DISCORD);
-            m
-        });
+    let mut sent = 0;
+    let mut errors = Vec::new();
+    let linked_message = get_linked_message(ctx, &bug).await;
     let message_link = linked_message.as_ref().map(|m| m.link()).ok();
+    let colour = bug.status.guild_colour(ctx, Some(bug.guild_id)).await;
+    let markers = StatusMarkers::of(ctx, Some(bug.guild_id)).await;
+    let branding = get_branding(ctx, Some(bug.guild_id)).await;
+    let prefix = get_bug_prefix(ctx, Some(bug.guild_id)).await;
 
     for user in notified_users {
         let channel = match user.create_dm_channel(ctx).await {
             Ok(channel) => channel,
             Err(e) => {
-                if res.is_ok() {
-                    res = Err(e.into());
-                }
+                errors.push(e);
                 continue;
             }
         };
 
-        if let Err(e) = channel
+        match channel
             .send_message(ctx, |m| {
                 m.content(format!(
-                    "**Eras of Arda Bugtracker notification {}**\n\n{}\n ",
+                    "**{} notification {}**\n\n{}\n ",
+                    branding.name,
                     ReactionType::from(EmojiIdentifier {
                         animated: false,
                         id: TERMITE_EMOJI,
@@ -140,7 +304,14 @@ pub async fn notify_users(
                     }),
                     message,
                 ))
-                .embed(create_bug_embed!(bug, linked_message))
+                .embed(create_bug_embed!(
+                    bug,
+                    linked_message,
+                    colour,
+                    markers,
+                    branding,
+                    prefix
+                ))
                 .components(|c| {
                     c.create_action_row(|a| {
                         if let Some(link) = message_link.as_ref() {
@@ -160,23 +331,33 @@ pub async fn notify_users(
             })
             .await
         {
-            if res.is_ok() {
-                res = Err(e.into());
-            }
+            Ok(_) => sent += 1,
+            Err(e) => errors.push(e),
         }
     }
 
-    res
+    Ok((sent, errors))
 }
 
 #[command]
-#[checks(is_admin, is_lotr_discord)]
+#[description = "Create a bug report from the replied message"]
+#[usage = "[legacy] [status] <bug title> [| summary]"]
+#[checks(is_bug_reporter, is_lotr_discord, not_in_maintenance)]
 #[aliases(report)]
+#[bucket = "track"]
 pub async fn track(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
     let category = args.single::<BugCategory>().unwrap_or_default();
     let status = args.single::<BugStatus>().unwrap_or_default();
 
-    let title = args.rest();
+    // an optional summary follows the title, after a ` | `: a `|` without
+    // spaces around it is part of the title
+    let mut parts = args.rest().splitn(2, " | ");
+    let title = parts.next().unwrap_or_default().trim();
+    let summary = parts
+        .next()
+        .map(str::trim)
+        .filter(|summary| !summary.is_empty());
     if title.is_empty() {
         failure!(ctx, msg, "You must provide a title for the bug report!");
         return Ok(());
@@ -189,20 +370,33 @@ pub async fn track(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
         return Ok(());
     };
 
-    let bug_id =
-        match add_bug_report(ctx, referenced_message, title.to_string(), status, category).await {
-            Ok(bug_id) => bug_id,
-            Err(e) => {
-                failure!(ctx, msg, "Could not submit the bug report!");
-                return Err(e);
-            }
-        };
+    let bug_id = match add_bug_report(
+        ctx,
+        guild_id,
+        referenced_message,
+        title.to_string(),
+        summary,
+        status,
+        category,
+    )
+    .await
+    {
+        Ok(bug_id) => bug_id,
+        Err(e) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.submit_failed"));
+            return Err(e);
+        }
+    };
 
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
     msg.channel_id
         .send_message(ctx, |m| {
             m.content(format!(
-                "Tracking bug EoA-{} (priority: `{}`) [{}]",
-                bug_id, status, category
+                "Tracking bug {}: **{}** (priority: `{}`) [{}]",
+                prefix.id(bug_id),
+                sanitize_echo(title),
+                status,
+                category
             ))
             .reference_message(referenced_message)
             .allowed_mentions(|f| f.empty_parse())
@@ -218,31 +412,94 @@ pub async fn track(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
         })
         .await?;
 
-    if let Err(e) = add_notified_user(ctx, bug_id, referenced_message.author.id).await {
+    if let Err(e) = add_notified_user(ctx, bug_id, guild_id, referenced_message.author.id).await {
         println!(
             "=== ERROR ===
-Could not subscribe bug author to bug EoA-{bug_id}
+Could not subscribe bug author to bug {}
 Error: {e}
-=== END ==="
+=== END ===",
+            prefix.id(bug_id)
         );
         return Err(e);
     }
 
+    let branding = get_branding(ctx, Some(guild_id)).await;
     notify_users(
         ctx,
         bug_id,
-        "A bug report you submitted is being tracked in the Eras of Arda bugtracker.
+        guild_id,
+        format!(
+            "A bug report you submitted is being tracked in the {} bugtracker.
 You will receive notifications when its status is changed or further information is added.",
+            branding.name
+        ),
     )
     .await
 }
 
+/// Notifies the subscribers of a bug of its status change, in the background so
+/// that the command can answer first
+fn notify_status_change(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    old_status: BugStatus,
+    new_status: BugStatus,
+) {
+    if old_status == new_status {
+        return;
+    }
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let _work = match get_shutdown(&ctx).await.track() {
+            Some(work) => work,
+            None => return,
+        };
+        let notification = format!(
+            "A bug you are subscribed to has been changed from `{}` to `{}`",
+            old_status, new_status
+        );
+        if let Err(e) = notify_users(&ctx, bug_id, guild_id, notification).await {
+            let prefix = get_bug_prefix(&ctx, Some(guild_id)).await;
+            println!(
+                "Could not notify the subscribers of {}: {}",
+                prefix.id(bug_id),
+                e
+            );
+        }
+    });
+}
+
+/// Changes the status of a bug and notifies its subscribers, returning the old
+/// status
+async fn change_status_and_notify(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    new_status: BugStatus,
+    changed_by: UserId,
+) -> Result<BugStatus, DbError> {
+    let old_status = change_bug_status(ctx, bug_id, guild_id, new_status, changed_by).await?;
+    notify_status_change(ctx, bug_id, guild_id, old_status, new_status);
+    Ok(old_status)
+}
+
 enum Either<'a> {
     Message(&'a Message),
     Interaction(&'a MessageComponentInteraction),
+    /// A [`/bug` slash command][handle_bug_slash_command]
+    Command(&'a ApplicationCommandInteraction),
 }
 
 impl<'a> Either<'a> {
+    fn guild_id(&self) -> Option<GuildId> {
+        match self {
+            Either::Message(msg) => msg.guild_id,
+            Either::Interaction(interaction) => interaction.guild_id,
+            Either::Command(command) => command.guild_id,
+        }
+    }
+
     async fn failure(&self, ctx: &Context, message: &str) -> Result<(), SerenityError> {
         match self {
             Either::Message(msg) => failure!(ctx, msg, message),
@@ -257,29 +514,181 @@ impl<'a> Either<'a> {
                     })
                     .await?;
             }
+            Either::Command(command) => {
+                command
+                    .create_interaction_response(ctx, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|d| {
+                                d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                                    .content(message)
+                            })
+                    })
+                    .await?;
+            }
         }
         Ok(())
     }
 }
 
+/// Prefix of the custom ids of the `!bugs` navigation buttons
+pub const BUGLIST_CUSTOM_ID_PREFIX: &str = "buglist__";
+
+/// Everything needed to display a page of `!bugs`.
+///
+/// The query is encoded in the custom id of the navigation buttons, so that
+/// they keep working after the command has returned, and even after a restart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BugListQuery {
+    user_id: UserId,
+    status: Option<BugStatus>,
+    category: Option<BugCategory>,
+    /// Only show the bugs reported by this user
+    reporter: Option<UserId>,
+    /// Only show the bugs with this label, never set together with `reporter`
+    label: Option<String>,
+    /// Only show the bugs whose original message had an attachment, or only
+    /// those without, never set together with `reporter` or `label`
+    attachment: Option<bool>,
+    /// Only show the snoozed bugs, never set together with `status`
+    snoozed: bool,
+    order: BugOrder,
+    limit: u32,
+    page: u32,
+}
+
+impl BugListQuery {
+    fn with_page(&self, page: u32) -> Self {
+        Self {
+            page,
+            ..self.clone()
+        }
+    }
+
+    fn to_custom_id(&self) -> String {
+        let mut custom_id = format!(
+            "{}{}__{}__{}__{}__{}__{}",
+            BUGLIST_CUSTOM_ID_PREFIX,
+            self.user_id.0,
+            self.page,
+            self.limit,
+            self.order.as_str(),
+            match self.status {
+                Some(status) => status.as_str(),
+                None if self.snoozed => "snoozed",
+                None => "all",
+            },
+            self.category.map_or("all", BugCategory::as_str),
+        );
+        // optional, so that the buttons sent before reporters were stored keep working
+        if let Some(reporter) = self.reporter {
+            custom_id.push_str(&format!("__{}", reporter.0));
+        } else if let Some(label) = &self.label {
+            custom_id.push_str(&format!("__l:{}", label));
+        } else if let Some(attachment) = self.attachment {
+            custom_id.push_str(&format!("__i:{}", u8::from(attachment)));
+        }
+        custom_id
+    }
+
+    /// Custom id of the First and Last buttons, which can point to the same
+    /// page as Previous and Next: custom ids must be unique in a message
+    fn to_jump_custom_id(&self, last: bool) -> String {
+        format!("{}#{}", self.to_custom_id(), if last { "l" } else { "f" })
+    }
+
+    fn from_custom_id(custom_id: &str) -> Option<Self> {
+        // the marker of the First and Last buttons
+        let custom_id = custom_id.split_once('#').map_or(custom_id, |(id, _)| id);
+        let mut parts = custom_id
+            .strip_prefix(BUGLIST_CUSTOM_ID_PREFIX)?
+            .split("__");
+
+        let user_id = UserId(parts.next()?.parse().ok()?);
+        let page: u32 = parts.next()?.parse().ok()?;
+        let limit: u32 = parts.next()?.parse().ok()?;
+        let order = parts.next()?.parse().ok()?;
+        let (status, snoozed) = match parts.next()? {
+            "all" => (None, false),
+            "snoozed" => (None, true),
+            s => (Some(s.parse().ok()?), false),
+        };
+        let category = match parts.next()? {
+            "all" => None,
+            s => Some(s.parse().ok()?),
+        };
+        let (reporter, label, attachment) = match parts.next() {
+            Some(part) => {
+                if let Some(label) = part.strip_prefix("l:") {
+                    (None, Some(normalize_label(label)?), None)
+                } else if let Some(attachment) = part.strip_prefix("i:") {
+                    let attachment = match attachment {
+                        "1" => true,
+                        "0" => false,
+                        _ => return None,
+                    };
+                    (None, None, Some(attachment))
+                } else {
+                    (Some(UserId(part.parse().ok()?)), None, None)
+                }
+            }
+            None => (None, None, None),
+        };
+
+        if parts.next().is_some() || page == 0 || limit == 0 {
+            return None;
+        }
+
+        Some(Self {
+            user_id,
+            status,
+            category,
+            reporter,
+            label,
+            attachment,
+            snoozed,
+            order,
+            limit,
+            page,
+        })
+    }
+}
+
 macro_rules! create_buttons {
-    ($disable_previous:expr, $disable_next:expr) => {
+    ($query:expr, $total_bugs:expr) => {
         |c| {
+            let query: &BugListQuery = &$query;
+            let last_page = ($total_bugs.max(1) - 1) / query.limit + 1;
             c.create_action_row(|a| {
+                a.create_button(|b| {
+                    b.style(ButtonStyle::Secondary);
+                    b.label("First");
+                    b.custom_id(query.with_page(1).to_jump_custom_id(false));
+                    b.emoji(ReactionType::Unicode("⏮️".into()));
+                    b.disabled(query.page <= 1);
+                    b
+                });
                 a.create_button(|b| {
                     b.style(ButtonStyle::Secondary);
                     b.label("Previous");
-                    b.custom_id("previous_page");
+                    b.custom_id(query.with_page(query.page - 1).to_custom_id());
                     b.emoji(ReactionType::Unicode("⬅️".into()));
-                    b.disabled($disable_previous);
+                    b.disabled(query.page <= 1);
                     b
                 });
                 a.create_button(|b| {
                     b.style(ButtonStyle::Secondary);
                     b.label("Next");
-                    b.custom_id("next_page");
+                    b.custom_id(query.with_page(query.page + 1).to_custom_id());
                     b.emoji(ReactionType::Unicode("➡️".into()));
-                    b.disabled($disable_next);
+                    b.disabled(query.page * query.limit >= $total_bugs);
+                    b
+                });
+                a.create_button(|b| {
+                    b.style(ButtonStyle::Secondary);
+                    b.label("Last");
+                    b.custom_id(query.with_page(last_page).to_jump_custom_id(true));
+                    b.emoji(ReactionType::Unicode("⏭️".into()));
+                    b.disabled(query.page >= last_page);
                     b
                 });
                 a
@@ -289,25 +698,45 @@ macro_rules! create_buttons {
     };
 }
 
+/// Shows a bug list. The errors caused by the query, like a page past the
+/// end, are answered by [`reply_bug_list`].
 async fn display_bugs(
     ctx: &Context,
-    status: Option<BugStatus>,
-    limit: u32,
-    display_order: BugOrder,
-    category: Option<BugCategory>,
-    page: u32,
-    reply_to: Either<'_>,
+    query: BugListQuery,
+    reply_to: &Either<'_>,
 ) -> Result<Option<Message>, SerenityError> {
+    let BugListQuery {
+        status,
+        category,
+        reporter,
+        ref label,
+        attachment,
+        snoozed,
+        order: display_order,
+        limit,
+        page,
+        ..
+    } = query;
     assert_ne!(page, 0);
 
+    let guild_id = reply_to.guild_id().unwrap_or(EOA_DISCORD);
+    let filter = BugListFilter {
+        status,
+        category,
+        reporter,
+        label: label.as_deref(),
+        attachment,
+        snoozed,
+    };
     if let Some((bugs, total_bugs)) =
-        get_bug_list(ctx, status, limit, display_order, category, page - 1).await
+        get_bug_list(ctx, guild_id, filter, limit, display_order, page - 1).await
     {
         if total_bugs != 0 && (page - 1) * limit >= total_bugs {
-            reply_to.failure(ctx, "Page number too high, consider calling `!bugs` and using the navigation arrows.").await?;
             return Err(SerenityError::Other("page_too_high"));
         }
 
+        let prefix = get_bug_prefix(ctx, reply_to.guild_id()).await;
+        let markers = StatusMarkers::of(ctx, reply_to.guild_id()).await;
         let title;
         let content_alt;
         let content;
@@ -315,7 +744,7 @@ async fn display_bugs(
         if let Some(status) = status {
             title = format!(
                 "{} Bug reports (Status: {}){} (Total: {})",
-                status.marker(),
+                markers.get(status),
                 status,
                 if let Some(c) = category {
                     format!(" [{c}]")
@@ -330,7 +759,7 @@ async fn display_bugs(
                 .map(|b| {
                     format!(
                         "{}{}",
-                        b,
+                        b.list_entry(&prefix),
                         if category.is_none() {
                             format!(" [{}]", b.category)
                         } else {
@@ -340,10 +769,11 @@ async fn display_bugs(
                 })
                 .collect::<Vec<_>>()
                 .join("\n");
-            colour = status.colour();
+            colour = status.guild_colour(ctx, reply_to.guild_id()).await;
         } else {
             title = format!(
-                "Open bug reports{} (Total: {})",
+                "{} bug reports{} (Total: {})",
+                if snoozed { "Snoozed" } else { "Open" },
                 if let Some(c) = category {
                     format!(" [{c}]")
                 } else {
@@ -351,14 +781,18 @@ async fn display_bugs(
                 },
                 total_bugs
             );
-            content_alt = "_No open bugs!_";
+            content_alt = if snoozed {
+                "_No snoozed bugs!_"
+            } else {
+                "_No open bugs!_"
+            };
             content = bugs
                 .iter()
                 .map(|b| {
                     format!(
                         "{} {}{}",
-                        b.status.marker(),
-                        b,
+                        markers.get(b.status),
+                        b.list_entry(&prefix),
                         if category.is_none() {
                             format!(" [{}]", b.category)
                         } else {
@@ -371,31 +805,45 @@ async fn display_bugs(
             colour = serenity::utils::Colour::LIGHT_GREY;
         }
 
+        let content = if bugs.is_empty() {
+            match (reporter, label, attachment) {
+                (Some(_), _, _) => "_This user has no bug reports matching these filters!_",
+                (None, Some(_), _) => "_No bugs with this label match these filters!_",
+                (None, None, Some(true)) => "_No bugs with an attachment match these filters!_",
+                (None, None, Some(false)) => "_No bugs without an attachment match these filters!_",
+                (None, None, None) => content_alt,
+            }
+            .to_string()
+        } else {
+            content
+        };
+        let content = match reporter {
+            Some(user_id) => format!("Reported by {}\n\n{}", user_id.mention(), content),
+            None => content,
+        };
+        let content = match label {
+            Some(label) => format!("Labelled `{}`\n\n{}", label, content),
+            None => content,
+        };
+        let content = match attachment {
+            Some(true) => format!("With an attachment\n\n{}", content),
+            Some(false) => format!("Without an attachment\n\n{}", content),
+            None => content,
+        };
+
+        let branding = get_branding(ctx, reply_to.guild_id()).await;
+
         if content.len() > 4096 {
-            reply_to
-                .failure(
-                    ctx,
-                    "Too many bugs to display. Consider lowering the limit.",
-                )
-                .await?;
             return Err(SerenityError::Other("too_many_bugs"));
         }
 
         macro_rules! create_embed_reponse {
             () => {
                 |e| {
-                    e.author(|a| {
-                        a.name("Eras of Arda Bugtracker");
-                        a.icon_url(crate::constants::TERMITE_IMAGE);
-                        a
-                    });
+                    e.author(|a| branding.author(a));
                     e.colour(colour);
-                    e.title(title);
-                    e.description(if bugs.is_empty() {
-                        content_alt
-                    } else {
-                        &content
-                    });
+                    e.title(&title);
+                    e.description(&content);
                     e.footer(|f| {
                         f.text(format!(
                             "Page {}/{}",
@@ -410,19 +858,17 @@ async fn display_bugs(
 
         match reply_to {
             Either::Interaction(interaction) => {
-                interaction
-                    .create_interaction_response(ctx, |r| {
+                retry_discord!(
+                    "turning a bug list page",
+                    interaction.create_interaction_response(ctx, |r| {
                         r.kind(InteractionResponseType::UpdateMessage)
                             .interaction_response_data(|m| {
                                 m.embeds([])
                                     .create_embed(create_embed_reponse!())
-                                    .components(create_buttons!(
-                                        page <= 1,
-                                        (page * limit) >= total_bugs
-                                    ))
+                                    .components(create_buttons!(query, total_bugs))
                             })
                     })
-                    .await?;
+                )?;
 
                 Ok(None)
             }
@@ -431,11 +877,23 @@ async fn display_bugs(
                     .channel_id
                     .send_message(ctx, |m| {
                         m.embed(create_embed_reponse!())
-                            .components(create_buttons!(page <= 1, (page * limit) >= total_bugs))
+                            .components(create_buttons!(query, total_bugs))
                     })
                     .await?;
                 Ok(Some(response_message))
             }
+            Either::Command(command) => {
+                command
+                    .create_interaction_response(ctx, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|m| {
+                                m.create_embed(create_embed_reponse!())
+                                    .components(create_buttons!(query, total_bugs))
+                            })
+                    })
+                    .await?;
+                Ok(None)
+            }
         }
     } else {
         Err(SerenityError::Other(
@@ -444,613 +902,4051 @@ async fn display_bugs(
     }
 }
 
-#[command]
-#[aliases(bugs)]
-#[sub_commands(bugtracker_help)]
-pub async fn buglist(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let category = args.single::<BugCategory>().ok();
-    let status = args.single::<BugStatus>().ok();
-
-    let mut display_order = match args.current() {
-        Some("latest") => BugOrder::Chronological(false),
-        Some("oldest") => BugOrder::Chronological(true),
-        Some("highest") => BugOrder::Priority(false),
-        Some("lowest") => BugOrder::Priority(true),
-        _ => BugOrder::None,
+/// Shows a bug list with [`display_bugs`], and answers the errors caused by
+/// the query with an ephemeral message, or a reply to a `!bugs` message
+async fn reply_bug_list(ctx: &Context, query: BugListQuery, reply_to: Either<'_>) -> CommandResult {
+    let message = match display_bugs(ctx, query, &reply_to).await {
+        Ok(_) => return Ok(()),
+        Err(SerenityError::Other("page_too_high")) => match reply_to {
+            Either::Message(_) => {
+                "Page number too high, consider calling `!bugs` and using the navigation arrows."
+            }
+            _ => "Page number too high, consider using the navigation arrows.",
+        },
+        Err(SerenityError::Other("too_many_bugs")) => {
+            "Too many bugs to display. Consider lowering the limit."
+        }
+        Err(e) => return Err(e.into()),
     };
-    if let BugOrder::None = display_order {
-        display_order = BugOrder::Chronological(false);
-    } else {
-        args.advance();
-    }
-
-    let mut page = args.single::<u32>().unwrap_or(1).max(1);
-
-    let limit = if args.current() == Some("limit") {
-        args.advance();
-        args.single::<u32>().ok()
-    } else {
-        None
-    }
-    .unwrap_or(10);
+    reply_to.failure(ctx, message).await?;
+    Ok(())
+}
 
-    let mut response_message = match display_bugs(
-        ctx,
-        status,
-        limit,
-        display_order,
-        category,
-        page,
-        Either::Message(msg),
-    )
-    .await
+async fn display_resolved_changelog(
+    ctx: &Context,
+    msg: &Message,
+    category: Option<BugCategory>,
+    mut args: Args,
+) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let since = match args
+        .single::<String>()
+        .ok()
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok())
     {
-        Ok(Some(msg)) => msg,
-        Ok(None) => unreachable!(),
-        Err(SerenityError::Other("page_too_high" | "too_many_bugs")) => return Ok(()),
-        Err(e) => return Err(e.into()),
+        Some(date) => date,
+        None => {
+            failure!(
+                ctx,
+                msg,
+                "You must provide a date in the format `YYYY-MM-DD`!"
+            );
+            return Ok(());
+        }
     };
+    let plain = matches!(args.current(), Some("plain" | "plain:true"));
 
-    while let Some(interaction) = CollectComponentInteraction::new(ctx)
-        .timeout(Duration::from_secs(120))
-        .channel_id(msg.channel_id)
-        .message_id(response_message.id)
-        .await
+    let bugs = if let Some(bugs) =
+        get_resolved_bugs_since(ctx, guild_id, since.and_hms(0, 0, 0), category).await
     {
-        if interaction.user.id != msg.author.id {
-            interaction.create_interaction_response(ctx, |r| {
-                r.kind(InteractionResponseType::ChannelMessageWithSource);
-                r.interaction_response_data(|d| {
-                    d.content("You are not the original user of the command! Call `!bugs` yourself to use the buttons.");
-                    d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
-                })
-            })
+        bugs
+    } else {
+        failure!(ctx, msg, "Could not get bugs from the database!");
+        return Ok(());
+    };
+
+    if bugs.is_empty() {
+        msg.reply(ctx, format!("No bugs have been resolved since {}.", since))
             .await?;
-            continue;
-        }
-        match interaction.data.custom_id.as_str() {
-            "previous_page" => {
-                if page != 0 {
-                    page -= 1;
-                }
+        return Ok(());
+    }
+
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    let lines = bugs
+        .iter()
+        .map(|b| format!("{}: {}", prefix.id(b.bug_id), b.title))
+        .collect::<Vec<_>>();
+
+    if plain {
+        // code blocks are split to stay under the 2000 characters message limit
+        let mut block = String::new();
+        for line in &lines {
+            if block.len() + line.len() + 8 > 2000 {
+                msg.channel_id
+                    .say(ctx, format!("```\n{}```", block))
+                    .await?;
+                block.clear();
             }
-            "next_page" => {
-                page += 1;
+            block.push_str(line);
+            block.push('\n');
+        }
+        msg.channel_id
+            .say(ctx, format!("```\n{}```", block))
+            .await?;
+    } else {
+        let mut content = String::new();
+        let mut displayed = 0;
+        for line in &lines {
+            if content.len() + line.len() + 40 > 4096 {
+                break;
             }
-            _ => (),
+            content.push_str(line);
+            content.push('\n');
+            displayed += 1;
+        }
+        if displayed < lines.len() {
+            content.push_str(&format!(
+                "_...and {} more. Use the `plain` option to see all of them._",
+                lines.len() - displayed
+            ));
         }
 
-        let _ = display_bugs(
-            ctx,
-            status,
-            limit,
-            display_order,
-            category,
-            page,
-            Either::Interaction(interaction.as_ref()),
-        )
-        .await?;
+        let colour = BugStatus::Resolved.guild_colour(ctx, msg.guild_id).await;
+        let marker = StatusMarkers::of(ctx, msg.guild_id)
+            .await
+            .get(BugStatus::Resolved)
+            .to_string();
+        let branding = get_branding(ctx, msg.guild_id).await;
+        msg.channel_id
+            .send_message(ctx, |m| {
+                m.embed(|e| {
+                    e.author(|a| branding.author(a));
+                    e.colour(colour);
+                    e.title(format!(
+                        "{} Bugs resolved since {}{} (Total: {})",
+                        marker,
+                        since,
+                        if let Some(c) = category {
+                            format!(" [{c}]")
+                        } else {
+                            "".into()
+                        },
+                        lines.len()
+                    ));
+                    e.description(content);
+                    e
+                })
+            })
+            .await?;
     }
 
-    response_message
-        .edit(ctx, |m| m.components(create_buttons!(true, true)))
-        .await?;
-
     Ok(())
 }
 
-#[command]
-#[sub_commands(
-    track,
-    bug_status,
-    resolve,
-    bug_close,
-    bug_link,
-    bug_rename,
-    stats,
-    bug_toggle_edition,
-    bugtracker_help,
-    notifications,
-    unsubscribe,
-    subscribe
-)]
-pub async fn bug(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let bug_id = if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            bug_id
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id);
+async fn display_fixed_between(
+    ctx: &Context,
+    msg: &Message,
+    category: Option<BugCategory>,
+    mut args: Args,
+) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let (from, to) = match (args.single::<String>(), args.single::<String>()) {
+        (Ok(from), Ok(to)) => (from, to),
+        _ => {
+            failure!(
+                ctx,
+                msg,
+                "Usage:  `!bugs [legacy|renewed] between <version> <version>`, e.g.  \
+`!bugs between v41.0 v41.2`"
+            );
+            return Ok(());
+        }
+    };
+    let (mut low, mut high) = match (from.parse::<ModVersion>(), to.parse::<ModVersion>()) {
+        (Ok(low), Ok(high)) => (low, high),
+        _ => {
+            failure!(
+                ctx,
+                msg,
+                "Versions must look like `v41.2` or `v41.2.1`, got `{}` and `{}`.",
+                sanitize_echo(&from),
+                sanitize_echo(&to)
+            );
             return Ok(());
         }
-    } else {
-        failure!(ctx, msg, "The first argument must be a bug id.");
-        return Ok(());
     };
+    if low > high {
+        std::mem::swap(&mut low, &mut high);
+    }
 
-    let mut bug = match get_bug_from_id(ctx, bug_id).await {
-        Ok(bug) => bug,
+    let bugs = match get_fixed_bugs(ctx, guild_id, category).await {
+        Ok(bugs) => bugs,
         Err(e) => {
-            failure!(ctx, msg, "Bug EoA-{} does not exist!", bug_id);
-            return Err(e);
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
         }
     };
 
-    macro_rules! create_bug_buttons {
-        ($message_link:expr) => {
-            |c| {
-                c.create_action_row(|a| {
-                    a.create_button(|b| {
-                        b.style(ButtonStyle::Primary)
-                            .label("Subscribe")
-                            .custom_id(format!("bug_subscribe__{bug_id}"))
-                    });
-                    if let Some(ref link) = $message_link {
-                        a.create_button(|b| {
-                            b.style(ButtonStyle::Link).label("Message link").url(link)
-                        });
-                    }
-                    a
-                });
-
-                c
-            }
-        };
-        ($message_link:expr, $create_buttons:expr, $disabled:expr) => {
-            |c| {
-                c.create_action_row(|a| {
-                    a.create_button(|b| {
-                        b.style(ButtonStyle::Primary)
-                            .label("Subscribe")
-                            .custom_id(format!("bug_subscribe__{bug_id}"))
-                    });
-                    if let Some(link) = $message_link.as_ref() {
-                        a.create_button(|b| {
-                            b.style(ButtonStyle::Link).label("Message link").url(link)
-                        });
-                    }
-                    if $create_buttons {
-                        a.create_button(|b| {
-                            b.style(ButtonStyle::Success)
-                                .label("Resolve")
-                                .custom_id("resolve_bug")
-                                .disabled($disabled)
-                        });
+    let mut unreadable = Vec::new();
+    let mut fixed = Vec::new();
+    for (bug, fixed_in) in bugs {
+        match fixed_in.parse::<ModVersion>() {
+            Ok(version) if (low..=high).contains(&version) => fixed.push((version, bug, fixed_in)),
+            Ok(_) => (),
+            Err(_) => unreadable.push(bug.bug_id),
+        }
+    }
+    fixed.sort_by_key(|(version, bug, _)| (*version, bug.bug_id));
 
-                        a.create_button(|b| {
-                            b.style(ButtonStyle::Danger)
-                                .label("Close")
-                                .custom_id("close_bug")
-                                .disabled($disabled)
-                        });
-                    }
-                    a
-                });
-                c
-            }
-        };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    let mut content = String::new();
+    for (i, (_, bug, fixed_in)) in fixed.iter().enumerate() {
+        let line = format!(
+            "{} — fixed in {}{}\n",
+            bug.list_entry(&prefix),
+            fixed_in,
+            if category.is_none() {
+                format!(" [{}]", bug.category)
+            } else {
+                "".into()
+            },
+        );
+        if content.len() + line.len() + 200 > 4096 {
+            content.push_str(&format!("_...and {} more_\n", fixed.len() - i));
+            break;
+        }
+        content.push_str(&line);
+    }
+    if fixed.is_empty() {
+        content.push_str("_No bugs fixed in these versions!_\n");
+    }
+    if !unreadable.is_empty() {
+        content.push_str(&format!(
+            "\n_{} resolved bugs were left out because their version could not be read: {}_",
+            unreadable.len(),
+            unreadable
+                .iter()
+                .take(10)
+                .map(|bug_id| prefix.id(*bug_id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        if unreadable.len() > 10 {
+            content.push_str("_, ..._");
+        }
     }
 
-    let linked_message = bug
-        .channel_id
-        .message(ctx, bug.message_id)
+    let colour = BugStatus::Resolved.guild_colour(ctx, msg.guild_id).await;
+    let marker = StatusMarkers::of(ctx, msg.guild_id)
         .await
-        .map(|mut m| {
-            m.guild_id = Some(EOA_DISCORD);
-            m
-        });
-    let message_link = linked_message.as_ref().map(|m| m.link()).ok();
-
-    let is_lotr_discord = msg.guild_id == Some(EOA_DISCORD);
-    let is_admin = if let Some(guild_id) = msg.guild_id {
-        is_admin_function(ctx, guild_id, msg.author.id)
-            .await
-            .unwrap_or_default()
-            || crate::utils::has_permission(ctx, guild_id, msg.author.id, MANAGE_BOT_PERMS).await
-    } else {
-        false
-    };
-
-    let mut create_buttons = bug.status != BugStatus::Resolved
-        && bug.status != BugStatus::Closed
-        && (msg.author.id == OWNER_ID || (is_lotr_discord && is_admin));
-
-    let mut response_message = msg
-        .channel_id
+        .get(BugStatus::Resolved)
+        .to_string();
+    let branding = get_branding(ctx, msg.guild_id).await;
+    msg.channel_id
         .send_message(ctx, |m| {
-            m.embed(create_bug_embed!(bug, linked_message))
-                .components(create_bug_buttons!(message_link, create_buttons, false))
+            m.embed(|e| {
+                e.author(|a| branding.author(a));
+                e.colour(colour);
+                e.title(format!(
+                    "{} Bugs fixed between {} and {}{} (Total: {})",
+                    marker,
+                    sanitize_echo(&from),
+                    sanitize_echo(&to),
+                    if let Some(c) = category {
+                        format!(" [{c}]")
+                    } else {
+                        "".into()
+                    },
+                    fixed.len()
+                ));
+                e.description(content);
+                e
+            })
         })
         .await?;
 
-    if create_buttons {
-        // Listen to interactions for 120 seconds
-        while let Some(interaction) = CollectComponentInteraction::new(ctx)
-            .timeout(Duration::from_secs(120))
-            .channel_id(msg.channel_id)
-            .message_id(response_message.id)
-            .await
-        {
-            if interaction.user.id != msg.author.id {
-                interaction
-                    .create_interaction_response(ctx, |r| {
-                        r.kind(InteractionResponseType::ChannelMessageWithSource)
-                            .interaction_response_data(|d| {
-                                d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
-                                    .content("You are not allowed to modify bug status!")
-                            })
-                    })
-                    .await?;
-            } else {
-                let new_status = match interaction.data.custom_id.as_str() {
-                    "resolve_bug" => BugStatus::Resolved,
-                    "close_bug" => BugStatus::Closed,
-                    _ => continue,
-                };
-
-                change_bug_status(ctx, bug_id, new_status).await?;
-
-                let old_status = bug.status;
-                bug.status = new_status;
-
-                interaction
-                    .create_interaction_response(ctx, |r| {
-                        r.kind(InteractionResponseType::UpdateMessage)
-                            .interaction_response_data(|m| {
-                                m.embeds([])
-                                    .create_embed(create_bug_embed!(bug, linked_message))
-                                    .components(create_bug_buttons!(message_link))
-                            })
-                    })
-                    .await?;
-
-                notify_users(
-                    ctx,
-                    bug_id,
-                    format!(
-                        "A bug you are subscribed to has been changed from `{}` to `{}`",
-                        old_status, new_status
-                    ),
-                )
-                .await?;
-
-                create_buttons = false;
+    Ok(())
+}
 
-                break;
-            }
+async fn display_labels(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let labels = match get_label_counts(ctx, guild_id).await {
+        Ok(labels) => labels,
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
         }
+    };
 
-        if create_buttons {
-            // If no interaction was received after timeout, remove the buttons
-            response_message
-                .edit(ctx, |m| {
-                    m.components(create_bug_buttons!(message_link, create_buttons, true))
-                })
-                .await?;
+    let mut content = String::new();
+    for (i, (label, count)) in labels.iter().enumerate() {
+        let line = format!(
+            "`{}` — {} bug{}\n",
+            label,
+            count,
+            if *count == 1 { "" } else { "s" }
+        );
+        if content.len() + line.len() + 40 > 4096 {
+            content.push_str(&format!("_...and {} more_", labels.len() - i));
+            break;
         }
+        content.push_str(&line);
+    }
+    if labels.is_empty() {
+        content.push_str("_No labels yet!_");
     }
 
+    let branding = get_branding(ctx, msg.guild_id).await;
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| branding.author(a));
+                e.colour(serenity::utils::Colour::LIGHT_GREY);
+                e.title(format!("Bug labels (Total: {})", labels.len()));
+                e.description(content);
+                e
+            })
+        })
+        .await?;
+
     Ok(())
 }
 
-#[command]
-#[checks(is_lotr_discord, is_admin)]
-#[aliases("status")]
-pub async fn bug_status(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            if let Ok(new_status) = args.single::<BugStatus>() {
-                let old_status = match change_bug_status(ctx, bug_id, new_status).await {
-                    Ok(old_status) => {
-                        termite_success!(
-                            ctx,
-                            msg,
-                            "Status changed for EoA-{} from `{}` to `{}`!",
-                            bug_id,
-                            old_status,
-                            new_status
-                        );
-                        old_status
-                    }
-                    Err(e) => {
-                        failure!(ctx, msg, "The bug EoA-{} does not exist!", bug_id);
-                        return Err(e);
-                    }
-                };
+async fn display_leaderboard(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let days = args
+        .single::<u32>()
+        .ok()
+        .map(|days| days.clamp(1, MAX_LOOKBACK_DAYS));
+    let since = days.map(|days| (Utc::now() - chrono::Duration::days(days.into())).naive_utc());
 
-                if old_status != new_status {
-                    notify_users(
-                        ctx,
-                        bug_id,
-                        format!(
-                            "A bug you are subscribed to has been changed from `{}` to `{}`",
-                            old_status, new_status
-                        ),
-                    )
-                    .await?;
-                }
-            } else {
-                failure!(ctx, msg, "The second argument must be a bug status.")
-            }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id)
+    let reporters = match get_top_reporters(ctx, guild_id, since, LEADERBOARD_SIZE).await {
+        Ok(reporters) => reporters,
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
         }
+    };
+
+    let content = if reporters.is_empty() {
+        "_No bug reports yet!_".to_string()
     } else {
-        failure!(ctx, msg, "The first argument must be a bug id.")
-    }
+        reporters
+            .iter()
+            .enumerate()
+            .map(|(i, (user_id, count))| {
+                format!(
+                    "{} {} — {} bug{}",
+                    match i {
+                        0 => "🥇".to_string(),
+                        1 => "🥈".to_string(),
+                        2 => "🥉".to_string(),
+                        _ => format!("`{}.`", i + 1),
+                    },
+                    user_id.mention(),
+                    count,
+                    if *count == 1 { "" } else { "s" }
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let branding = get_branding(ctx, msg.guild_id).await;
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| branding.author(a));
+                e.colour(serenity::utils::Colour::GOLD);
+                e.title(match days {
+                    Some(days) => format!("Top bug reporters of the last {} days", days),
+                    None => "Top bug reporters".to_string(),
+                });
+                e.description(content);
+                e.footer(|f| f.text("Ties go to the earliest reporter"));
+                e
+            })
+            .allowed_mentions(|a| a.empty_parse())
+        })
+        .await?;
+
     Ok(())
 }
 
-#[command]
-#[checks(is_lotr_discord, is_admin)]
-pub async fn resolve(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            if let Err(e) = change_bug_status(ctx, bug_id, BugStatus::Resolved).await {
-                failure!(ctx, msg, "The bug EoA-{} does not exist!", bug_id);
-                return Err(e);
-            } else {
-                termite_success!(ctx, msg, "EoA-{} has been marked as resolved.", bug_id);
-                notify_users(
-                    ctx,
-                    bug_id,
-                    "A bug you are subscribed to has been marked as resolved.",
-                )
-                .await?;
-            }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id)
+/// Formats the compact bug index: a numbered list of bug ids and titles cut to
+/// [`BUG_INDEX_TITLE_WIDTH`], in a code block that fits in an embed
+/// description, followed by the number of bugs left out
+fn bug_index(bugs: &[PartialBugReport], total: u32, prefix: &BugPrefix) -> String {
+    if bugs.is_empty() {
+        return "_No open bugs!_".into();
+    }
+    let ids: Vec<_> = bugs.iter().map(|bug| prefix.id(bug.bug_id)).collect();
+    let id_width = ids.iter().map(String::len).max().unwrap_or_default();
+    let number_width = bugs.len().to_string().len();
+
+    let mut index = String::from("```\n");
+    let mut shown = 0;
+    for (bug, id) in bugs.iter().zip(&ids) {
+        // a backtick could close the code block
+        let mut title = bug.title.replace('`', "'");
+        if title.chars().count() > BUG_INDEX_TITLE_WIDTH {
+            title = title
+                .chars()
+                .take(BUG_INDEX_TITLE_WIDTH - 1)
+                .chain(['…'])
+                .collect();
         }
-    } else {
-        failure!(ctx, msg, "The first argument must be a bug id.")
+        let line = format!(
+            "{:>number_width$}. {:<id_width$}  {}\n",
+            shown + 1,
+            id,
+            title,
+            number_width = number_width,
+            id_width = id_width
+        );
+        // room for the closing fence and the number of bugs left out
+        if index.len() + line.len() + 40 > 4096 {
+            break;
+        }
+        index.push_str(&line);
+        shown += 1;
     }
-    Ok(())
+    index.push_str("```");
+    if total as usize > shown {
+        index.push_str(&format!("\n_...and {} more_", total as usize - shown));
+    }
+    index
 }
 
-#[command]
-#[checks(is_lotr_discord, is_admin)]
-#[aliases("close")]
-pub async fn bug_close(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            if let Err(e) = change_bug_status(ctx, bug_id, BugStatus::Closed).await {
-                failure!(ctx, msg, "The bug EoA-{} does not exist!", bug_id);
-                return Err(e);
+async fn display_bug_index(
+    ctx: &Context,
+    msg: &Message,
+    category: Option<BugCategory>,
+    args: Args,
+) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let order = args
+        .current()
+        .and_then(|order| order.parse().ok())
+        .unwrap_or(BugOrder::Priority(false));
+    let filter = BugListFilter {
+        category,
+        ..Default::default()
+    };
+    let (bugs, total) = get_bug_list(ctx, guild_id, filter, BUG_INDEX_LIMIT, order, 0)
+        .await
+        .ok_or("Could not get the bug list from the database")?;
+
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    let branding = get_branding(ctx, msg.guild_id).await;
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| branding.author(a));
+                e.colour(serenity::utils::Colour::LIGHT_GREY);
+                e.title(format!(
+                    "Open bugs{} (Total: {})",
+                    if let Some(c) = category {
+                        format!(" [{c}]")
+                    } else {
+                        "".into()
+                    },
+                    total
+                ));
+                e.description(bug_index(&bugs, total, &prefix));
+                e
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn display_stale_bugs(
+    ctx: &Context,
+    msg: &Message,
+    category: Option<BugCategory>,
+    mut args: Args,
+) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let days = args
+        .single::<u32>()
+        .unwrap_or(30)
+        .clamp(1, MAX_LOOKBACK_DAYS);
+    let before = Utc::now() - chrono::Duration::days(days.into());
+
+    let bugs = match get_stale_bugs(ctx, guild_id, before.naive_utc(), category).await {
+        Ok(bugs) => bugs,
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    let markers = StatusMarkers::of(ctx, msg.guild_id).await;
+    let mut content = String::new();
+    for (i, (bug, last_activity)) in bugs.iter().enumerate() {
+        let line = format!(
+            "{} {}{} — idle for {} days\n",
+            markers.get(bug.status),
+            bug.list_entry(&prefix),
+            if category.is_none() {
+                format!(" [{}]", bug.category)
             } else {
-                termite_success!(ctx, msg, "EoA-{} has been marked as closed.", bug_id);
-                notify_users(
-                    ctx,
-                    bug_id,
-                    "A bug you are subscribed to has been marked as closed.",
-                )
-                .await?;
-            }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id)
+                "".into()
+            },
+            Utc::now().signed_duration_since(*last_activity).num_days()
+        );
+        if content.len() + line.len() + 40 > 4096 {
+            content.push_str(&format!("_...and {} more_", bugs.len() - i));
+            break;
         }
-    } else {
-        failure!(ctx, msg, "The first argument must be a bug id.")
+        content.push_str(&line);
     }
+    if bugs.is_empty() {
+        content.push_str("_No stale bugs!_");
+    }
+
+    let branding = get_branding(ctx, msg.guild_id).await;
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| branding.author(a));
+                e.colour(serenity::utils::Colour::LIGHT_GREY);
+                e.title(format!(
+                    "Bugs without activity for {} days{} (Total: {})",
+                    days,
+                    if let Some(c) = category {
+                        format!(" [{c}]")
+                    } else {
+                        "".into()
+                    },
+                    bugs.len()
+                ));
+                e.description(content);
+                e
+            })
+        })
+        .await?;
+
     Ok(())
 }
 
 #[command]
-#[checks(is_lotr_discord, is_admin)]
-#[sub_commands(bug_link_remove)]
-#[aliases("link")]
-pub async fn bug_link(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            if let Some(message) = &msg.referenced_message {
-                let title = args.rest();
-                if title.is_empty() {
-                    failure!(ctx, msg, "Specify a title for your message link!");
-                    return Ok(());
-                }
-                if let Some(link_id) = add_link(ctx, bug_id, &message.link(), title).await {
-                    termite_success!(ctx, msg, "Added link #{} to EoA-{}", link_id, bug_id);
-                    notify_users(
+#[description = "List the bug reports"]
+#[usage = "[latest|oldest|highest|lowest] [status] [page] [limit n]"]
+#[aliases(bugs)]
+#[sub_commands(bugtracker_help)]
+pub async fn buglist(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    if args.current() == Some("labels") {
+        return display_labels(ctx, msg).await;
+    }
+    if args.current() == Some("leaderboard") {
+        args.advance();
+        return display_leaderboard(ctx, msg, args).await;
+    }
+    let (reporter, label, attachment) = match args.current() {
+        Some("by") => {
+            args.advance();
+            match args.single::<UserId>() {
+                Ok(user_id) => (Some(user_id), None, None),
+                Err(_) => {
+                    failure!(
                         ctx,
-                        bug_id,
-                        format!("Link #{link_id} has been added to a bug you are subscribed to"),
-                    )
-                    .await?;
-                } else {
-                    failure!(ctx, msg, "EoA-{} does not exist!", bug_id);
+                        msg,
+                        "You must mention the reporter: `!bugs by @user [legacy|renewed] [status]`"
+                    );
+                    return Ok(());
                 }
-            } else if let Some(link) = args
+            }
+        }
+        Some("label") => {
+            args.advance();
+            match args
                 .single::<String>()
                 .ok()
-                .filter(|s| s.starts_with("http"))
+                .as_deref()
+                .and_then(normalize_label)
             {
-                let title = args.rest();
-                if title.is_empty() {
-                    failure!(ctx, msg, "Specify a title for your message link!");
+                Some(label) => (None, Some(label), None),
+                None => {
+                    failure!(
+                        ctx,
+                        msg,
+                        "You must give a valid label: `!bugs label <label> [legacy|renewed] [status]`"
+                    );
                     return Ok(());
                 }
-                if let Some(link_id) = add_link(ctx, bug_id, &link, title).await {
-                    termite_success!(ctx, msg, "Added link #{} to EoA-{}", link_id, bug_id);
-                    notify_users(
-                        ctx,
-                        bug_id,
-                        format!("Link #{link_id} has been added to a bug you are subscribed to"),
-                    )
+            }
+        }
+        Some("withimage") => {
+            args.advance();
+            (None, None, Some(true))
+        }
+        Some("noimage") => {
+            args.advance();
+            (None, None, Some(false))
+        }
+        _ => (None, None, None),
+    };
+    let category = args.single::<BugCategory>().ok();
+    if args.current() == Some("stale") {
+        args.advance();
+        return display_stale_bugs(ctx, msg, category, args).await;
+    }
+    if args.current() == Some("between") {
+        args.advance();
+        return display_fixed_between(ctx, msg, category, args).await;
+    }
+    if args.current() == Some("index") {
+        args.advance();
+        return display_bug_index(ctx, msg, category, args).await;
+    }
+    let snoozed = args.current() == Some("snoozed");
+    if snoozed {
+        args.advance();
+    }
+    let status = if snoozed {
+        None
+    } else {
+        args.single::<BugStatus>().ok()
+    };
+
+    // a typo in the status would otherwise silently list the open bugs
+    if let (None, Some(word)) = (status, args.current()) {
+        let other_keyword =
+            word.parse::<BugOrder>().is_ok() || word.parse::<u32>().is_ok() || word == "limit";
+        if let Some(keyword) = BugStatus::suggest(word).filter(|_| !other_keyword) {
+            failure!(
+                ctx,
+                msg,
+                "Unknown status `{}`, did you mean `{}`?",
+                sanitize_echo(word),
+                keyword
+            );
+            return Ok(());
+        }
+    }
+
+    if status == Some(BugStatus::Resolved) && args.current() == Some("since") {
+        args.advance();
+        return display_resolved_changelog(ctx, msg, category, args).await;
+    }
+
+    let order = match args.current().map(str::parse) {
+        Some(Ok(order)) => {
+            args.advance();
+            order
+        }
+        _ => BugOrder::Chronological(false),
+    };
+
+    let page = args.single::<u32>().unwrap_or(1).max(1);
+
+    let limit = if args.current() == Some("limit") {
+        args.advance();
+        args.single::<u32>().ok()
+    } else {
+        None
+    }
+    .unwrap_or(10)
+    .max(1);
+
+    let query = BugListQuery {
+        user_id: msg.author.id,
+        status,
+        category,
+        reporter,
+        label,
+        attachment,
+        snoozed,
+        order,
+        limit,
+        page,
+    };
+
+    reply_bug_list(ctx, query, Either::Message(msg)).await
+}
+
+/// Handles the navigation buttons of `!bugs`, see [`BugListQuery`]
+pub async fn handle_buglist_interaction(
+    ctx: &Context,
+    interaction: &MessageComponentInteraction,
+) -> CommandResult {
+    let query = match BugListQuery::from_custom_id(&interaction.data.custom_id) {
+        Some(query) => query,
+        None => return Ok(()),
+    };
+
+    if interaction.user.id != query.user_id {
+        interaction.create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource);
+            r.interaction_response_data(|d| {
+                d.content("You are not the original user of the command! Call `!bugs` yourself to use the buttons.");
+                d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+            })
+        })
+        .await?;
+        return Ok(());
+    }
+
+    reply_bug_list(ctx, query, Either::Interaction(interaction)).await
+}
+
+/// Registers the `/bug` slash command in the Eras of Arda Discord, the only
+/// server with the bugtracker. It mirrors the most used `!bug` commands for
+/// mobile users, see [`handle_bug_slash_command`].
+pub async fn register_bug_slash_command(ctx: &Context) -> Result<(), SerenityError> {
+    macro_rules! string_option {
+        ($option:ident, $name:expr, $description:expr, $required:expr) => {
+            $option.create_sub_option(|o| {
+                o.name($name)
+                    .description($description)
+                    .kind(ApplicationCommandOptionType::String)
+                    .required($required)
+            })
+        };
+    }
+
+    EOA_DISCORD
+        .create_application_command(ctx, |c| {
+            c.name("bug").description("Manage the bugtracker");
+            c.create_option(|o| {
+                o.name("list")
+                    .description("List the bugs")
+                    .kind(ApplicationCommandOptionType::SubCommand);
+                o.create_sub_option(|s| {
+                    s.name("status")
+                        .description("Only list the bugs with this status")
+                        .kind(ApplicationCommandOptionType::String);
+                    for status in SLASH_COMMAND_STATUSES {
+                        s.add_string_choice(status, status.as_str());
+                    }
+                    s
+                });
+                o.create_sub_option(|s| {
+                    s.name("category")
+                        .description("Only list the bugs of this category")
+                        .kind(ApplicationCommandOptionType::String);
+                    for category in [
+                        BugCategory::FaRenewed,
+                        BugCategory::FaLegacy,
+                        BugCategory::SaRenewed,
+                        BugCategory::SaLegacy,
+                    ] {
+                        s.add_string_choice(category, category.as_str());
+                    }
+                    s
+                });
+                o.create_sub_option(|s| {
+                    s.name("page")
+                        .description("Page number")
+                        .kind(ApplicationCommandOptionType::Integer)
+                })
+            });
+            c.create_option(|o| {
+                o.name("status")
+                    .description("Change the status of a bug")
+                    .kind(ApplicationCommandOptionType::SubCommand);
+                string_option!(o, "bug_id", "Bug id, e.g. EoA-12", true);
+                o.create_sub_option(|s| {
+                    s.name("status")
+                        .description("New status")
+                        .kind(ApplicationCommandOptionType::String)
+                        .required(true);
+                    for status in SLASH_COMMAND_STATUSES {
+                        s.add_string_choice(status, status.as_str());
+                    }
+                    s
+                })
+            });
+            c.create_option(|o| {
+                o.name("resolve")
+                    .description("Mark a bug as resolved")
+                    .kind(ApplicationCommandOptionType::SubCommand);
+                string_option!(o, "bug_id", "Bug id, e.g. EoA-12", true);
+                string_option!(
+                    o,
+                    "version",
+                    "Version that fixes the bug, e.g. v41.2",
+                    false
+                )
+            });
+            c.create_option(|o| {
+                o.name("close")
+                    .description("Mark a bug as closed")
+                    .kind(ApplicationCommandOptionType::SubCommand);
+                string_option!(o, "bug_id", "Bug id, e.g. EoA-12", true)
+            });
+            c.create_option(|o| {
+                o.name("rename")
+                    .description("Change the title of a bug")
+                    .kind(ApplicationCommandOptionType::SubCommand);
+                string_option!(o, "bug_id", "Bug id, e.g. EoA-12", true);
+                string_option!(o, "title", "New title", true)
+            });
+            c.create_option(|o| {
+                o.name("link")
+                    .description("Add a link to a bug")
+                    .kind(ApplicationCommandOptionType::SubCommand);
+                string_option!(o, "bug_id", "Bug id, e.g. EoA-12", true);
+                string_option!(o, "url", "Link url", true);
+                string_option!(o, "title", "Link title", true)
+            })
+        })
+        .await?;
+    Ok(())
+}
+
+/// Statuses offered by the `/bug` slash command
+const SLASH_COMMAND_STATUSES: [BugStatus; 7] = [
+    BugStatus::Low,
+    BugStatus::Medium,
+    BugStatus::High,
+    BugStatus::Critical,
+    BugStatus::ForgeVanilla,
+    BugStatus::Resolved,
+    BugStatus::Closed,
+];
+
+fn slash_option<'a>(
+    options: &'a [ApplicationCommandInteractionDataOption],
+    name: &str,
+) -> Option<&'a serde_json::Value> {
+    options.iter().find(|o| o.name == name)?.value.as_ref()
+}
+
+/// Replies to a failed database call of a slash command, like
+/// [`db_failure!`][crate::db_failure]
+async fn slash_db_failure(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    bug: String,
+    error: DbError,
+) -> CommandResult {
+    let reply = match error {
+        DbError::NotFound => t!(ctx, command, "bug.not_found", bug),
+        DbError::Archived => t!(ctx, command, "bug.archived", bug),
+        DbError::Busy => t!(ctx, command, "db.busy"),
+        _ => t!(ctx, command, "db.unavailable"),
+    };
+    Either::Command(command).failure(ctx, &reply).await?;
+    match error {
+        DbError::NotFound | DbError::Archived | DbError::Busy => Ok(()),
+        e => Err(e.into()),
+    }
+}
+
+/// Handles the `/bug` slash command. Its subcommands call the same functions
+/// as their `!bug` counterparts, and `/bug list` renders the same pages as
+/// `!bugs`. Reporting a bug still needs  `!track`, since a report is made from
+/// the message it replies to.
+pub async fn handle_bug_slash_command(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+) -> CommandResult {
+    let reply_to = Either::Command(command);
+    let subcommand = match command.data.options.first() {
+        Some(subcommand) => subcommand,
+        None => return Ok(()),
+    };
+    let options = &subcommand.options;
+    let string = |name| slash_option(options, name).and_then(serde_json::Value::as_str);
+
+    if subcommand.name == "list" {
+        let query = BugListQuery {
+            user_id: command.user.id,
+            status: string("status").and_then(|s| s.parse().ok()),
+            category: string("category").and_then(|s| s.parse().ok()),
+            reporter: None,
+            label: None,
+            attachment: None,
+            snoozed: false,
+            order: BugOrder::Chronological(false),
+            limit: 10,
+            page: slash_option(options, "page")
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|page| u32::try_from(page).ok())
+                .unwrap_or(1)
+                .max(1),
+        };
+        return reply_bug_list(ctx, query, reply_to).await;
+    }
+
+    let guild_id = command.guild_id.ok_or(NotInGuild)?;
+    if guild_id != EOA_DISCORD || !is_admin_user(ctx, guild_id, command.user.id).await {
+        reply_to
+            .failure(ctx, "You need to be a server admin to use this command!")
+            .await?;
+        return Ok(());
+    }
+    if in_maintenance(ctx).await {
+        reply_to
+            .failure(ctx, "The bot is in maintenance mode.")
+            .await?;
+        return Ok(());
+    }
+
+    let prefix = get_bug_prefix(ctx, command.guild_id).await;
+    let bug_id = string("bug_id").unwrap_or_default();
+    let bug_id = match parse_bug_id(ctx, command.guild_id, bug_id).await {
+        Some(bug_id) => bug_id,
+        None => {
+            let reply = t!(ctx, command, "bug.invalid_id", sanitize_echo(bug_id));
+            reply_to.failure(ctx, &reply).await?;
+            return Ok(());
+        }
+    };
+
+    let (content, notification) = match subcommand.name.as_str() {
+        "status" | "resolve" | "close" => {
+            let new_status = match subcommand.name.as_str() {
+                "resolve" => BugStatus::Resolved,
+                "close" => BugStatus::Closed,
+                _ => match string("status").and_then(|s| s.parse().ok()) {
+                    Some(status) => status,
+                    None => {
+                        reply_to
+                            .failure(ctx, "The status must be a bug status.")
+                            .await?;
+                        return Ok(());
+                    }
+                },
+            };
+            let version = string("version");
+            if version.map_or(false, |v| v.chars().count() > 32) {
+                reply_to
+                    .failure(ctx, "The version can be at most 32 characters.")
                     .await?;
-                } else {
-                    failure!(ctx, msg, "EoA-{} does not exist!", bug_id);
+                return Ok(());
+            }
+            let old_status =
+                match change_status_and_notify(ctx, bug_id, guild_id, new_status, command.user.id)
+                    .await
+                {
+                    Ok(old_status) => old_status,
+                    Err(e) => return slash_db_failure(ctx, command, prefix.id(bug_id), e).await,
+                };
+            if let Some(version) = version {
+                if let Err(e) = set_fixed_in(ctx, bug_id, guild_id, version).await {
+                    return slash_db_failure(ctx, command, prefix.id(bug_id), e).await;
                 }
-            } else {
-                failure!(ctx, msg, "You need to either reference a message or specify a link to add to the bug report.");
             }
+            (
+                t!(
+                    ctx,
+                    command,
+                    "bug.status_changed",
+                    prefix.id(bug_id),
+                    old_status,
+                    new_status
+                ),
+                None,
+            )
+        }
+        "rename" => {
+            let new_title = string("title").unwrap_or_default();
+            if let Err(e) = change_title(ctx, bug_id, guild_id, new_title).await {
+                return slash_db_failure(ctx, command, prefix.id(bug_id), e).await;
+            }
+            (
+                format!(
+                    "Successfully changed the title of {} to **{}**",
+                    prefix.id(bug_id),
+                    sanitize_echo(new_title)
+                ),
+                Some("The title of a bug you are subscribed to has been changed".to_string()),
+            )
+        }
+        "link" => {
+            let url = string("url").unwrap_or_default();
+            if !url.starts_with("http") {
+                reply_to
+                    .failure(ctx, "The link must be a http(s) url.")
+                    .await?;
+                return Ok(());
+            }
+            let link_id = match add_link(
+                ctx,
+                bug_id,
+                guild_id,
+                url,
+                string("title").unwrap_or_default(),
+            )
+            .await
+            {
+                Ok(link_id) => link_id,
+                Err(e) => return slash_db_failure(ctx, command, prefix.id(bug_id), e).await,
+            };
+            (
+                format!("Added link #{} to {}", link_id, prefix.id(bug_id)),
+                Some(format!(
+                    "Link #{link_id} has been added to a bug you are subscribed to"
+                )),
+            )
+        }
+        _ => return Ok(()),
+    };
+
+    retry_discord!(
+        "answering a bug slash command",
+        command.create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|d| {
+                    d.content(&content).allowed_mentions(|a| a.empty_parse())
+                })
+        })
+    )?;
+
+    if let Some(notification) = notification {
+        notify_users(ctx, bug_id, guild_id, notification).await?;
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Display or edit a bug report"]
+#[usage = "<bug id>"]
+#[sub_commands(
+    track,
+    bug_status,
+    bug_triage,
+    resolve,
+    bug_close,
+    bug_link,
+    bug_label,
+    bug_snooze,
+    bug_rename,
+    bug_summary,
+    stats,
+    bug_toggle_edition,
+    bug_clone,
+    bug_set_message,
+    bug_refresh,
+    bug_diff,
+    bug_recent,
+    bug_merge,
+    bug_colour,
+    bug_marker,
+    bug_branding,
+    bug_prefix,
+    bug_webhook,
+    bug_post,
+    bug_audit,
+    bug_raw,
+    bug_markdown_export,
+    bug_discussion,
+    bug_archive,
+    bug_import,
+    bug_reset,
+    bugtracker_help,
+    notifications,
+    bug_notify,
+    unsubscribe,
+    subscribe,
+    watch,
+    unwatch
+)]
+pub async fn bug(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let bug_id = if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            bug_id
         } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id);
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+            return Ok(());
         }
     } else {
-        failure!(ctx, msg, "The first argument must be a bug id.");
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+        return Ok(());
+    };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+
+    let mut bug = match get_bug_from_id(ctx, bug_id, guild_id).await {
+        Ok(bug) => bug,
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    macro_rules! create_bug_buttons {
+        ($message_link:expr) => {
+            |c| {
+                c.create_action_row(|a| {
+                    a.create_button(|b| {
+                        b.style(ButtonStyle::Primary)
+                            .label("Subscribe")
+                            .custom_id(format!("bug_subscribe__{bug_id}"))
+                    });
+                    if let Some(ref link) = $message_link {
+                        a.create_button(|b| {
+                            b.style(ButtonStyle::Link).label("Message link").url(link)
+                        });
+                    }
+                    a
+                });
+
+                c
+            }
+        };
+        ($message_link:expr, $create_buttons:expr, $disabled:expr) => {
+            |c| {
+                c.create_action_row(|a| {
+                    a.create_button(|b| {
+                        b.style(ButtonStyle::Primary)
+                            .label("Subscribe")
+                            .custom_id(format!("bug_subscribe__{bug_id}"))
+                    });
+                    if let Some(link) = $message_link.as_ref() {
+                        a.create_button(|b| {
+                            b.style(ButtonStyle::Link).label("Message link").url(link)
+                        });
+                    }
+                    if $create_buttons {
+                        a.create_button(|b| {
+                            b.style(ButtonStyle::Success)
+                                .label("Resolve")
+                                .custom_id("resolve_bug")
+                                .disabled($disabled)
+                        });
+
+                        a.create_button(|b| {
+                            b.style(ButtonStyle::Danger)
+                                .label("Close")
+                                .custom_id("close_bug")
+                                .disabled($disabled)
+                        });
+                    }
+                    a
+                });
+                c
+            }
+        };
+    }
+
+    let linked_message = get_linked_message(ctx, &bug).await;
+    let message_link = linked_message.as_ref().map(|m| m.link()).ok();
+
+    let is_lotr_discord = msg.guild_id == Some(EOA_DISCORD);
+    let is_admin = match msg.guild_id {
+        Some(guild_id) => is_admin_user(ctx, guild_id, msg.author.id).await,
+        None => false,
+    };
+
+    let mut create_buttons = bug.status != BugStatus::Resolved
+        && bug.status != BugStatus::Closed
+        && !bug.archived
+        && (msg.author.id == OWNER_ID || (is_lotr_discord && is_admin));
+
+    let colour = bug.status.guild_colour(ctx, msg.guild_id).await;
+    let markers = StatusMarkers::of(ctx, msg.guild_id).await;
+    let branding = get_branding(ctx, msg.guild_id).await;
+    let mut response_message = msg
+        .channel_id
+        .send_message(ctx, |m| {
+            m.embed(create_bug_embed!(
+                bug,
+                linked_message,
+                colour,
+                markers,
+                branding,
+                prefix
+            ))
+            .components(create_bug_buttons!(message_link, create_buttons, false))
+        })
+        .await?;
+
+    if create_buttons {
+        let _collector = crate::metrics::collector_started();
+        // Listen to interactions for 120 seconds
+        while let Some(interaction) = CollectComponentInteraction::new(ctx)
+            .timeout(Duration::from_secs(120))
+            .channel_id(msg.channel_id)
+            .message_id(response_message.id)
+            .await
+        {
+            if interaction.user.id != msg.author.id {
+                retry_discord!(
+                    "refusing a bug status change",
+                    interaction.create_interaction_response(ctx, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|d| {
+                                d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                                    .content("You are not allowed to modify bug status!")
+                            })
+                    })
+                )?;
+            } else {
+                let new_status = match interaction.data.custom_id.as_str() {
+                    "resolve_bug" => BugStatus::Resolved,
+                    "close_bug" => BugStatus::Closed,
+                    _ => continue,
+                };
+                if in_maintenance(ctx).await {
+                    interaction
+                        .say_ephemeral(ctx, ":x: The bot is in maintenance mode.")
+                        .await;
+                    continue;
+                }
+
+                change_status_and_notify(ctx, bug_id, guild_id, new_status, msg.author.id).await?;
+
+                bug.status = new_status;
+                bug.status_since = chrono::Utc::now();
+                let colour = new_status.guild_colour(ctx, msg.guild_id).await;
+
+                retry_discord!(
+                    "updating the bug embed",
+                    interaction.create_interaction_response(ctx, |r| {
+                        r.kind(InteractionResponseType::UpdateMessage)
+                            .interaction_response_data(|m| {
+                                m.embeds([])
+                                    .create_embed(create_bug_embed!(
+                                        bug,
+                                        linked_message,
+                                        colour,
+                                        markers,
+                                        branding,
+                                        prefix
+                                    ))
+                                    .components(create_bug_buttons!(message_link))
+                            })
+                    })
+                )?;
+
+                if let Some(guild_id) = msg.guild_id {
+                    archive_bug_thread(ctx, guild_id, &bug).await;
+                }
+
+                create_buttons = false;
+
+                break;
+            }
+        }
+
+        if create_buttons {
+            // If no interaction was received after timeout, remove the buttons
+            retry_discord!(
+                "disabling the bug buttons",
+                response_message.edit(ctx, |m| {
+                    m.components(create_bug_buttons!(message_link, create_buttons, true))
+                })
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Archives the thread started on the bug report's message, if the guild's
+/// [`ARCHIVE_BUG_THREADS`] setting asks for it. A thread started on a message
+/// has the same id as the message. Bugs without a thread, or whose thread was
+/// deleted, are left alone.
+async fn archive_bug_thread(ctx: &Context, guild_id: GuildId, bug: &BugReport) {
+    let lock = match get_setting(ctx, guild_id, ARCHIVE_BUG_THREADS)
+        .await
+        .as_deref()
+    {
+        Some("true") => false,
+        Some("lock") => true,
+        _ => return,
+    };
+    let thread_id = ChannelId(bug.message_id.0);
+    match thread_id.to_channel(ctx).await {
+        Ok(Channel::Guild(channel))
+            if matches!(
+                channel.kind,
+                ChannelType::PublicThread | ChannelType::PrivateThread
+            ) => {}
+        _ => return,
+    }
+    // a thread locked by a moderator stays locked when only archived
+    if let Err(e) = thread_id
+        .edit_thread(ctx, |t| {
+            t.archived(true);
+            if lock {
+                t.locked(true);
+            }
+            t
+        })
+        .await
+    {
+        println!(
+            "=== ERROR ===\nCould not archive the thread of bug {}\nError: {}\n=== END ===",
+            bug.bug_id, e
+        );
+    }
+}
+
+/// Removes the `--dry-run` flag from the arguments, wherever it is, and
+/// returns whether it was present
+fn take_dry_run(args: &mut Args) -> bool {
+    if !args.raw().any(|arg| arg == "--dry-run") {
+        return false;
+    }
+    let rest = args
+        .raw()
+        .filter(|arg| *arg != "--dry-run")
+        .collect::<Vec<_>>()
+        .join(" ");
+    *args = Args::new(&rest, &[Delimiter::Single(' ')]);
+    true
+}
+
+/// Reports what a status change would do, without changing anything
+async fn dry_run_status_change(
+    ctx: &Context,
+    msg: &Message,
+    bug_id: u64,
+    new_status: BugStatus,
+) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    match get_bug_from_id(ctx, bug_id, guild_id).await {
+        Ok(bug) if bug.archived => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+        }
+        Ok(bug) if bug.status == new_status => {
+            msg.reply(
+                ctx,
+                format!(
+                    "**[Dry run]** {} is already `{}`. Nothing was changed.",
+                    prefix.id(bug_id),
+                    new_status
+                ),
+            )
+            .await?;
+        }
+        Ok(bug) => {
+            msg.reply(
+                ctx,
+                format!(
+                    "**[Dry run]** Would change {} from `{}` to `{}`. Nothing was changed.",
+                    prefix.id(bug_id),
+                    bug.status,
+                    new_status
+                ),
+            )
+            .await?;
+        }
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Change the status of a bug"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases("status")]
+pub async fn bug_status(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let dry_run = take_dry_run(&mut args);
+    if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            if let Ok(new_status) = args.single::<BugStatus>() {
+                if dry_run {
+                    return dry_run_status_change(ctx, msg, bug_id, new_status).await;
+                }
+                match change_status_and_notify(ctx, bug_id, guild_id, new_status, msg.author.id)
+                    .await
+                {
+                    Ok(old_status) => termite_success!(
+                        ctx,
+                        msg,
+                        t!(
+                            ctx,
+                            msg,
+                            "bug.status_changed",
+                            prefix.id(bug_id),
+                            old_status,
+                            new_status
+                        )
+                    ),
+                    Err(DbError::Archived) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+                    }
+                    Err(DbError::NotFound) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+                    }
+                    Err(e) => {
+                        db_failure!(ctx, msg, e);
+                        return Err(e.into());
+                    }
+                }
+            } else {
+                failure!(ctx, msg, "The second argument must be a bug status.")
+            }
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id))
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"))
+    }
+    Ok(())
+}
+
+/// Bug id in the title of a bug embed, after the status
+/// marker: `:red_circle: EoA-12: title [category]`
+fn bug_id_from_embed_title(title: &str) -> Option<&str> {
+    title.split_whitespace().nth(1)?.strip_suffix(':')
+}
+
+/// Changes the status of a bug when an admin replies to one of the bot's bug
+/// embeds with just a status keyword. Other replies are ignored, so that they
+/// don't get in the way of discussions.
+pub async fn handle_status_reply(ctx: &Context, msg: &Message, guild_id: GuildId) -> CommandResult {
+    let new_status = match msg.content.parse::<BugStatus>() {
+        Ok(status) if !msg.author.bot => status,
+        _ => return Ok(()),
+    };
+    let bug_id = match msg
+        .referenced_message
+        .as_deref()
+        .filter(|reply| reply.author.id == BOT_ID)
+        .and_then(|reply| reply.embeds.get(0)?.title.as_deref())
+        .and_then(bug_id_from_embed_title)
+    {
+        Some(bug_id) => match parse_bug_id(ctx, Some(guild_id), bug_id).await {
+            Some(bug_id) => bug_id,
+            None => return Ok(()),
+        },
+        None => return Ok(()),
+    };
+    if !is_admin_user(ctx, guild_id, msg.author.id).await {
+        return Ok(());
+    }
+    if in_maintenance(ctx).await {
+        failure!(ctx, msg, "The bot is in maintenance mode.");
+        return Ok(());
+    }
+
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    match change_status_and_notify(ctx, bug_id, guild_id, new_status, msg.author.id).await {
+        Ok(_) => termite!(ctx, msg),
+        Err(DbError::Archived) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+        }
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Change the status and the category of a bug"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases("triage")]
+pub async fn bug_triage(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let bug_id = match args.single::<String>() {
+        Ok(bug_id) => bug_id,
+        Err(_) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+            return Ok(());
+        }
+    };
+    let bug_id = match parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+        Some(bug_id) => bug_id,
+        None => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+            return Ok(());
+        }
+    };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    let (new_status, new_category) =
+        match (args.single::<BugStatus>(), args.single::<BugCategory>()) {
+            (Ok(status), Ok(category)) => (status, category),
+            _ => {
+                failure!(
+                    ctx,
+                    msg,
+                    "Usage:  `!bug triage <bug id> <status> <category>`, where the category is \
+one of `fa_renewed`, `fa_legacy`, `sa_renewed` or `sa_legacy`."
+                );
+                return Ok(());
+            }
+        };
+
+    let (old_status, old_category) = match triage_bug(
+        ctx,
+        bug_id,
+        guild_id,
+        new_status,
+        new_category,
+        msg.author.id,
+    )
+    .await
+    {
+        Ok(old) => old,
+        Err(DbError::Archived) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+        }
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    termite_success!(
+        ctx,
+        msg,
+        "Triaged {}: status `{}` → `{}`, category {} → {}",
+        prefix.id(bug_id),
+        old_status,
+        new_status,
+        old_category,
+        new_category
+    );
+    notify_status_change(ctx, bug_id, guild_id, old_status, new_status);
+
+    Ok(())
+}
+
+#[command]
+#[description = "Mark a bug as resolved"]
+#[usage = "<bug id> [version]"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+pub async fn resolve(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let dry_run = take_dry_run(&mut args);
+    if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            // checked first, the status must not change for an invalid version
+            let version = args.single::<String>().ok();
+            if matches!(&version, Some(version) if version.chars().count() > 32) {
+                failure!(ctx, msg, "The version can be at most 32 characters.");
+                return Ok(());
+            }
+            if dry_run {
+                dry_run_status_change(ctx, msg, bug_id, BugStatus::Resolved).await?;
+            } else {
+                match change_status_and_notify(
+                    ctx,
+                    bug_id,
+                    guild_id,
+                    BugStatus::Resolved,
+                    msg.author.id,
+                )
+                .await
+                {
+                    Ok(_) => {
+                        if let Some(version) = version {
+                            if let Err(e) = set_fixed_in(ctx, bug_id, guild_id, &version).await {
+                                db_failure!(ctx, msg, e);
+                                return Err(e.into());
+                            } else if version.parse::<ModVersion>().is_err() {
+                                msg.reply(
+                                    ctx,
+                                    format!(
+                                        "`{}` is not a `vX.Y.Z` version, so this bug won't be \
+listed by  `!bugs between`.",
+                                        sanitize_echo(&version)
+                                    ),
+                                )
+                                .await?;
+                            }
+                        }
+                        termite_success!(ctx, msg, t!(ctx, msg, "bug.resolved", prefix.id(bug_id)));
+                    }
+                    Err(DbError::Archived) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+                    }
+                    Err(DbError::NotFound) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+                    }
+                    Err(e) => {
+                        db_failure!(ctx, msg, e);
+                        return Err(e.into());
+                    }
+                }
+            }
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id))
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"))
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Mark a bug as closed"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases("close")]
+pub async fn bug_close(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let dry_run = take_dry_run(&mut args);
+    if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            if dry_run {
+                dry_run_status_change(ctx, msg, bug_id, BugStatus::Closed).await?;
+            } else {
+                match change_status_and_notify(
+                    ctx,
+                    bug_id,
+                    guild_id,
+                    BugStatus::Closed,
+                    msg.author.id,
+                )
+                .await
+                {
+                    Ok(_) => {
+                        termite_success!(ctx, msg, t!(ctx, msg, "bug.closed", prefix.id(bug_id)))
+                    }
+                    Err(DbError::Archived) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+                    }
+                    Err(DbError::NotFound) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+                    }
+                    Err(e) => {
+                        db_failure!(ctx, msg, e);
+                        return Err(e.into());
+                    }
+                }
+            }
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id))
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"))
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Add a link to a bug"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[sub_commands(bug_link_remove)]
+#[aliases("link")]
+pub async fn bug_link(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            if let Some(message) = &msg.referenced_message {
+                let title = args.rest();
+                if title.is_empty() {
+                    failure!(ctx, msg, "Specify a title for your message link!");
+                    return Ok(());
+                }
+                match add_link(ctx, bug_id, guild_id, &message.link(), title).await {
+                    Ok(link_id) => {
+                        termite_success!(
+                            ctx,
+                            msg,
+                            "Added link #{} to {}",
+                            link_id,
+                            prefix.id(bug_id)
+                        );
+                        notify_users(
+                            ctx,
+                            bug_id,
+                            guild_id,
+                            format!(
+                                "Link #{link_id} has been added to a bug you are subscribed to"
+                            ),
+                        )
+                        .await?;
+                    }
+                    Err(DbError::Archived) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+                    }
+                    Err(DbError::NotFound) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+                    }
+                    Err(e) => {
+                        db_failure!(ctx, msg, e);
+                        return Err(e.into());
+                    }
+                }
+            } else if let Some(link) = args
+                .single::<String>()
+                .ok()
+                .filter(|s| s.starts_with("http"))
+            {
+                let title = args.rest();
+                if title.is_empty() {
+                    failure!(ctx, msg, "Specify a title for your message link!");
+                    return Ok(());
+                }
+                match add_link(ctx, bug_id, guild_id, &link, title).await {
+                    Ok(link_id) => {
+                        termite_success!(
+                            ctx,
+                            msg,
+                            "Added link #{} to {}",
+                            link_id,
+                            prefix.id(bug_id)
+                        );
+                        notify_users(
+                            ctx,
+                            bug_id,
+                            guild_id,
+                            format!(
+                                "Link #{link_id} has been added to a bug you are subscribed to"
+                            ),
+                        )
+                        .await?;
+                    }
+                    Err(DbError::Archived) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+                    }
+                    Err(DbError::NotFound) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+                    }
+                    Err(e) => {
+                        db_failure!(ctx, msg, e);
+                        return Err(e.into());
+                    }
+                }
+            } else {
+                failure!(ctx, msg, "You need to either reference a message or specify a link to add to the bug report.");
+            }
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Remove a link from a bug"]
+#[checks(is_admin, is_lotr_discord, not_in_maintenance)]
+#[aliases("remove")]
+pub async fn bug_link_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            let link_num = args.single::<String>();
+            if let Ok(link_num) = link_num {
+                if let Ok(link_num) = link_num.trim_start_matches('#').parse::<u64>() {
+                    let link = match get_bug_from_id(ctx, bug_id, guild_id).await {
+                        Ok(bug) => bug.links.into_iter().find(|link| link.num == link_num),
+                        Err(DbError::NotFound) => {
+                            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+                            return Ok(());
+                        }
+                        Err(e) => {
+                            db_failure!(ctx, msg, e);
+                            return Err(e.into());
+                        }
+                    };
+                    let result = match &link {
+                        Some(link) => remove_link(ctx, bug_id, guild_id, link).await,
+                        None => Err(DbError::NotFound),
+                    };
+                    match result {
+                        Ok(()) => termite_success!(
+                            ctx,
+                            msg,
+                            "Successfully removed link #{} from {}",
+                            link_num,
+                            prefix.id(bug_id)
+                        ),
+                        Err(DbError::Archived) => {
+                            failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+                        }
+                        Err(DbError::NotFound) => failure!(
+                            ctx,
+                            msg,
+                            "Link #{} does not exist in {}",
+                            link_num,
+                            prefix.id(bug_id)
+                        ),
+                        Err(e) => {
+                            db_failure!(ctx, msg, e);
+                            return Err(e.into());
+                        }
+                    }
+                } else {
+                    failure!(ctx, msg, "`{}` is not a valid link number!", link_num);
+                }
+            } else {
+                failure!(ctx, msg, "The second argument must be a valid link number.");
+            }
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Hide a bug from the bug lists until a date"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases("snooze")]
+pub async fn bug_snooze(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            let until = match args.single::<String>().ok().as_deref() {
+                Some("off") => None,
+                Some(date) => match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                    Ok(date) if date > Utc::now().naive_utc().date() => Some(date.and_hms(0, 0, 0)),
+                    _ => {
+                        failure!(
+                            ctx,
+                            msg,
+                            "The second argument must be a future date, as `YYYY-MM-DD`, or `off`."
+                        );
+                        return Ok(());
+                    }
+                },
+                None => {
+                    failure!(
+                        ctx,
+                        msg,
+                        "Use `!bug snooze <bug id> <YYYY-MM-DD>`, or `!bug snooze <bug id> off`."
+                    );
+                    return Ok(());
+                }
+            };
+            // snoozing hides a bug from the open bugs, the others can't be
+            // snoozed. The errors are reported by `snooze_bug`.
+            if until.is_some() {
+                if let Ok(bug) = get_bug_from_id(ctx, bug_id, guild_id).await {
+                    if !bug.status.is_open() {
+                        failure!(
+                            ctx,
+                            msg,
+                            "{} is `{}`, only open bugs can be snoozed.",
+                            prefix.id(bug_id),
+                            bug.status
+                        );
+                        return Ok(());
+                    }
+                }
+            }
+            match snooze_bug(ctx, bug_id, guild_id, until).await {
+                Ok(()) => match until {
+                    Some(until) => termite_success!(
+                        ctx,
+                        msg,
+                        "Snoozed {} until {}",
+                        prefix.id(bug_id),
+                        discord_timestamp(&utc(until), 'D')
+                    ),
+                    None => {
+                        termite_success!(ctx, msg, "{} is no longer snoozed", prefix.id(bug_id))
+                    }
+                },
+                Err(DbError::Archived) => {
+                    failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+                }
+                Err(DbError::NotFound) => {
+                    failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+                }
+                Err(e) => {
+                    db_failure!(ctx, msg, e);
+                    return Err(e.into());
+                }
+            }
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Add or remove the labels of a bug"]
+#[checks(is_lotr_discord, is_admin)]
+#[sub_commands(bug_label_add, bug_label_remove)]
+#[aliases("label")]
+pub async fn bug_label(ctx: &Context, msg: &Message) -> CommandResult {
+    failure!(
+        ctx,
+        msg,
+        "Use `!bug label add <bug id> <label>` or `!bug label remove <bug id> <label>`."
+    );
+    Ok(())
+}
+
+#[command]
+#[description = "Add a label to a bug"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases("add")]
+pub async fn bug_label_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            if let Some(label) = args
+                .single::<String>()
+                .ok()
+                .as_deref()
+                .and_then(normalize_label)
+            {
+                match add_label(ctx, bug_id, guild_id, &label).await {
+                    Ok(true) => termite_success!(
+                        ctx,
+                        msg,
+                        "Added label `{}` to {}",
+                        label,
+                        prefix.id(bug_id)
+                    ),
+                    Ok(false) => failure!(
+                        ctx,
+                        msg,
+                        "{} already has the label `{}`",
+                        prefix.id(bug_id),
+                        label
+                    ),
+                    Err(DbError::Archived) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+                    }
+                    Err(DbError::NotFound) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+                    }
+                    Err(e) => {
+                        db_failure!(ctx, msg, e);
+                        return Err(e.into());
+                    }
+                }
+            } else {
+                failure!(
+                    ctx,
+                    msg,
+                    "The second argument must be a label of at most {} letters, digits, `-` or `_`.",
+                    MAX_LABEL_LENGTH
+                );
+            }
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Remove a label from a bug"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases("remove")]
+pub async fn bug_label_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            if let Some(label) = args
+                .single::<String>()
+                .ok()
+                .as_deref()
+                .and_then(normalize_label)
+            {
+                match remove_label(ctx, bug_id, guild_id, &label).await {
+                    Ok(()) => termite_success!(
+                        ctx,
+                        msg,
+                        "Removed label `{}` from {}",
+                        label,
+                        prefix.id(bug_id)
+                    ),
+                    Err(DbError::Archived) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+                    }
+                    Err(DbError::NotFound) => failure!(
+                        ctx,
+                        msg,
+                        "{} does not have the label `{}`",
+                        prefix.id(bug_id),
+                        label
+                    ),
+                    Err(e) => {
+                        db_failure!(ctx, msg, e);
+                        return Err(e.into());
+                    }
+                }
+            } else {
+                failure!(ctx, msg, "The second argument must be a valid label.");
+            }
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Change the category of a bug"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases("toggle")]
+pub async fn bug_toggle_edition(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            if let Ok(category) = args.single::<BugCategory>() {
+                match change_category(ctx, bug_id, guild_id, category).await {
+                    Ok(old_category) if category != old_category => {
+                        termite_success!(
+                            ctx,
+                            msg,
+                            "{} has been changed from {} to {}",
+                            prefix.id(bug_id),
+                            old_category,
+                            category
+                        );
+                        notify_users(
+                            ctx,
+                            bug_id,
+                            guild_id,
+                            format!(
+                                "A bug you are subscribed to has been changed from {} to {}",
+                                old_category, category
+                            ),
+                        )
+                        .await?;
+                    }
+                    Ok(_) => (),
+                    Err(DbError::Archived) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+                    }
+                    Err(DbError::NotFound) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+                    }
+                    Err(e) => {
+                        db_failure!(ctx, msg, e);
+                        return Err(e.into());
+                    }
+                }
+            } else {
+                failure!(
+                    ctx,
+                    msg,
+                    "The first argument must be one of \
+`fa_renewed`, `fa_legacy`, `sa_renewed` or `sa_legacy`"
+                );
+            }
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Change the title of a bug"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases(rename)]
+pub async fn bug_rename(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            let new_title = args.rest();
+            if new_title.is_empty() {
+                failure!(
+                    ctx,
+                    msg,
+                    t!(ctx, msg, "bug.missing_title", prefix.id(bug_id))
+                );
+            } else {
+                match change_title(ctx, bug_id, guild_id, new_title).await {
+                    Ok(()) => {
+                        termite_success!(
+                            ctx,
+                            msg,
+                            t!(
+                                ctx,
+                                msg,
+                                "bug.renamed",
+                                prefix.id(bug_id),
+                                sanitize_echo(new_title)
+                            )
+                        );
+                        notify_users(
+                            ctx,
+                            bug_id,
+                            guild_id,
+                            "The title of a bug you are subscribed to has been changed",
+                        )
+                        .await?
+                    }
+                    Err(DbError::Archived) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+                    }
+                    Err(DbError::NotFound) => {
+                        failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+                    }
+                    Err(e) => {
+                        db_failure!(ctx, msg, e);
+                        return Err(e.into());
+                    }
+                }
+            }
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Change or remove the summary of a bug"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases(summary)]
+pub async fn bug_summary(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let bug_id = if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            bug_id
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+            return Ok(());
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+        return Ok(());
+    };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    // without a text, the original message is shown again
+    let summary = Some(args.rest().trim()).filter(|summary| !summary.is_empty());
+
+    match change_summary(ctx, bug_id, guild_id, summary).await {
+        Ok(()) => {
+            if summary.is_some() {
+                termite_success!(
+                    ctx,
+                    msg,
+                    "Successfully changed the summary of {}",
+                    prefix.id(bug_id)
+                );
+            } else {
+                termite_success!(
+                    ctx,
+                    msg,
+                    "Removed the summary of {}, its original message is shown instead",
+                    prefix.id(bug_id)
+                );
+            }
+        }
+        Err(DbError::Archived) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)))
+        }
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)))
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Point a bug to the replied message"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases(setmessage)]
+pub async fn bug_set_message(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let bug_id = if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            bug_id
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+            return Ok(());
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+        return Ok(());
+    };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+
+    let mut referenced_message = if let Some(message) = &msg.referenced_message {
+        message.as_ref().clone()
+    } else {
+        failure!(
+            ctx,
+            msg,
+            "You must reply to the new message of the bug report!"
+        );
+        return Ok(());
+    };
+
+    let bug = match get_bug_from_id(ctx, bug_id, guild_id).await {
+        Ok(bug) if bug.archived => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Ok(bug) => bug,
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    // referenced messages do not include their guild id
+    referenced_message.guild_id = Some(guild_id);
+    set_bug_message(ctx, bug_id, guild_id, &referenced_message).await?;
+
+    if let Err(e) = referenced_message.react(ctx, bug.status.reaction()).await {
+        println!("Could not add reaction to bug report: {}", e);
+    }
+
+    termite_success!(
+        ctx,
+        msg,
+        "{} now points to {}",
+        prefix.id(bug_id),
+        referenced_message.link()
+    );
+
+    Ok(())
+}
+
+#[command]
+#[description = "Update the saved copy of a bug's message"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases(refresh)]
+pub async fn bug_refresh(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let bug_id = if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            bug_id
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+            return Ok(());
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+        return Ok(());
+    };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+
+    let bug = match get_bug_from_id(ctx, bug_id, guild_id).await {
+        Ok(bug) if bug.archived => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Ok(bug) => bug,
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    let message = match bug.channel_id.message(ctx, bug.message_id).await {
+        Ok(message) => message,
+        Err(SerenityError::Http(e))
+            if matches!(
+                e.as_ref(),
+                HttpError::UnsuccessfulRequest(ErrorResponse {
+                    error: DiscordJsonError {
+                        code: UNKNOWN_CHANNEL | UNKNOWN_MESSAGE,
+                        ..
+                    },
+                    ..
+                })
+            ) =>
+        {
+            failure!(
+                ctx,
+                msg,
+                "The original message of {} was deleted, {}",
+                prefix.id(bug_id),
+                if bug.saved_message.is_some() {
+                    "its saved copy is kept as is."
+                } else {
+                    "and there is no saved copy of it."
+                }
+            );
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let saved_message = SavedMessage::of(&message);
+    if bug.saved_message.as_ref() == Some(&saved_message) {
+        msg.reply(
+            ctx,
+            format!(
+                "The saved copy of {} is already up to date.",
+                prefix.id(bug_id)
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+    if let Err(e) = update_saved_message(ctx, bug_id, &saved_message).await {
+        db_failure!(ctx, msg, e);
+        return Err(e.into());
+    }
+    termite_success!(
+        ctx,
+        msg,
+        "Refreshed the saved copy of {} from {}",
+        prefix.id(bug_id),
+        message.link()
+    );
+
+    Ok(())
+}
+
+#[command]
+#[description = "List the latest status changes"]
+#[aliases(recent)]
+pub async fn bug_recent(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    if args.current() == Some("changes") {
+        args.advance();
+    }
+    let limit = args.single::<u32>().unwrap_or(10).clamp(1, 25);
+
+    let changes = match get_recent_status_changes(ctx, guild_id, limit).await {
+        Ok(changes) => changes,
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    let markers = StatusMarkers::of(ctx, msg.guild_id).await;
+    let lines = changes
+        .iter()
+        .map(|change| {
+            format!(
+                "{} **{}** → `{}`{}, {}",
+                markers.get(change.new_status),
+                prefix.id(change.bug_id),
+                change.new_status,
+                match (change.old_status, change.changed_by) {
+                    (None, _) => " (reported)".to_string(),
+                    (Some(_), Some(user_id)) => format!(" by {}", user_id.mention()),
+                    (Some(_), None) => "".to_string(),
+                },
+                discord_timestamp(&change.timestamp, 'R')
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let branding = get_branding(ctx, msg.guild_id).await;
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| branding.author(a));
+                e.colour(serenity::utils::Colour::TEAL);
+                e.title("Recent status changes");
+                e.description(if lines.is_empty() {
+                    "_No status changes yet!_".to_string()
+                } else {
+                    lines.join("\n")
+                });
+                e
+            })
+            .allowed_mentions(|a| a.empty_parse())
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[description = "Compare a bug with its state on a date"]
+#[aliases(diff)]
+pub async fn bug_diff(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let bug_id = if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            bug_id
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+            return Ok(());
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+        return Ok(());
+    };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+
+    let date = match args
+        .single::<String>()
+        .ok()
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok())
+    {
+        Some(date) => date,
+        None => {
+            failure!(
+                ctx,
+                msg,
+                "You must provide a date in the format `YYYY-MM-DD`!"
+            );
+            return Ok(());
+        }
+    };
+
+    let bug = match get_bug_from_id(ctx, bug_id, guild_id).await {
+        Ok(bug) => bug,
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    let then = match get_bug_snapshot(ctx, &bug, utc(date.and_hms(0, 0, 0))).await {
+        Ok(Some(snapshot)) => snapshot,
+        Ok(None) => {
+            failure!(ctx, msg, "This bug didn't exist yet on {}.", date);
+            return Ok(());
+        }
+        Err(e) => {
+            failure!(
+                ctx,
+                msg,
+                "Could not get the history of {}",
+                prefix.id(bug_id)
+            );
+            return Err(e);
+        }
+    };
+
+    let mut changes = Vec::new();
+    if then.status != bug.status {
+        changes.push(format!("Status: `{}` → `{}`", then.status, bug.status));
+    }
+    if then.title != bug.title {
+        changes.push("Title changed".to_string());
+    }
+
+    let colour = bug.status.guild_colour(ctx, msg.guild_id).await;
+    let markers = StatusMarkers::of(ctx, msg.guild_id).await;
+    let branding = get_branding(ctx, msg.guild_id).await;
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| branding.author(a));
+                e.colour(colour);
+                e.title(format!("{} since {}", prefix.id(bug_id), date));
+                e.description(if changes.is_empty() {
+                    "_No changes!_".to_string()
+                } else {
+                    changes.join("\n")
+                });
+                e.field(
+                    format!("On {}", date),
+                    format!(
+                        "{} {}\n{}",
+                        markers.get(then.status),
+                        then.status,
+                        then.title
+                    ),
+                    true,
+                );
+                e.field(
+                    "Now",
+                    format!("{} {}\n{}", markers.get(bug.status), bug.status, bug.title),
+                    true,
+                );
+                e
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[description = "Merge a bug into another one"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases(merge)]
+pub async fn bug_merge(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let mut bug_ids = Vec::with_capacity(2);
+    while bug_ids.len() < 2 {
+        let arg = match args.single::<String>() {
+            Ok(arg) => arg,
+            Err(_) => {
+                failure!(
+                    ctx,
+                    msg,
+                    "You must specify two bug ids:  `!bug merge <bug id> into <bug id>`"
+                );
+                return Ok(());
+            }
+        };
+        if arg.eq_ignore_ascii_case("into") {
+            continue;
+        }
+        match parse_bug_id(ctx, msg.guild_id, &arg).await {
+            Some(bug_id) => bug_ids.push(bug_id),
+            None => {
+                failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", arg));
+                return Ok(());
+            }
+        }
+    }
+    let (bug_id, into) = (bug_ids[0], bug_ids[1]);
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+
+    if bug_id == into {
+        failure!(ctx, msg, "Cannot merge a bug into itself!");
+        return Ok(());
+    }
+
+    let bug = match get_bug_from_id(ctx, bug_id, guild_id).await {
+        Ok(bug) if bug.archived => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Ok(bug) => bug,
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+    if let Some(merged_into) = bug.merged_into {
+        failure!(
+            ctx,
+            msg,
+            "{} has already been merged into {}!",
+            prefix.id(bug_id),
+            prefix.id(merged_into)
+        );
+        return Ok(());
+    }
+    let target = match get_bug_from_id(ctx, into, guild_id).await {
+        Ok(bug) if bug.archived => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.archived", prefix.id(into)));
+            return Ok(());
+        }
+        Ok(bug) => bug,
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(into)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+    if target.merged_into.is_some() {
+        failure!(
+            ctx,
+            msg,
+            "Cannot merge into {}, it was merged itself!",
+            prefix.id(into)
+        );
+        return Ok(());
+    }
+
+    let confirmed = confirm(
+        ctx,
+        msg,
+        format!(
+            "This will move the links, subscribers and watchers of {0} to {1}, and close {0}.",
+            prefix.id(bug_id),
+            prefix.id(into)
+        ),
+    )
+    .await?;
+    if !confirmed {
+        failure!(ctx, msg, "Bug merge cancelled.");
+        return Ok(());
+    }
+
+    if let Err(e) = merge_bugs(ctx, &bug, into).await {
+        failure!(
+            ctx,
+            msg,
+            "Could not merge {} into {}",
+            prefix.id(bug_id),
+            prefix.id(into)
+        );
+        return Err(e.into());
+    }
+
+    termite_success!(
+        ctx,
+        msg,
+        "Merged {} into {}",
+        prefix.id(bug_id),
+        prefix.id(into)
+    );
+
+    Ok(())
+}
+
+#[command]
+#[description = "Change the status markers of the bugtracker"]
+#[checks(is_admin, not_in_maintenance)]
+#[only_in(guilds)]
+#[aliases(marker, emoji)]
+pub async fn bug_marker(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    let status = match args.single::<BugStatus>() {
+        Ok(status) => status,
+        Err(_) => {
+            failure!(
+                ctx,
+                msg,
+                "You must specify a status:  `!bug marker <status> [emoji|reset]`"
+            );
+            return Ok(());
+        }
+    };
+
+    match args.single::<String>().ok().as_deref() {
+        None => {
+            let markers = StatusMarkers::of(ctx, msg.guild_id).await;
+            msg.reply(
+                ctx,
+                format!("The marker of `{}` bugs is {}", status, markers.get(status)),
+            )
+            .await?;
+        }
+        Some("reset" | "default") => {
+            remove_setting(ctx, guild_id, &status_marker(status)).await?;
+            success!(
+                ctx,
+                msg,
+                "Reset the marker of `{}` bugs to {}",
+                status,
+                status.marker()
+            );
+        }
+        Some(marker) => {
+            let marker = match validate_marker(marker) {
+                Ok(marker) => marker,
+                Err(e) => {
+                    failure!(ctx, msg, "The marker {}.", e);
+                    return Ok(());
+                }
+            };
+            set_setting(ctx, guild_id, &status_marker(status), &marker).await?;
+            success!(
+                ctx,
+                msg,
+                "Set the marker of `{}` bugs to {}",
+                status,
+                marker
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[command]
+#[description = "Change the status colours of the bugtracker"]
+#[checks(is_admin, not_in_maintenance)]
+#[only_in(guilds)]
+#[aliases(colour, color)]
+pub async fn bug_colour(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    let status = match args.single::<BugStatus>() {
+        Ok(status) => status,
+        Err(_) => {
+            failure!(
+                ctx,
+                msg,
+                "You must specify a status:  `!bug colour <status> [#RRGGBB|reset]`"
+            );
+            return Ok(());
+        }
+    };
+
+    match args.single::<String>().ok().as_deref() {
+        None => {
+            let colour = status.guild_colour(ctx, msg.guild_id).await;
+            msg.reply(
+                ctx,
+                format!("The colour of `{}` bugs is `#{}`.", status, colour.hex()),
+            )
+            .await?;
+        }
+        Some("reset" | "default") => {
+            remove_setting(ctx, guild_id, &status_colour(status)).await?;
+            success!(
+                ctx,
+                msg,
+                "Reset the colour of `{}` bugs to `#{}`.",
+                status,
+                status.colour().hex()
+            );
+        }
+        Some(colour) => {
+            let hex = colour.trim_start_matches('#');
+            if hex.len() != 6 || u32::from_str_radix(hex, 16).is_err() {
+                failure!(ctx, msg, "`{}` is not a valid `#RRGGBB` colour!", colour);
+                return Ok(());
+            }
+            let hex = hex.to_uppercase();
+            set_setting(ctx, guild_id, &status_colour(status), &hex).await?;
+            success!(
+                ctx,
+                msg,
+                "Set the colour of `{}` bugs to `#{}`.",
+                status,
+                hex
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[command]
+#[description = "Change the name and icon of the bugtracker"]
+#[checks(is_admin, not_in_maintenance)]
+#[only_in(guilds)]
+#[aliases(branding)]
+pub async fn bug_branding(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    match args.single::<String>().ok().as_deref() {
+        None => {
+            let branding = get_branding(ctx, msg.guild_id).await;
+            msg.channel_id
+                .send_message(ctx, |m| {
+                    m.embed(|e| {
+                        e.author(|a| branding.author(a));
+                        e.description(
+                            "This is the author of the bugtracker embeds on this server. Change it with \
+ `!bug branding name <name>`  and  `!bug branding icon <url>`.",
+                        )
+                    })
+                    .reference_message(msg)
+                })
+                .await?;
+        }
+        Some("name") => {
+            let name = args.rest().trim();
+            if name.is_empty() || name.chars().count() > 256 {
+                failure!(ctx, msg, "The name must be between 1 and 256 characters.");
+                return Ok(());
+            }
+            set_setting(ctx, guild_id, BRANDING_NAME, name).await?;
+            success!(ctx, msg, "Set the bugtracker name to **{}**.", name);
+        }
+        Some("icon") => {
+            let url = args.rest().trim();
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                failure!(ctx, msg, "The icon must be an image URL.");
+                return Ok(());
+            }
+            set_setting(ctx, guild_id, BRANDING_ICON, url).await?;
+            success!(ctx, msg, "Set the bugtracker icon.");
+        }
+        Some("reset" | "default") => {
+            remove_setting(ctx, guild_id, BRANDING_NAME).await?;
+            remove_setting(ctx, guild_id, BRANDING_ICON).await?;
+            success!(ctx, msg, "Reset the bugtracker branding.");
+        }
+        Some(_) => {
+            failure!(
+                ctx,
+                msg,
+                "Usage:  `!bug branding [name <name> | icon <url> | reset]`"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[command]
+#[description = "Change the prefix of the bug ids"]
+#[checks(is_admin, not_in_maintenance)]
+#[only_in(guilds)]
+#[aliases(prefix)]
+pub async fn bug_prefix(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    match args.single::<String>().ok().as_deref() {
+        None => {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            msg.reply(
+                ctx,
+                format!(
+                    "Bug ids are displayed as `{}` on this server.",
+                    prefix.id(12)
+                ),
+            )
+            .await?;
+        }
+        Some("reset" | "default") => {
+            remove_setting(ctx, guild_id, BUG_PREFIX).await?;
+            success!(ctx, msg, "Reset the bug id prefix.");
+        }
+        Some(prefix) if validate_setting(BUG_PREFIX, prefix).is_ok() => {
+            set_setting(ctx, guild_id, BUG_PREFIX, prefix).await?;
+            success!(
+                ctx,
+                msg,
+                "Bug ids will now be displayed as `{}-12`.",
+                prefix
+            );
+        }
+        Some(_) => {
+            failure!(
+                ctx,
+                msg,
+                "The prefix must be 1 to 10 letters or digits, starting with a letter. Usage:  \
+`!bug prefix [<prefix> | reset]`"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `url` is a Discord webhook URL
+fn is_webhook_url(url: &str) -> bool {
+    ["discord.com", "discordapp.com"]
+        .iter()
+        .flat_map(|domain| {
+            [
+                format!("https://{}/api/webhooks/", domain),
+                format!("https://ptb.{}/api/webhooks/", domain),
+                format!("https://canary.{}/api/webhooks/", domain),
+            ]
+        })
+        .find_map(|prefix| url.strip_prefix(&prefix))
+        .and_then(|path| path.split_once('/'))
+        .map_or(false, |(id, token)| {
+            id.parse::<u64>().is_ok() && !token.is_empty() && !token.contains('/')
+        })
+}
+
+/// Saves the webhook of `!bug webhook add`. The errors must not reach the after
+/// hook, which logs the message and with it the webhook token.
+async fn add_webhook(
+    ctx: &Context,
+    msg: &Message,
+    guild_id: GuildId,
+    mut args: Args,
+) -> CommandResult {
+    let name = args.single::<String>().unwrap_or_default().to_lowercase();
+    let url = args.single::<String>().unwrap_or_default();
+    let avatar_url = match args.current() {
+        Some(url) if url.starts_with("https://") || url.starts_with("http://") => {
+            let url = url.to_string();
+            args.advance();
+            Some(url)
+        }
+        _ => None,
+    };
+    let username = Some(args.rest().trim().to_string()).filter(|name| !name.is_empty());
+
+    // the URL is a secret, it shouldn't stay in the channel
+    if let Err(e) = msg.delete(ctx).await {
+        println!("Could not delete a webhook URL: {}", e);
+        msg.reply(
+            ctx,
+            "Could not delete your message, delete it yourself: it holds the webhook token!",
+        )
+        .await?;
+    }
+
+    if name.is_empty()
+        || name.len() > MAX_WEBHOOK_NAME_LENGTH
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        msg.channel_id
+            .say(
+                ctx,
+                format!(
+                    "The webhook name must be at most {} letters, digits, `-` or `_`.",
+                    MAX_WEBHOOK_NAME_LENGTH
+                ),
+            )
+            .await?;
+        return Ok(());
+    }
+    if !is_webhook_url(&url) {
+        msg.channel_id
+            .say(
+                ctx,
+                "The second argument must be a Discord webhook URL. Usage:  \
+`!bug webhook add <name> <webhook url> [avatar url] [display name]`",
+            )
+            .await?;
+        return Ok(());
+    }
+    if username
+        .as_ref()
+        .map_or(false, |name| name.chars().count() > 80)
+    {
+        msg.channel_id
+            .say(ctx, "The display name must be at most 80 characters.")
+            .await?;
+        return Ok(());
+    }
+
+    let webhook = BugWebhook {
+        name,
+        url,
+        username,
+        avatar_url,
+    };
+    if let Err(e) = set_webhook(ctx, guild_id, &webhook).await {
+        msg.channel_id
+            .say(
+                ctx,
+                "Could not save the webhook, try again in a few minutes.",
+            )
+            .await?;
+        return Err(e.into());
+    }
+    msg.channel_id
+        .say(
+            ctx,
+            format!(
+                "Saved the webhook `{0}`. Post bugs to it with  `!bug post <bug id> {0}`.",
+                webhook.name
+            ),
+        )
+        .await?;
+    Ok(())
+}
+
+#[command]
+#[description = "List, add or remove the webhooks bugs are posted to"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[only_in(guilds)]
+#[aliases("webhook", "webhooks")]
+pub async fn bug_webhook(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    match args.single::<String>().ok().as_deref() {
+        None | Some("list") => {
+            let webhooks = match get_webhooks(ctx, guild_id).await {
+                Ok(webhooks) => webhooks,
+                Err(e) => {
+                    db_failure!(ctx, msg, e);
+                    return Err(e.into());
+                }
+            };
+            // the URLs hold the webhook tokens, they are never displayed
+            let content = if webhooks.is_empty() {
+                "No webhooks yet. Add one with  `!bug webhook add <name> <webhook url> \
+[avatar url] [display name]`."
+                    .to_string()
+            } else {
+                webhooks
+                    .iter()
+                    .map(|w| match &w.username {
+                        Some(username) => format!("`{}`, posting as **{}**", w.name, username),
+                        None => format!("`{}`", w.name),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            msg.reply(ctx, content).await?;
+        }
+        Some("add") => {
+            if let Err(e) = add_webhook(ctx, msg, guild_id, args).await {
+                println!("=== ERROR ===\nCould not add a webhook: {}\n=== END ===", e);
+            }
+        }
+        Some("remove" | "delete") => {
+            let name = args.single::<String>().unwrap_or_default().to_lowercase();
+            match remove_webhook(ctx, guild_id, &name).await {
+                Ok(()) => success!(ctx, msg, "Removed the webhook `{}`.", name),
+                Err(DbError::NotFound) => {
+                    failure!(
+                        ctx,
+                        msg,
+                        "There is no webhook named `{}`.",
+                        sanitize_echo(&name)
+                    )
+                }
+                Err(e) => {
+                    db_failure!(ctx, msg, e);
+                    return Err(e.into());
+                }
+            }
+        }
+        Some(_) => {
+            failure!(
+                ctx,
+                msg,
+                "Usage:  `!bug webhook [list | add <name> <webhook url> [avatar url] [display name] \
+| remove <name>]`"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Posts the embed of a bug through a [webhook][bug_webhook], e.g. to cross-post
+/// it to a partner server
+#[command]
+#[description = "Post a bug through a webhook"]
+#[checks(is_lotr_discord, is_admin)]
+#[aliases("post")]
+pub async fn bug_post(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    let bug_id = match args.single::<String>() {
+        Ok(bug_id) => match parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            Some(bug_id) => bug_id,
+            None => {
+                failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+                return Ok(());
+            }
+        },
+        Err(_) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+            return Ok(());
+        }
+    };
+    let name = match args.single::<String>() {
+        Ok(name) => name.to_lowercase(),
+        Err(_) => {
+            failure!(ctx, msg, "Usage:  `!bug post <bug id> <webhook name>`");
+            return Ok(());
+        }
+    };
+
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    let bug = match get_bug_from_id(ctx, bug_id, guild_id).await {
+        Ok(bug) => bug,
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+    let config = match get_webhook(ctx, guild_id, &name).await {
+        Ok(config) => config,
+        Err(DbError::NotFound) => {
+            failure!(
+                ctx,
+                msg,
+                "There is no webhook named `{}`. Add one with  `!bug webhook add`.",
+                sanitize_echo(&name)
+            );
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    let linked_message = get_linked_message(ctx, &bug).await;
+    let colour = bug.status.guild_colour(ctx, msg.guild_id).await;
+    let markers = StatusMarkers::of(ctx, msg.guild_id).await;
+    let branding = get_branding(ctx, msg.guild_id).await;
+    let embed = Embed::fake(create_bug_embed!(
+        bug,
+        linked_message,
+        colour,
+        markers,
+        branding,
+        prefix
+    ));
+
+    let res = match ctx.http.get_webhook_from_url(&config.url).await {
+        Ok(webhook) => {
+            webhook
+                .execute(ctx, true, |w| {
+                    if let Some(username) = &config.username {
+                        w.username(username);
+                    }
+                    if let Some(avatar_url) = &config.avatar_url {
+                        w.avatar_url(avatar_url);
+                    }
+                    w.embeds(vec![embed])
+                })
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    match res {
+        Ok(_) => success!(ctx, msg),
+        Err(SerenityError::Http(e))
+            if matches!(
+                e.as_ref(),
+                HttpError::UnsuccessfulRequest(ErrorResponse {
+                    error: DiscordJsonError {
+                        code: UNKNOWN_WEBHOOK,
+                        ..
+                    },
+                    ..
+                })
+            ) =>
+        {
+            failure!(
+                ctx,
+                msg,
+                "The webhook `{}` was deleted on Discord. Remove it with  `!bug webhook remove {}`  \
+and add a new one.",
+                config.name,
+                config.name
+            );
+        }
+        Err(e) => {
+            failure!(
+                ctx,
+                msg,
+                "Could not post {} through the webhook `{}`.",
+                prefix.id(bug_id),
+                config.name
+            );
+            return Err(e.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Channel and message ids of a Discord message link
+fn parse_message_link(url: &str) -> Option<(ChannelId, MessageId)> {
+    let path = ["discord.com", "discordapp.com"]
+        .iter()
+        .flat_map(|domain| {
+            [
+                format!("https://{}/channels/", domain),
+                format!("https://ptb.{}/channels/", domain),
+                format!("https://canary.{}/channels/", domain),
+            ]
+        })
+        .find_map(|prefix| url.strip_prefix(&prefix))?;
+
+    let mut ids = path.trim_end_matches('/').split('/');
+    let _guild = ids.next()?;
+    let channel_id = ids.next()?.parse().ok()?;
+    let message_id = ids.next()?.parse().ok()?;
+    if ids.next().is_some() {
+        return None;
+    }
+    Some((ChannelId(channel_id), MessageId(message_id)))
+}
+
+/// Formats a bug as a markdown report, to be copied into a GitHub issue
+fn bug_markdown(
+    bug: &BugReport,
+    id: &str,
+    message: Option<&SavedMessage>,
+    message_link: Option<&str>,
+) -> String {
+    const DATE_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+    let mut report = format!("## {}: {}\n\n", id, bug.title);
+    report.push_str(&format!(
+        "**Status:** {}\n**Category:** {}\n",
+        bug.status, bug.category
+    ));
+    match message {
+        Some(message) => report.push_str(&format!(
+            "**Reported:** {} by {}\n",
+            bug.timestamp.format(DATE_FORMAT),
+            message.author
+        )),
+        None => report.push_str(&format!(
+            "**Reported:** {}\n",
+            bug.timestamp.format(DATE_FORMAT)
+        )),
+    }
+    report.push_str(&format!(
+        "**Status since:** {}\n",
+        bug.status_since.format(DATE_FORMAT)
+    ));
+    if !bug.labels.is_empty() {
+        report.push_str(&format!("**Labels:** {}\n", bug.labels.join(", ")));
+    }
+    let description = bug
+        .summary
+        .as_deref()
+        .or_else(|| message.map(|message| message.content.as_str()))
+        .unwrap_or_default();
+    if !description.is_empty() {
+        report.push_str(&format!("\n### Description\n\n{}\n", description));
+    }
+    if let Some(message) = message {
+        if let Some(url) = &message.attachment_url {
+            report.push_str(&format!("\n![attachment]({})\n", url));
+        }
+    }
+    if !bug.links.is_empty() || message_link.is_some() {
+        report.push_str("\n### Links\n\n");
+        if let Some(link) = message_link {
+            report.push_str(&format!("- [Original message]({})\n", link));
+        }
+        for link in &bug.links {
+            report.push_str(&format!("- [{}]({})\n", link.title, link.url));
+        }
+    }
+    report
+}
+
+/// Posts a bug as a markdown report, in a code block so it can be copied
+/// verbatim
+#[command]
+#[description = "Post a bug as a markdown report"]
+#[aliases(md, markdown)]
+pub async fn bug_markdown_export(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    let bug_id = match args.single::<String>() {
+        Ok(bug_id) => match parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            Some(bug_id) => bug_id,
+            None => {
+                failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+                return Ok(());
+            }
+        },
+        Err(_) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+            return Ok(());
+        }
+    };
+    let bug = match get_bug_from_id(ctx, bug_id, guild_id).await {
+        Ok(bug) => bug,
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    let linked_message = get_linked_message(ctx, &bug).await;
+    let (message, message_link) = match &linked_message {
+        Ok(message) => (Some(SavedMessage::of(message)), Some(message.link())),
+        Err(_) => (bug.saved_message.clone(), None),
+    };
+    let report = bug_markdown(
+        &bug,
+        &prefix.id(bug_id),
+        message.as_ref(),
+        message_link.as_deref(),
+    );
+
+    // a code block can't hold triple backticks, send those as a file instead
+    if report.len() < 1980 && !report.contains("```") {
+        msg.reply(ctx, format!("```md\n{}```", report)).await?;
+    } else {
+        msg.channel_id
+            .send_message(ctx, |m| {
+                m.add_file((report.as_bytes(), format!("bug-{}.md", bug_id).as_str()))
+                    .reference_message(msg)
+            })
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Summarizes the thread started on a bug report's message, for triagers
+/// looking for the bugs still being discussed: participants and latest
+/// activity in the last [`BUG_DISCUSSION_MESSAGES`] messages, without
+/// reposting them
+#[command]
+#[description = "List the bug threads with recent activity"]
+#[aliases(discussion)]
+pub async fn bug_discussion(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    let bug_id = match args.single::<String>() {
+        Ok(bug_id) => match parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            Some(bug_id) => bug_id,
+            None => {
+                failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+                return Ok(());
+            }
+        },
+        Err(_) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+            return Ok(());
+        }
+    };
+    let bug = match get_bug_from_id(ctx, bug_id, guild_id).await {
+        Ok(bug) => bug,
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    // a thread started on a message has the same id as the message
+    let thread_id = ChannelId(bug.message_id.0);
+    let thread = match thread_id.to_channel(ctx).await {
+        Ok(Channel::Guild(channel))
+            if matches!(
+                channel.kind,
+                ChannelType::PublicThread | ChannelType::PrivateThread
+            ) =>
+        {
+            channel
+        }
+        _ => {
+            failure!(
+                ctx,
+                msg,
+                "{} has no discussion thread, or it was deleted.",
+                prefix.id(bug_id)
+            );
+            return Ok(());
+        }
+    };
+    let messages = thread_id
+        .messages(ctx, |r| r.limit(BUG_DISCUSSION_MESSAGES))
+        .await?;
+
+    let mut participants: Vec<UserId> = messages
+        .iter()
+        .filter(|message| !message.author.bot)
+        .map(|message| message.author.id)
+        .collect();
+    participants.sort_unstable();
+    participants.dedup();
+    // newest first
+    let latest = match messages.first() {
+        Some(message) => format!(
+            "{} by {}",
+            discord_timestamp(&message.timestamp, 'R'),
+            message.author.mention()
+        ),
+        None => "No messages yet".to_string(),
+    };
+    let message_count = if messages.len() as u64 >= BUG_DISCUSSION_MESSAGES {
+        format!("{}+", messages.len())
+    } else {
+        messages.len().to_string()
+    };
+    let archived = thread
+        .thread_metadata
+        .map_or(false, |metadata| metadata.archived);
+
+    let colour = bug.status.guild_colour(ctx, msg.guild_id).await;
+    let branding = get_branding(ctx, msg.guild_id).await;
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| branding.author(a));
+                e.colour(colour);
+                e.title(format!("Discussion of {}", prefix.id(bug_id)));
+                e.description(format!("{}\n<#{}>", bug.title, thread_id));
+                e.field("Participants", participants.len(), true);
+                e.field("Messages", message_count, true);
+                e.field("Latest activity", latest, false);
+                if archived {
+                    e.footer(|f| f.text("This thread is archived"));
+                }
+                e
+            })
+            .reference_message(msg)
+            .allowed_mentions(|a| a.empty_parse())
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Moves old resolved and closed bugs to the archive table
+#[command]
+#[description = "Archive the old resolved and closed bugs"]
+#[checks(not_in_maintenance)]
+#[owners_only]
+#[aliases(archive)]
+pub async fn bug_archive(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let before = match (args.single::<String>().as_deref(), args.single::<String>()) {
+        (Ok("before"), Ok(date)) => NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok(),
+        _ => None,
+    };
+    let before = match before {
+        Some(before) => before,
+        None => {
+            failure!(ctx, msg, "Usage: `!bug archive before <YYYY-MM-DD>`");
+            return Ok(());
+        }
+    };
+
+    let confirmed = confirm(
+        ctx,
+        msg,
+        format!(
+            "This will archive the bugs resolved or closed before {}. They can still be \
+displayed, but no longer changed.",
+            before
+        ),
+    )
+    .await?;
+    if !confirmed {
+        failure!(ctx, msg, "Bug archive cancelled.");
+        return Ok(());
+    }
+
+    match archive_bugs(ctx, guild_id, before.and_hms(0, 0, 0)).await {
+        Ok(count) => {
+            println!(
+                "Archived {} bugs resolved or closed before {}",
+                count, before
+            );
+            success!(ctx, msg, "Archived {} bugs.", count);
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Posts the stored fields of a bug as JSON, to debug odd renderings
+#[command]
+#[description = "Post the stored fields of a bug as JSON"]
+#[owners_only]
+#[aliases(raw)]
+pub async fn bug_raw(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let bug_id = match args.single::<String>() {
+        Ok(bug_id) => match parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            Some(bug_id) => bug_id,
+            None => {
+                failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+                return Ok(());
+            }
+        },
+        Err(_) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+            return Ok(());
+        }
+    };
+    let bug = match get_bug_from_id(ctx, bug_id, guild_id).await {
+        Ok(bug) => bug,
+        Err(DbError::NotFound) => {
+            let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    let json = serde_json::to_string_pretty(&bug)?;
+    if json.len() < 1980 {
+        msg.reply(
+            ctx,
+            format!("```json\n{}\n```", json.replace("```", "`\u{200B}``")),
+        )
+        .await?;
+    } else {
+        msg.channel_id
+            .send_message(ctx, |m| {
+                m.add_file((json.as_bytes(), format!("bug-{}.json", bug_id).as_str()))
+                    .reference_message(msg)
+            })
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[command]
+#[description = "List the available audits"]
+#[owners_only]
+#[aliases(audit)]
+#[sub_commands(audit_links)]
+pub async fn bug_audit(ctx: &Context, msg: &Message) -> CommandResult {
+    msg.reply(
+        ctx,
+        "Available audits:  `!bug audit links [--remove] [--check-urls]`",
+    )
+    .await?;
+    Ok(())
+}
+
+/// Reports the bug links that point to deleted Discord messages
+#[command]
+#[description = "Report the bug links to deleted messages"]
+#[owners_only]
+#[aliases(links)]
+pub async fn audit_links(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let remove = args.raw().any(|arg| arg == "--remove");
+    let check_urls = args.raw().any(|arg| arg == "--check-urls");
+    // only a write with `--remove`, so it has no maintenance check
+    if remove && in_maintenance(ctx).await {
+        failure!(ctx, msg, "The bot is in maintenance mode.");
+        return Ok(());
+    }
+
+    let links = match get_all_links(ctx, guild_id).await {
+        Ok(links) => links,
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    msg.reply(
+        ctx,
+        format!("Checking {} links, this may take a while...", links.len()),
+    )
+    .await?;
+    let typing = msg.channel_id.start_typing(&ctx.http);
+
+    let rclient = get_reqwest_client!(ctx);
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    let mut orphaned = Vec::new();
+    let mut skipped = 0;
+    let mut removed = 0;
+    for (bug_id, link) in links {
+        let missing = if let Some((channel_id, message_id)) = parse_message_link(&link.url) {
+            match channel_id.message(ctx, message_id).await {
+                Ok(_) => false,
+                Err(SerenityError::Http(e)) => matches!(
+                    e.as_ref(),
+                    HttpError::UnsuccessfulRequest(ErrorResponse {
+                        error: DiscordJsonError {
+                            code: UNKNOWN_CHANNEL | UNKNOWN_MESSAGE,
+                            ..
+                        },
+                        ..
+                    })
+                ),
+                Err(_) => false,
+            }
+        } else if check_urls {
+            match rclient.head(&link.url).send().await {
+                Ok(res) => matches!(
+                    res.status(),
+                    reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE
+                ),
+                Err(_) => false,
+            }
+        } else {
+            skipped += 1;
+            continue;
+        };
+
+        if missing {
+            if remove {
+                match remove_link(ctx, bug_id, guild_id, &link).await {
+                    Ok(()) => removed += 1,
+                    Err(e) => println!(
+                        "=== ERROR ===\nCould not remove link #{} from {}: {}\n=== END ===",
+                        link.num,
+                        prefix.id(bug_id),
+                        e
+                    ),
+                }
+            }
+            orphaned.push(format!("{} {}", prefix.id(bug_id), link));
+        }
+        tokio::time::sleep(LINK_AUDIT_DELAY).await;
+    }
+
+    if let Ok(typing) = typing {
+        typing.stop();
+    }
+
+    let mut description = String::new();
+    for (i, line) in orphaned.iter().enumerate() {
+        if description.len() + line.len() + 40 > 4096 {
+            description.push_str(&format!("_...and {} more_", orphaned.len() - i));
+            break;
+        }
+        description.push_str(line);
+        description.push('\n');
+    }
+    if orphaned.is_empty() {
+        description.push_str("_No orphaned links!_");
+    }
+
+    let branding = get_branding(ctx, msg.guild_id).await;
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| branding.author(a));
+                e.colour(serenity::utils::Colour::TEAL);
+                e.title(format!(
+                    "{} orphaned links{}",
+                    orphaned.len(),
+                    if remove && !orphaned.is_empty() {
+                        format!(" ({} removed)", removed)
+                    } else {
+                        String::new()
+                    }
+                ));
+                e.description(description);
+                if skipped != 0 {
+                    e.footer(|f| {
+                        f.text(format!(
+                            "{} non-Discord links skipped, use --check-urls to check them",
+                            skipped
+                        ))
+                    });
+                }
+                e
+            });
+            m.reference_message(msg)
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Splits CSV text into rows of trimmed fields, with the line number where
+/// each row starts. Fields may be quoted, with `""` for a literal quote.
+/// Blank lines are skipped.
+fn parse_csv(text: &str) -> Vec<(usize, Vec<String>)> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+
+    let mut end_row = |row_line: usize, row: &mut Vec<String>, field: &mut String| {
+        row.push(field.trim().to_string());
+        field.clear();
+        if row.iter().any(|f| !f.is_empty()) {
+            rows.push((row_line, std::mem::take(row)));
+        } else {
+            row.clear();
+        }
+    };
+
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            ',' if !in_quotes => {
+                row.push(field.trim().to_string());
+                field.clear();
+            }
+            '\n' if !in_quotes => {
+                end_row(row_line, &mut row, &mut field);
+                line += 1;
+                row_line = line;
+            }
+            '\r' if !in_quotes => (),
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    end_row(row_line, &mut row, &mut field);
+
+    rows
+}
+
+/// Reads a bug from a CSV row: `title, status, category[, link]`. Empty
+/// status and category fields use the defaults.
+fn parse_import_row(row: &[String]) -> Result<ImportedBug, String> {
+    let field = |i: usize| row.get(i).map(String::as_str).unwrap_or_default();
+
+    let title = field(0);
+    if title.is_empty() {
+        return Err("missing title".to_string());
+    }
+    if title.len() > 255 {
+        return Err("title longer than 255 bytes".to_string());
+    }
+    let status = match field(1) {
+        "" => BugStatus::default(),
+        s => s
+            .parse()
+            .map_err(|_| format!("unknown status `{}`", sanitize_echo(s)))?,
+    };
+    let category = match field(2) {
+        "" => BugCategory::default(),
+        c => c
+            .parse()
+            .map_err(|_| format!("unknown category `{}`", sanitize_echo(c)))?,
+    };
+    let link = match field(3) {
+        "" => None,
+        l if l.starts_with("https://") || l.starts_with("http://") => Some(l.to_string()),
+        _ => return Err("the link must be a URL".to_string()),
+    };
+    if row.len() > 4 {
+        return Err(format!("{} columns instead of at most 4", row.len()));
+    }
+
+    Ok(ImportedBug {
+        title: title.to_string(),
+        status,
+        category,
+        link,
+    })
+}
+
+/// Downloads a file with the shared HTTP client, giving up once it gets
+/// bigger than `max_size` bytes. Returns `None` if the file was too big.
+async fn download_bounded(
+    ctx: &Context,
+    url: &str,
+    max_size: u64,
+) -> Result<Option<Vec<u8>>, reqwest::Error> {
+    let rclient = get_reqwest_client!(ctx);
+    let mut res = rclient.get(url).send().await?.error_for_status()?;
+
+    let mut data = Vec::new();
+    while let Some(chunk) = res.chunk().await? {
+        if (data.len() + chunk.len()) as u64 > max_size {
+            return Ok(None);
+        }
+        data.extend_from_slice(&chunk);
+    }
+    Ok(Some(data))
+}
+
+#[command]
+#[description = "Create bugs from a CSV file"]
+#[checks(not_in_maintenance)]
+#[owners_only]
+#[aliases(import)]
+pub async fn bug_import(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let attachment = if let Some(attachment) = msg.attachments.get(0) {
+        attachment
+    } else {
+        failure!(
+            ctx,
+            msg,
+            "You must attach a CSV file with the columns  `title, status, category[, link]`."
+        );
+        return Ok(());
+    };
+
+    let too_big = || {
+        format!(
+            "Attachment is too big! Filesize must be under {}.",
+            bytesize::ByteSize(MAX_CSV_FILE_SIZE)
+        )
+    };
+    if attachment.size > MAX_CSV_FILE_SIZE {
+        failure!(ctx, msg, too_big());
+        return Ok(());
+    }
+    let data = match download_bounded(ctx, &attachment.url, MAX_CSV_FILE_SIZE).await {
+        Ok(Some(data)) => data,
+        Ok(None) => {
+            failure!(ctx, msg, too_big());
+            return Ok(());
+        }
+        Err(e) => {
+            failure!(ctx, msg, "Could not download attachment!");
+            return Err(e.into());
+        }
+    };
+    let text = if let Ok(text) = String::from_utf8(data) {
+        text
+    } else {
+        failure!(ctx, msg, "The CSV file must be encoded in UTF-8.");
+        return Ok(());
+    };
+
+    let mut rows = parse_csv(&text);
+    // skip the header row, if any
+    if rows
+        .first()
+        .and_then(|(_, row)| row.first())
+        .map_or(false, |field| field.eq_ignore_ascii_case("title"))
+    {
+        rows.remove(0);
+    }
+
+    let mut bugs = Vec::with_capacity(rows.len());
+    let mut errors = Vec::new();
+    for (line, row) in &rows {
+        match parse_import_row(row) {
+            Ok(bug) => bugs.push(bug),
+            Err(e) => errors.push(format!("Line {}: {}", line, e)),
+        }
+    }
+
+    let mut report = String::new();
+    for (i, error) in errors.iter().enumerate() {
+        if report.len() + error.len() > 1500 {
+            report.push_str(&format!("...and {} more\n", errors.len() - i));
+            break;
+        }
+        report.push_str(error);
+        report.push('\n');
+    }
+
+    if bugs.is_empty() {
+        failure!(ctx, msg, "No valid bugs to import!\n{}", report);
+        return Ok(());
     }
+
+    // the imported bugs all point to this message
+    let source = msg
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!(
+                "Bugs imported from `{}` by {}",
+                sanitize_echo(&attachment.filename),
+                msg.author.mention()
+            ))
+            .allowed_mentions(|a| a.empty_parse())
+        })
+        .await?;
+
+    let bug_ids = match import_bugs(ctx, guild_id, &source, &bugs).await {
+        Ok(bug_ids) => bug_ids,
+        Err(e) => {
+            source.delete(ctx).await?;
+            failure!(ctx, msg, "Could not import the bugs, nothing was imported.");
+            return Err(e.into());
+        }
+    };
+
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+    termite_success!(
+        ctx,
+        msg,
+        "Imported {} bugs ({} to {}).{}{}",
+        bug_ids.len(),
+        prefix.id(bug_ids[0]),
+        prefix.id(bug_ids[bug_ids.len() - 1]),
+        if errors.is_empty() {
+            ""
+        } else {
+            "\nThese rows were skipped:\n"
+        },
+        report
+    );
+
     Ok(())
 }
 
 #[command]
-#[checks(is_admin, is_lotr_discord)]
-#[aliases("remove")]
-pub async fn bug_link_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
-        {
-            let link_id = args.single::<String>();
-            if let Ok(link_id) = link_id {
-                if let Ok(link_id) = link_id.trim_start_matches('#').parse::<u64>() {
-                    if remove_link(ctx, bug_id, link_id).await.is_ok() {
-                        termite_success!(
-                            ctx,
-                            msg,
-                            "Successfully removed link #{} from EoA-{}",
-                            link_id,
-                            bug_id
-                        );
-                    } else {
-                        failure!(
-                            ctx,
-                            msg,
-                            "Link #{} does not exist in EoA-{}",
-                            link_id,
-                            bug_id
-                        );
-                    }
-                } else {
-                    failure!(ctx, msg, "`{}` is not a valid link id!", link_id);
-                }
-            } else {
-                failure!(ctx, msg, "The second argument must be a valid link id.");
-            }
+#[description = "Copy a bug to the other edition"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[aliases(clone)]
+pub async fn bug_clone(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let bug_id = if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            bug_id
         } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id);
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+            return Ok(());
         }
     } else {
-        failure!(ctx, msg, "The first argument must be a bug id.");
-    }
-    Ok(())
-}
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+        return Ok(());
+    };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
 
-#[command]
-#[checks(is_lotr_discord, is_admin)]
-#[aliases("toggle")]
-pub async fn bug_toggle_edition(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
+    let bug = match get_bug_from_id(ctx, bug_id, guild_id).await {
+        Ok(bug) => bug,
+        Err(DbError::NotFound) => {
+            failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+            return Ok(());
+        }
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    let linked_message = match get_linked_message(ctx, &bug).await {
+        Ok(message) => message,
+        Err(e) => {
+            failure!(
+                ctx,
+                msg,
+                "The original message of {} does not exist anymore!",
+                prefix.id(bug_id)
+            );
+            return Err(e.into());
+        }
+    };
+
+    let category = bug.category.other_edition();
+    let new_bug_id = match add_bug_report(
+        ctx,
+        guild_id,
+        &linked_message,
+        bug.title.clone(),
+        bug.summary.as_deref(),
+        bug.status,
+        category,
+    )
+    .await
+    {
+        Ok(bug_id) => bug_id,
+        Err(e) => {
+            failure!(ctx, msg, "Could not clone the bug report!");
+            return Err(e);
+        }
+    };
+
+    for link in &bug.links {
+        if add_link(ctx, new_bug_id, guild_id, &link.url, &link.title)
+            .await
+            .is_err()
         {
-            if let Ok(category) = args.single::<BugCategory>() {
-                if let Some(old_category) = change_category(ctx, bug_id, category).await {
-                     if category != old_category {
-                        termite_success!(
-                            ctx,
-                            msg,
-                            "EoA-{} has been changed from {} to {}",
-                            bug_id,
-                            old_category,
-                            category
-                        );
-                        notify_users(
-                            ctx,
-                            bug_id,
-                            format!(
-                                "A bug you are subscribed to has been changed from {} to {}",
-                                old_category, category
-                            ),
-                        )
-                        .await?;
-                    }
-                } else {
-                    failure!(ctx, msg, "The bug EoA-{} does not exist!", bug_id);
-                }
-            } else {
-                failure!(
-                    ctx,
-                    msg,
-                    "The first argument must be one of \
-`fa_renewed`, `fa_legacy`, `sa_renewed` or `sa_legacy`"
-                );
-            }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id);
+            println!(
+                "=== ERROR ===
+Could not copy link #{} of {} to {}
+=== END ===",
+                link.num,
+                prefix.id(bug_id),
+                prefix.id(new_bug_id)
+            );
         }
-    } else {
-        failure!(ctx, msg, "The first argument must be a bug id.");
     }
-    Ok(())
-}
 
-#[command]
-#[checks(is_lotr_discord, is_admin)]
-#[aliases(rename)]
-pub async fn bug_rename(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    if let Ok(bug_id) = args.single::<String>() {
-        if let Ok(bug_id) = bug_id
-            .to_uppercase()
-            .trim_start_matches("EoA-")
-            .parse::<u64>()
+    // both bugs share the original message, the link titles name the other bug
+    for (link_bug_id, other_bug_id, relation) in [
+        (new_bug_id, bug_id, "Cloned from"),
+        (bug_id, new_bug_id, "Cloned to"),
+    ] {
+        let link_title = format!("{} {}: {}", relation, prefix.id(other_bug_id), bug.title);
+        if let Err(e) = add_link(ctx, link_bug_id, guild_id, &bug.message_url(), &link_title).await
         {
-            let new_title = args.rest();
-            if new_title.is_empty() {
-                failure!(ctx, msg, "You must specify a new title for EoA-{}", bug_id);
-            } else if change_title(ctx, bug_id, new_title).await.is_ok() {
-                termite_success!(ctx, msg, "Successfully changed the title of EoA-{}", bug_id);
-                notify_users(
-                    ctx,
-                    bug_id,
-                    "The title of a bug you are subscribed to has been changed",
-                )
-                .await?
-            } else {
-                failure!(ctx, msg, "EoA-{} does not exist!", bug_id);
-            }
-        } else {
-            failure!(ctx, msg, "`{}` is not a valid bug id!", bug_id);
+            println!(
+                "=== ERROR ===
+Could not link {} to {}
+Error: {}
+=== END ===",
+                prefix.id(link_bug_id),
+                prefix.id(other_bug_id),
+                e
+            );
         }
-    } else {
-        failure!(ctx, msg, "The first argument must be a bug id.");
     }
+
+    termite_success!(
+        ctx,
+        msg,
+        "Cloned {} to {} [{}]",
+        prefix.id(bug_id),
+        prefix.id(new_bug_id),
+        category
+    );
+
     Ok(())
 }
 
 #[command]
+#[description = "Display the bugtracker statistics"]
 #[aliases(statistics)]
+#[sub_commands(stats_labels)]
 pub async fn stats(ctx: &Context, msg: &Message) -> CommandResult {
-    if let Some(counts) = get_bug_statistics(ctx).await {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    if let Some(counts) = get_bug_statistics(ctx, guild_id).await {
+        let branding = get_branding(ctx, msg.guild_id).await;
+        let open = counts.total - counts.resolved - counts.closed - counts.forgevanilla;
+        // the buttons open the first page of `!bugs` with their filter, and are
+        // then handled as its navigation buttons
+        let drill_down = |status: Option<BugStatus>| {
+            BugListQuery {
+                user_id: msg.author.id,
+                status,
+                category: None,
+                reporter: None,
+                label: None,
+                attachment: None,
+                snoozed: false,
+                order: BugOrder::Chronological(false),
+                limit: 10,
+                page: 1,
+            }
+            .to_custom_id()
+        };
+        let rows = [
+            vec![
+                ("Open", None, open),
+                ("Critical", Some(BugStatus::Critical), counts.critical),
+                ("High", Some(BugStatus::High), counts.high),
+                ("Medium", Some(BugStatus::Medium), counts.medium),
+                ("Low", Some(BugStatus::Low), counts.low),
+            ],
+            vec![
+                ("Resolved", Some(BugStatus::Resolved), counts.resolved),
+                ("Closed", Some(BugStatus::Closed), counts.closed),
+                (
+                    "Forge or Vanilla",
+                    Some(BugStatus::ForgeVanilla),
+                    counts.forgevanilla,
+                ),
+            ],
+        ];
         msg.channel_id
             .send_message(ctx, |m| {
                 m.embed(|e| {
-                    e.author(|a| {
-                        a.name("Eras of Arda Bugtracker");
-                        a.icon_url(crate::constants::TERMITE_IMAGE);
-                        a
-                    });
+                    e.author(|a| branding.author(a));
                     e.colour(serenity::utils::Colour::TEAL);
                     e.field(
                         "Bugtracker statistics",
@@ -1071,7 +4967,7 @@ _{} first age bugs, {} second age bugs_
                             counts.resolved,
                             counts.closed,
                             counts.forgevanilla,
-                            counts.total - counts.resolved - counts.closed - counts.forgevanilla,
+                            open,
                             counts.low,
                             counts.medium,
                             counts.high,
@@ -1084,6 +4980,22 @@ _{} first age bugs, {} second age bugs_
                     );
                     e
                 })
+                .components(|c| {
+                    for row in &rows {
+                        c.create_action_row(|a| {
+                            for (label, status, count) in row {
+                                a.create_button(|b| {
+                                    b.style(ButtonStyle::Secondary)
+                                        .label(format!("{} ({})", label, count))
+                                        .custom_id(drill_down(*status))
+                                        .disabled(*count == 0)
+                                });
+                            }
+                            a
+                        });
+                    }
+                    c
+                })
             })
             .await?;
     } else {
@@ -1092,7 +5004,62 @@ _{} first age bugs, {} second age bugs_
     Ok(())
 }
 
+/// Open bugs by label, with the number reported in the last week. Labels don't
+/// record when they were added, so the trend counts the bugs by report date.
+#[command]
+#[description = "Display the open bugs by label"]
+#[aliases(labels, label)]
+pub async fn stats_labels(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    let since = (Utc::now() - chrono::Duration::days(7)).naive_utc();
+    let labels = match get_open_label_counts(ctx, guild_id, since).await {
+        Ok(labels) => labels,
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    // at most 25 fields, one of them replaced by the hidden count
+    let shown = if labels.len() > 25 { 24 } else { labels.len() };
+    let branding = get_branding(ctx, msg.guild_id).await;
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| branding.author(a));
+                e.colour(serenity::utils::Colour::TEAL);
+                e.title("Open bugs by label");
+                if labels.is_empty() {
+                    e.description("_No open bug has a label!_");
+                }
+                for (label, count, new) in &labels[..shown] {
+                    e.field(
+                        format!("`{}`", label),
+                        if *new > 0 {
+                            format!("{}, +{} this week", count, new)
+                        } else {
+                            count.to_string()
+                        },
+                        true,
+                    );
+                }
+                if shown < labels.len() {
+                    e.field(
+                        "Other labels",
+                        format!("_...and {} more_", labels.len() - shown),
+                        true,
+                    );
+                }
+                e
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
 #[command]
+#[description = "Send the bugtracker commands in DMs"]
 #[checks(is_admin, is_lotr_discord)]
 #[aliases("help")]
 pub async fn bugtracker_help(ctx: &Context, msg: &Message) -> CommandResult {
@@ -1100,13 +5067,15 @@ pub async fn bugtracker_help(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "List the bugs you are subscribed to"]
 pub async fn notifications(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
     let closed = args
         .single::<String>()
         .map(|s| s.eq_ignore_ascii_case("closed") || s.eq_ignore_ascii_case("all"))
         .unwrap_or_default();
 
-    let list = get_notifications_for_user(ctx, msg.author.id, closed).await?;
+    let list = get_notifications_for_user(ctx, guild_id, msg.author.id, closed).await?;
 
     if list.is_empty() {
         msg.reply(
@@ -1122,47 +5091,320 @@ including those from closed or resolved bugs."
         )
         .await?;
     } else {
+        let branding = get_branding(ctx, msg.guild_id).await;
+        let prefix = get_bug_prefix(ctx, msg.guild_id).await;
         msg.channel_id
             .send_message(ctx, |m| {
                 m.embed(|e| {
-                    e.author(|a| {
-                        a.name("Eras of Arda Bugtracker")
-                            .icon_url(crate::constants::TERMITE_IMAGE)
-                    })
-                    .colour(serenity::utils::Colour::TEAL)
-                    .title("Bug notifications")
-                    .description(format!(
-                        "_List of bugs you are subscribed to_\n\n{}",
-                        list.iter()
-                            .map(|id| format!("EoA-{id}"))
-                            .collect::<Vec<_>>()
-                            .join(", "),
-                    ))
-                    .footer(|f| {
-                        f.text(if closed {
-                            "Including closed and resolved bugs"
-                        } else {
-                            "To see closed and resolved bugs, use  !bug notifications all"
-                        })
-                    });
+                    e.author(|a| branding.author(a))
+                        .colour(serenity::utils::Colour::TEAL)
+                        .title("Bug notifications")
+                        .description(format!(
+                            "_List of bugs you are subscribed to_\n\n{}",
+                            list.iter()
+                                .map(|id| prefix.id(*id))
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        ))
+                        .footer(|f| {
+                            f.text(if closed {
+                                "Including closed and resolved bugs"
+                            } else {
+                                "To see closed and resolved bugs, use  !bug notifications all"
+                            })
+                        });
+
+                    e
+                })
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+/// Without arguments, tells whether the notifications are paused. With a bug
+/// id, sends the current status of the bug to its subscribers again, e.g. for
+/// changes made while the bot could not send DMs.
+#[command]
+#[description = "Send the status of a bug to its subscribers again"]
+#[checks(is_admin)]
+#[aliases(notify)]
+#[sub_commands(notify_pause, notify_resume)]
+pub async fn bug_notify(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    if let Ok(bug_id) = args.single::<String>() {
+        let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+        let bug_id = match prefix.parse(&bug_id) {
+            Some(bug_id) => bug_id,
+            None => {
+                failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+                return Ok(());
+            }
+        };
+        let bug = match get_bug_from_id(ctx, bug_id, guild_id).await {
+            Ok(bug) => bug,
+            Err(DbError::NotFound) => {
+                failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+                return Ok(());
+            }
+            Err(e) => {
+                db_failure!(ctx, msg, e);
+                return Err(e.into());
+            }
+        };
+
+        let (sent, errors) = dispatch_notification(
+            ctx,
+            bug_id,
+            guild_id,
+            format!("A bug you are subscribed to is currently `{}`", bug.status),
+        )
+        .await?;
+        for e in &errors {
+            println!(
+                "Could not resend the notification of {}: {}",
+                prefix.id(bug_id),
+                e
+            );
+        }
+        success!(
+            ctx,
+            msg,
+            "Notification of {} sent to {} subscriber{}{}.",
+            prefix.id(bug_id),
+            sent,
+            if sent == 1 { "" } else { "s" },
+            match errors.len() {
+                0 => String::new(),
+                failed => format!(
+                    ", {} DM{} could not be sent",
+                    failed,
+                    if failed == 1 { "" } else { "s" }
+                ),
+            }
+        );
+        return Ok(());
+    }
+
+    let until = ctx
+        .data
+        .read()
+        .await
+        .get::<NotificationsPausedUntil>()
+        .copied();
+    match until {
+        Some(until) if until > chrono::Utc::now() => {
+            msg.reply(
+                ctx,
+                format!(
+                    "Bugtracker notifications are paused until {}.",
+                    discord_timestamp(&until, 't')
+                ),
+            )
+            .await?;
+        }
+        _ => {
+            msg.reply(ctx, "Bugtracker notifications are active.")
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Pause the bug notifications"]
+#[checks(is_admin, not_in_maintenance)]
+#[aliases(pause)]
+pub async fn notify_pause(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let minutes = match args.single::<u32>() {
+        Ok(minutes) if (1..=1440).contains(&minutes) => minutes,
+        _ => {
+            failure!(
+                ctx,
+                msg,
+                "You must specify a number of minutes between 1 and 1440:  `!bug notify pause <minutes>`"
+            );
+            return Ok(());
+        }
+    };
+
+    let until = chrono::Utc::now() + chrono::Duration::minutes(minutes.into());
+    ctx.data
+        .write()
+        .await
+        .insert::<NotificationsPausedUntil>(until);
+
+    success!(
+        ctx,
+        msg,
+        "Bugtracker notifications are paused until {}. Changes in the meantime will not \
+be sent to subscribers and watchers.",
+        discord_timestamp(&until, 't')
+    );
+    Ok(())
+}
+
+#[command]
+#[description = "Resume the bug notifications"]
+#[checks(is_admin, not_in_maintenance)]
+#[aliases(resume)]
+pub async fn notify_resume(ctx: &Context, msg: &Message) -> CommandResult {
+    ctx.data.write().await.remove::<NotificationsPausedUntil>();
+    success!(ctx, msg, "Bugtracker notifications resumed.");
+    Ok(())
+}
+
+/// `!bug subscribe #channel [label] <label>`, and the matching unsubscribe:
+/// feeds a channel the bugs given a label. Without a label, lists the labels
+/// the channel is subscribed to.
+async fn channel_label_subscription(
+    ctx: &Context,
+    msg: &Message,
+    channel_id: ChannelId,
+    mut args: Args,
+    subscribe: bool,
+) -> CommandResult {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+    if !is_admin_user(ctx, guild_id, msg.author.id).await {
+        failure!(
+            ctx,
+            msg,
+            "Only admins can change the subscriptions of a channel."
+        );
+        return Ok(());
+    }
+    match channel_id.to_channel(ctx).await {
+        Ok(Channel::Guild(channel)) if channel.guild_id == guild_id => (),
+        _ => {
+            failure!(
+                ctx,
+                msg,
+                "<#{}> is not a channel of this guild.",
+                channel_id
+            );
+            return Ok(());
+        }
+    }
+
+    args.advance();
+    if args
+        .current()
+        .map_or(false, |arg| arg.eq_ignore_ascii_case("label"))
+    {
+        args.advance();
+    }
+    let label = match args.current() {
+        Some(label) => match normalize_label(label) {
+            Some(label) => label,
+            None => {
+                failure!(
+                    ctx,
+                    msg,
+                    "The label must have at most {} letters, digits, `-` or `_`.",
+                    MAX_LABEL_LENGTH
+                );
+                return Ok(());
+            }
+        },
+        None => {
+            let labels = match get_label_subscriptions(ctx, channel_id).await {
+                Ok(labels) => labels,
+                Err(e) => {
+                    db_failure!(ctx, msg, e);
+                    return Err(e.into());
+                }
+            };
+            if labels.is_empty() {
+                success!(
+                    ctx,
+                    msg,
+                    "<#{}> is not subscribed to any label. Subscribe it with  `!bug subscribe <#{}> label <label>`.",
+                    channel_id,
+                    channel_id
+                );
+            } else {
+                success!(
+                    ctx,
+                    msg,
+                    "<#{}> gets the bugs labelled {}",
+                    channel_id,
+                    labels
+                        .iter()
+                        .map(|label| format!("`{}`", label))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+            return Ok(());
+        }
+    };
 
-                    e
-                })
-            })
-            .await?;
+    if subscribe {
+        match add_label_subscription(ctx, guild_id, channel_id, &label).await {
+            Ok(true) => success!(
+                ctx,
+                msg,
+                "The bugs given the label `{}` will be posted in <#{}>",
+                label,
+                channel_id
+            ),
+            Ok(false) => failure!(
+                ctx,
+                msg,
+                "<#{}> is already subscribed to the label `{}`",
+                channel_id,
+                label
+            ),
+            Err(e) => {
+                db_failure!(ctx, msg, e);
+                return Err(e.into());
+            }
+        }
+    } else {
+        match remove_label_subscription(ctx, channel_id, &label).await {
+            Ok(()) => success!(
+                ctx,
+                msg,
+                "The bugs given the label `{}` won't be posted in <#{}> anymore",
+                label,
+                channel_id
+            ),
+            Err(DbError::NotFound) => failure!(
+                ctx,
+                msg,
+                "<#{}> is not subscribed to the label `{}`",
+                channel_id,
+                label
+            ),
+            Err(e) => {
+                db_failure!(ctx, msg, e);
+                return Err(e.into());
+            }
+        }
     }
+
     Ok(())
 }
 
 #[command]
+#[description = "Stop the notifications of a bug"]
+#[checks(not_in_maintenance)]
 pub async fn unsubscribe(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let bug_id = match args.single::<u64>() {
-        Ok(bug_id) => bug_id,
-        _ => {
+    if let Some(channel_id) = args.current().and_then(parse_channel) {
+        return channel_label_subscription(ctx, msg, ChannelId(channel_id), args, false).await;
+    }
+    let bug_id = match args.single::<String>() {
+        Ok(bug_id) => parse_bug_id(ctx, msg.guild_id, &bug_id).await,
+        Err(_) => None,
+    };
+    let bug_id = match bug_id {
+        Some(bug_id) => bug_id,
+        None => {
             failure!(ctx, msg, "The first argument must be a bug id!");
             return Ok(());
         }
     };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
 
     if is_notified_user(ctx, bug_id, msg.author.id).await != Some(true) {
         failure!(ctx, msg, "You are not subscribed to this bug!");
@@ -1172,30 +5414,42 @@ pub async fn unsubscribe(ctx: &Context, msg: &Message, mut args: Args) -> Comman
     if let Err(e) =
         crate::database::bug_reports::remove_notified_user(ctx, bug_id, msg.author.id).await
     {
-        failure!(ctx, msg, "Could not unsubscribe from EoA-{}", bug_id);
+        failure!(ctx, msg, "Could not unsubscribe from {}", prefix.id(bug_id));
         return Err(e);
     }
 
     crate::success!(
         ctx,
         msg,
-        "Successfully unsubscribed from EoA-{}.
+        "Successfully unsubscribed from {}.
 You will no longer be notified if this bug is edited, closed or resolved.",
-        bug_id
+        prefix.id(bug_id)
     );
 
     Ok(())
 }
 
 #[command]
+#[description = "Get notified when a bug changes"]
+#[checks(not_in_maintenance)]
+#[only_in(guilds)]
 pub async fn subscribe(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let bug_id = match args.single::<u64>() {
-        Ok(bug_id) => bug_id,
-        _ => {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+    if let Some(channel_id) = args.current().and_then(parse_channel) {
+        return channel_label_subscription(ctx, msg, ChannelId(channel_id), args, true).await;
+    }
+    let bug_id = match args.single::<String>() {
+        Ok(bug_id) => parse_bug_id(ctx, msg.guild_id, &bug_id).await,
+        Err(_) => None,
+    };
+    let bug_id = match bug_id {
+        Some(bug_id) => bug_id,
+        None => {
             failure!(ctx, msg, "The first argument must be a bug id!");
             return Ok(());
         }
     };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
 
     if is_notified_user(ctx, bug_id, msg.author.id).await != Some(false) {
         failure!(ctx, msg, "You are already subscribed to this bug!");
@@ -1203,19 +5457,732 @@ pub async fn subscribe(ctx: &Context, msg: &Message, mut args: Args) -> CommandR
     }
 
     if let Err(e) =
-        crate::database::bug_reports::add_notified_user(ctx, bug_id, msg.author.id).await
+        crate::database::bug_reports::add_notified_user(ctx, bug_id, guild_id, msg.author.id).await
     {
-        failure!(ctx, msg, "Could not subscribe to EoA-{}", bug_id);
+        failure!(ctx, msg, "Could not subscribe to {}", prefix.id(bug_id));
         return Err(e);
     }
 
     crate::success!(
         ctx,
         msg,
-        "Successfully subscribed to EoA-{}.
+        "Successfully subscribed to {}.
 You will be notified if this bug is edited, closed or resolved.",
-        bug_id
+        prefix.id(bug_id)
+    );
+
+    Ok(())
+}
+
+#[command]
+#[description = "Get notified of every edit of a bug"]
+#[checks(not_in_maintenance)]
+#[only_in(guilds)]
+pub async fn watch(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+    let bug_id = if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            bug_id
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+            return Ok(());
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+        return Ok(());
+    };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+
+    if get_bug_from_id(ctx, bug_id, guild_id).await.is_err() {
+        failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+        return Ok(());
+    }
+
+    if is_watcher(ctx, bug_id, msg.author.id).await != Some(false) {
+        failure!(ctx, msg, "You are already watching {}!", prefix.id(bug_id));
+        return Ok(());
+    }
+
+    if let Err(e) = add_watcher(ctx, bug_id, guild_id, msg.author.id).await {
+        if let DbError::Conflict = e {
+            failure!(ctx, msg, "You are already watching {}!", prefix.id(bug_id));
+            return Ok(());
+        }
+        failure!(ctx, msg, "Could not watch {}", prefix.id(bug_id));
+        return Err(e.into());
+    }
+
+    success!(
+        ctx,
+        msg,
+        "You are now watching {}.
+You will get a DM for any change to its title, status, edition or links.",
+        prefix.id(bug_id)
+    );
+
+    Ok(())
+}
+
+#[command]
+#[description = "Stop watching a bug"]
+#[checks(not_in_maintenance)]
+#[only_in(guilds)]
+pub async fn unwatch(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let bug_id = if let Ok(bug_id) = args.single::<String>() {
+        if let Some(bug_id) = parse_bug_id(ctx, msg.guild_id, &bug_id).await {
+            bug_id
+        } else {
+            failure!(ctx, msg, t!(ctx, msg, "bug.invalid_id", bug_id));
+            return Ok(());
+        }
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "bug.missing_id"));
+        return Ok(());
+    };
+    let prefix = get_bug_prefix(ctx, msg.guild_id).await;
+
+    if is_watcher(ctx, bug_id, msg.author.id).await != Some(true) {
+        failure!(ctx, msg, "You are not watching {}!", prefix.id(bug_id));
+        return Ok(());
+    }
+
+    if let Err(e) = remove_watcher(ctx, bug_id, msg.author.id).await {
+        failure!(ctx, msg, "Could not stop watching {}", prefix.id(bug_id));
+        return Err(e);
+    }
+
+    success!(
+        ctx,
+        msg,
+        "You are no longer watching {}.",
+        prefix.id(bug_id)
     );
 
     Ok(())
 }
+
+#[command]
+#[description = "Delete all the bugs of this server"]
+#[checks(not_in_maintenance)]
+#[owners_only]
+#[only_in(guilds)]
+#[aliases(reset)]
+pub async fn bug_reset(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    if guild_id == EOA_DISCORD && args.current() != Some("eoa") {
+        failure!(
+            ctx,
+            msg,
+            "This would delete the whole Eras of Arda bugtracker! Use  `!bug reset eoa`  if you \
+really mean it."
+        );
+        return Ok(());
+    }
+
+    let count = count_guild_bugs(ctx, guild_id).await.unwrap_or_default();
+    if count == 0 {
+        failure!(ctx, msg, "There are no bugs to delete on this server.");
+        return Ok(());
+    }
+
+    let guild_name = guild_id.to_partial_guild(ctx).await?.name;
+    msg.reply(
+        ctx,
+        format!(
+            "This will permanently delete **{}** bugs from this server, along with their links \
+and notifications.\nType the name of the server (`{}`) within 30 seconds to confirm.",
+            count, guild_name
+        ),
+    )
+    .await?;
+
+    let _collector = crate::metrics::collector_started();
+    let confirmed = CollectReply::new(ctx)
+        .author_id(msg.author.id)
+        .channel_id(msg.channel_id)
+        .timeout(Duration::from_secs(30))
+        .await
+        .map(|reply| reply.content.trim() == guild_name)
+        .unwrap_or_default();
+    if !confirmed {
+        failure!(ctx, msg, "Bug reset cancelled.");
+        return Ok(());
+    }
+
+    let deleted = reset_guild_bugs(ctx, guild_id).await?;
+    println!("Deleted {} bugs from {}", deleted, guild_id);
+    success!(ctx, msg, "Deleted {} bugs from this server.", deleted);
+
+    Ok(())
+}
+
+async fn send_digest(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> CommandResult {
+    let now = chrono::Utc::now();
+    let since = now - chrono::Duration::days(7);
+    let digest = get_bug_digest(ctx, guild_id, since.naive_utc())
+        .await
+        .ok_or("Could not get the bug digest from the database")?;
+    let markers = StatusMarkers::of(ctx, Some(guild_id)).await;
+    let critical_colour = BugStatus::Critical.guild_colour(ctx, Some(guild_id)).await;
+    let resolved_colour = BugStatus::Resolved.guild_colour(ctx, Some(guild_id)).await;
+    let branding = get_branding(ctx, Some(guild_id)).await;
+
+    channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.author(|a| branding.author(a));
+                e.title("Bugtracker weekly digest");
+                e.description(format!(
+                    "From {} to {}",
+                    discord_timestamp(&since, 'd'),
+                    discord_timestamp(&now, 'd')
+                ));
+                e.colour(if digest.open_critical > 0 {
+                    critical_colour
+                } else {
+                    resolved_colour
+                });
+                e.field("Opened", digest.opened, true);
+                e.field("Resolved", digest.resolved, true);
+                e.field("Closed", digest.closed, true);
+                e.field(
+                    format!("{} Open critical bugs", markers.get(BugStatus::Critical)),
+                    digest.open_critical,
+                    false,
+                );
+                e.timestamp(now);
+                e
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[description = "Summarize the bugtracker activity of the last 7 days"]
+#[checks(is_lotr_discord, is_admin)]
+#[sub_commands(digest_channel)]
+pub async fn digest(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap_or(EOA_DISCORD);
+    if let Err(e) = send_digest(ctx, guild_id, msg.channel_id).await {
+        failure!(ctx, msg, "Could not create the bug digest!");
+        return Err(e);
+    }
+    Ok(())
+}
+
+#[command]
+#[description = "Set or remove the channel of the weekly digest"]
+#[checks(is_lotr_discord, is_admin, not_in_maintenance)]
+#[only_in(guilds)]
+#[aliases(channel)]
+pub async fn digest_channel(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    if args.current() == Some("off") {
+        remove_setting(ctx, guild_id, DIGEST_CHANNEL).await?;
+        success!(ctx, msg, "The weekly digest will no longer be posted.");
+    } else if let Ok(channel_id) = args.single::<ChannelId>() {
+        set_setting(ctx, guild_id, DIGEST_CHANNEL, &channel_id.0.to_string()).await?;
+        success!(
+            ctx,
+            msg,
+            "The weekly digest will be posted in {}",
+            channel_id.mention()
+        );
+    } else {
+        match get_setting(ctx, guild_id, DIGEST_CHANNEL).await {
+            Some(channel_id) => {
+                msg.reply(
+                    ctx,
+                    format!("The weekly digest is posted in <#{}>", channel_id),
+                )
+                .await?;
+            }
+            None => {
+                failure!(
+                    ctx,
+                    msg,
+                    "No digest channel set. Use  `!digest channel <channel mention>`  to set one."
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+static DIGEST_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Starts posting the bug digest every week in the digest channel of the
+/// EoA Discord, if there is one. Weeks are counted from the bot's start, and
+/// calling this more than once (e.g. on reconnects) has no effect.
+pub fn start_weekly_digest(ctx: Context) {
+    if DIGEST_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let shutdown = get_shutdown(&ctx).await;
+        let mut interval = tokio::time::interval(DIGEST_INTERVAL);
+        // the first tick completes immediately
+        interval.tick().await;
+        while let Some(_work) = shutdown.tick(&mut interval).await {
+            let channel_id = get_setting(&ctx, EOA_DISCORD, DIGEST_CHANNEL)
+                .await
+                .and_then(|id| id.parse().ok())
+                .map(ChannelId);
+            if let Some(channel_id) = channel_id {
+                if let Err(e) = send_digest(&ctx, EOA_DISCORD, channel_id).await {
+                    println!(
+                        "=== ERROR ===\nCould not post the weekly digest: {}\n=== END ===",
+                        e
+                    );
+                }
+            }
+        }
+    });
+}
+
+static SNOOZE_WATCHER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Wakes up the [snoozed][bug_snooze] bugs once their date has passed, and
+/// notifies their subscribers.
+pub fn start_snooze_watcher(ctx: Context) {
+    if SNOOZE_WATCHER_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let shutdown = get_shutdown(&ctx).await;
+        let mut interval = tokio::time::interval(SNOOZE_CHECK_INTERVAL);
+        while let Some(_work) = shutdown.tick(&mut interval).await {
+            let bugs = match take_expired_snoozes(&ctx).await {
+                Ok(bugs) => bugs,
+                Err(e) => {
+                    println!(
+                        "=== ERROR ===\nCould not wake up the snoozed bugs: {}\n=== END ===",
+                        e
+                    );
+                    continue;
+                }
+            };
+            for (bug_id, guild_id) in bugs {
+                let prefix = get_bug_prefix(&ctx, Some(guild_id)).await;
+                let notification = format!("{} is no longer snoozed.", prefix.id(bug_id));
+                if let Err(e) = notify_users(&ctx, bug_id, guild_id, notification).await {
+                    println!(
+                        "Could not notify the subscribers of {}: {}",
+                        prefix.id(bug_id),
+                        e
+                    );
+                }
+            }
+        }
+    });
+}
+
+static AUTO_CLOSE_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Closes the bugs of the EoA Discord that have been resolved for longer than
+/// its `auto_close_days` setting, if it is set.
+pub fn start_auto_close(ctx: Context) {
+    if AUTO_CLOSE_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let shutdown = get_shutdown(&ctx).await;
+        let mut interval = tokio::time::interval(AUTO_CLOSE_INTERVAL);
+        while let Some(_work) = shutdown.tick(&mut interval).await {
+            let guilds = match get_guilds_with_setting(&ctx, AUTO_CLOSE_DAYS).await {
+                Some(guilds) => guilds,
+                None => continue,
+            };
+            for (guild_id, days) in guilds {
+                let days: u32 = match days.parse() {
+                    Ok(days) => days,
+                    Err(_) => continue,
+                };
+                auto_close_guild_bugs(&ctx, guild_id, days).await;
+            }
+        }
+    });
+}
+
+async fn auto_close_guild_bugs(ctx: &Context, guild_id: GuildId, days: u32) {
+    let before = Utc::now() - chrono::Duration::days(days.into());
+    let bug_ids = match get_resolved_bugs_before(ctx, guild_id, before.naive_utc()).await {
+        Ok(bug_ids) => bug_ids,
+        Err(e) => {
+            println!(
+                "=== ERROR ===\nCould not get the bugs to auto-close: {}\n=== END ===",
+                e
+            );
+            return;
+        }
+    };
+
+    let prefix = get_bug_prefix(ctx, Some(guild_id)).await;
+    for bug_id in bug_ids {
+        match close_resolved_bug(ctx, bug_id, guild_id, BOT_ID).await {
+            Ok(true) => (),
+            // reopened since the query, leave it alone
+            Ok(false) => continue,
+            Err(e) => {
+                println!(
+                    "=== ERROR ===\nCould not auto-close {}: {}\n=== END ===",
+                    prefix.id(bug_id),
+                    e
+                );
+                continue;
+            }
+        }
+        let notification = format!(
+            "A bug you are subscribed to has been changed from `{}` to `{}`: \
+auto-closed after {} days resolved",
+            BugStatus::Resolved,
+            BugStatus::Closed,
+            days
+        );
+        if let Err(e) = notify_users(ctx, bug_id, guild_id, notification).await {
+            println!(
+                "Could not notify the subscribers of {}: {}",
+                prefix.id(bug_id),
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::bug_reports::BugLink;
+
+    #[test]
+    fn test_bug_id_from_embed_title() {
+        assert_eq!(
+            bug_id_from_embed_title(":red_circle: EoA-12: Crash on load: again [fa_renewed]"),
+            Some("EoA-12")
+        );
+        assert_eq!(bug_id_from_embed_title("Bug labels (Total: 3)"), None);
+        assert_eq!(bug_id_from_embed_title(""), None);
+    }
+
+    #[test]
+    fn test_embed_summary() {
+        let mut embed: Embed = serde_json::from_str(r#"{"type": "rich"}"#).unwrap();
+        assert_eq!(embed_summary(&embed), None);
+        embed.title = Some("Crash report".into());
+        assert_eq!(embed_summary(&embed).as_deref(), Some("Crash report"));
+        embed.description = Some("é".repeat(2000));
+        let summary = embed_summary(&embed).unwrap();
+        assert_eq!(summary.chars().count(), 1024);
+        assert!(summary.starts_with("**Crash report**\n") && summary.ends_with("..."));
+    }
+
+    #[test]
+    fn test_parse_message_link() {
+        assert_eq!(
+            parse_message_link(
+                "https://discord.com/channels/405091134327619587/405096837436833807/938135367486410792"
+            ),
+            Some((
+                ChannelId(405096837436833807),
+                MessageId(938135367486410792)
+            ))
+        );
+        assert_eq!(
+            parse_message_link("https://canary.discordapp.com/channels/1/2/3"),
+            Some((ChannelId(2), MessageId(3)))
+        );
+        assert_eq!(parse_message_link("https://discord.com/channels/1/2"), None);
+        assert_eq!(
+            parse_message_link("https://github.com/channels/1/2/3"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_webhook_url() {
+        assert!(is_webhook_url(
+            "https://discord.com/api/webhooks/405091134327619587/abc-DEF_123"
+        ));
+        assert!(is_webhook_url(
+            "https://canary.discordapp.com/api/webhooks/1/token"
+        ));
+        assert!(!is_webhook_url("https://discord.com/api/webhooks/1/"));
+        assert!(!is_webhook_url("https://discord.com/api/webhooks/id/token"));
+        assert!(!is_webhook_url(
+            "https://discord.com/api/webhooks/1/token/extra"
+        ));
+        assert!(!is_webhook_url("https://example.com/api/webhooks/1/token"));
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = "\u{feff}title,status,category,link\r\n\
+Broken \"hobbit\" beds, low ,fa_legacy,\r\n\
+\r\n\
+\"Trader, not trading\",high,,https://example.com\n\
+\"Multi\nline \"\"quoted\"\"\"\n\
+,,\n\
+Last row";
+        assert_eq!(
+            parse_csv(csv),
+            vec![
+                (1, vec!["title", "status", "category", "link"]),
+                (2, vec!["Broken \"hobbit\" beds", "low", "fa_legacy", ""]),
+                (
+                    4,
+                    vec!["Trader, not trading", "high", "", "https://example.com"]
+                ),
+                (5, vec!["Multi\nline \"quoted\""]),
+                (8, vec!["Last row"]),
+            ]
+        );
+        assert!(parse_csv("").is_empty());
+
+        let row = |fields: &[&str]| fields.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            parse_import_row(&row(&["Crash", "crit", "sa_legacy", "https://example.com"])),
+            Ok(ImportedBug {
+                title: "Crash".into(),
+                status: BugStatus::Critical,
+                category: BugCategory::SaLegacy,
+                link: Some("https://example.com".into()),
+            })
+        );
+        assert_eq!(
+            parse_import_row(&row(&["Crash"])).map(|bug| (bug.status, bug.category)),
+            Ok((BugStatus::default(), BugCategory::default()))
+        );
+        assert!(parse_import_row(&row(&["", "low"])).is_err());
+        assert!(parse_import_row(&row(&["Crash", "meh"])).is_err());
+        assert!(parse_import_row(&row(&["Crash", "low", "legacy"])).is_err());
+        assert!(parse_import_row(&row(&["Crash", "low", "", "example.com"])).is_err());
+        assert!(parse_import_row(&row(&["Crash", "low", "", "", "extra"])).is_err());
+    }
+
+    #[test]
+    fn test_buglist_custom_id() {
+        let query = BugListQuery::from_custom_id(
+            "buglist__405421991777009678__3__25__oldest__high__sa_renewed",
+        )
+        .unwrap();
+        assert_eq!(
+            query,
+            BugListQuery {
+                user_id: UserId(405421991777009678),
+                status: Some(BugStatus::High),
+                category: Some(BugCategory::SaRenewed),
+                reporter: None,
+                label: None,
+                attachment: None,
+                snoozed: false,
+                order: BugOrder::Chronological(true),
+                limit: 25,
+                page: 3,
+            }
+        );
+        assert!(query.to_custom_id().len() <= 100);
+        assert_eq!(
+            BugListQuery::from_custom_id(&query.to_custom_id()),
+            Some(query)
+        );
+
+        let query = BugListQuery::from_custom_id("buglist__1__1__10__latest__all__all").unwrap();
+        assert_eq!(query.status, None);
+        assert_eq!(query.category, None);
+        assert!(!query.snoozed);
+
+        let snoozed = BugListQuery {
+            snoozed: true,
+            ..query.clone()
+        };
+        assert_eq!(
+            snoozed.to_custom_id(),
+            "buglist__1__1__10__latest__snoozed__all"
+        );
+        assert_eq!(
+            BugListQuery::from_custom_id(&snoozed.to_custom_id()),
+            Some(snoozed)
+        );
+
+        let query = BugListQuery {
+            reporter: Some(UserId(222868216920145920)),
+            ..query
+        };
+        assert!(query.to_custom_id().len() <= 100);
+        assert_eq!(
+            BugListQuery::from_custom_id(&query.to_custom_id()),
+            Some(query.clone())
+        );
+
+        let without_image = BugListQuery {
+            reporter: None,
+            attachment: Some(false),
+            ..query.clone()
+        };
+        assert_eq!(
+            without_image.to_custom_id(),
+            "buglist__1__1__10__latest__all__all__i:0"
+        );
+        assert_eq!(
+            BugListQuery::from_custom_id(&without_image.to_custom_id()),
+            Some(without_image)
+        );
+        assert_eq!(
+            BugListQuery::from_custom_id("buglist__1__1__10__latest__all__all__i:yes"),
+            None
+        );
+
+        // longest labels with the longest keywords still fit in a custom id
+        let query = BugListQuery {
+            user_id: UserId(4054219917770096780),
+            status: Some(BugStatus::ForgeVanilla),
+            category: Some(BugCategory::FaRenewed),
+            reporter: None,
+            label: Some("a".repeat(MAX_LABEL_LENGTH)),
+            attachment: None,
+            snoozed: false,
+            order: BugOrder::Priority(false),
+            limit: 100,
+            page: 999,
+        };
+        assert!(query.to_custom_id().len() <= 100);
+        assert!(query.to_jump_custom_id(true).len() <= 100);
+        assert_eq!(
+            BugListQuery::from_custom_id(&query.to_jump_custom_id(true)),
+            Some(query.clone())
+        );
+        assert_eq!(
+            BugListQuery::from_custom_id(&query.to_custom_id()),
+            Some(query)
+        );
+        assert_eq!(
+            BugListQuery::from_custom_id("buglist__1__1__10__latest__all__all__l:Not a label"),
+            None
+        );
+
+        assert_eq!(
+            BugListQuery::from_custom_id("buglist__1__0__10__latest__all__all"),
+            None
+        );
+        assert_eq!(
+            BugListQuery::from_custom_id("buglist__1__1__10__sideways__all__all"),
+            None
+        );
+        assert_eq!(
+            BugListQuery::from_custom_id("buglist__1__1__10__latest__all"),
+            None
+        );
+        assert_eq!(BugListQuery::from_custom_id("next_page"), None);
+    }
+
+    #[test]
+    fn test_bug_index() {
+        let prefix = BugPrefix("EoA".into());
+        let bug = |bug_id, title: &str| {
+            PartialBugReport::new(
+                bug_id,
+                title.into(),
+                "low".into(),
+                NaiveDate::from_ymd(2022, 3, 4).and_hms(12, 30, 0),
+                BugCategory::FaRenewed,
+            )
+            .unwrap()
+        };
+        let bugs = [
+            bug(7, "Crash with `Optifine`"),
+            bug(
+                12,
+                "Hobbits eat too much, which makes them very slow to travel",
+            ),
+        ];
+        assert_eq!(
+            bug_index(&bugs, 2, &prefix),
+            "```\n1. EoA-7   Crash with 'Optifine'\n\
+2. EoA-12  Hobbits eat too much, which makes them …\n```"
+        );
+        assert!(bug_index(&bugs, 5, &prefix).ends_with("```\n_...and 3 more_"));
+        assert_eq!(bug_index(&[], 0, &prefix), "_No open bugs!_");
+    }
+
+    #[test]
+    fn test_bug_markdown() {
+        let timestamp = utc(NaiveDate::from_ymd(2022, 3, 4).and_hms(12, 30, 0));
+        let mut bug = BugReport {
+            bug_id: 12,
+            guild_id: EOA_DISCORD,
+            channel_id: ChannelId(1),
+            message_id: MessageId(2),
+            title: "Crash on load".into(),
+            status: BugStatus::Resolved,
+            timestamp,
+            status_since: timestamp + chrono::Duration::days(1),
+            category: BugCategory::SaRenewed,
+            links: vec![],
+            labels: vec![],
+            merged_into: None,
+            saved_message: None,
+            snoozed_until: None,
+            archived: false,
+            summary: None,
+        };
+        assert_eq!(
+            bug_markdown(&bug, "EoA-12", None, None),
+            "## EoA-12: Crash on load
+
+**Status:** Resolved
+**Category:** SA Renewed
+**Reported:** 2022-03-04 12:30 UTC
+**Status since:** 2022-03-05 12:30 UTC
+"
+        );
+
+        bug.labels = vec!["crash".into(), "mod-conflict".into()];
+        bug.links = vec![BugLink {
+            id: 40,
+            num: 1,
+            url: "https://github.com/issue".into(),
+            title: "Issue".into(),
+        }];
+        let message = SavedMessage {
+            author: "Tom".into(),
+            content: "It *crashes*".into(),
+            attachment_url: Some("https://cdn/log.png".into()),
+        };
+        assert_eq!(
+            bug_markdown(&bug, "EoA-12", Some(&message), Some("https://discord/link")),
+            "## EoA-12: Crash on load
+
+**Status:** Resolved
+**Category:** SA Renewed
+**Reported:** 2022-03-04 12:30 UTC by Tom
+**Status since:** 2022-03-05 12:30 UTC
+**Labels:** crash, mod-conflict
+
+### Description
+
+It *crashes*
+
+![attachment](https://cdn/log.png)
+
+### Links
+
+- [Original message](https://discord/link)
+- [Issue](https://github.com/issue)
+"
+        );
+
+        // the summary replaces the content of the message
+        bug.summary = Some("Crashes with Optifine".into());
+        let report = bug_markdown(&bug, "EoA-12", Some(&message), None);
+        assert!(report.contains("### Description\n\nCrashes with Optifine\n"));
+        assert!(!report.contains("It *crashes*"));
+    }
+}
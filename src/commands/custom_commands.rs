@@ -46,10 +46,13 @@ pub async fn manual_dispatch(
 ) -> CommandResult {
     let framework = {
         let data_read = ctx.data.read().await;
-        data_read
-            .get::<FrameworkKey>()
-            .expect("There should be a framework in the typemap")
-            .clone()
+        match data_read.get::<FrameworkKey>() {
+            Some(framework) => framework.clone(),
+            None => {
+                println!("=== ERROR ===\nNo framework in the type map\n=== END ===");
+                return Err("Could not find the framework to dispatch the command".into());
+            }
+        }
     };
 
     let prefix =
@@ -79,6 +82,8 @@ pub async fn manual_dispatch(
 }
 
 #[command]
+#[description = "Display or remove the custom commands"]
+#[usage = "[display|remove] [command name]"]
 #[aliases("command")]
 #[sub_commands(define, custom_command_remove, custom_command_display)]
 pub async fn custom_command(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
@@ -161,7 +166,11 @@ Channel: {:?}\nMessage: {}\n=== END ===",
                         _ => Ok(()),
                     };
                 } else if s == "admin" {
-                    failure!(ctx, msg, "You are not an admin on this server!");
+                    failure!(
+                        ctx,
+                        msg,
+                        "You need to be a server admin to use this command!"
+                    );
                     return Ok(());
                 }
             }
@@ -254,7 +263,10 @@ Channel: {:?}\nMessage: {}\n=== END ===",
 
         if is_alias {
             if let Some(command) = message.extra["command"].as_str() {
-                manual_dispatch(ctx.clone(), msg, command).await?;
+                if let Err(e) = manual_dispatch(ctx.clone(), msg, command).await {
+                    failure!(ctx, msg, "Couldn't run the aliased command `{}`", command);
+                    return Err(e);
+                }
                 return Ok(());
             }
         }
@@ -274,7 +286,9 @@ Channel: {:?}\nMessage: {}\n=== END ===",
 }
 
 #[command]
-#[checks(is_admin)]
+#[description = "Define or update a custom command"]
+#[usage = "<command name> <json command>"]
+#[checks(is_admin, not_in_maintenance)]
 #[only_in(guilds)]
 pub async fn define(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
@@ -367,7 +381,8 @@ pub async fn define(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
 }
 
 #[command]
-#[checks(is_admin)]
+#[description = "Remove a custom command"]
+#[checks(is_admin, not_in_maintenance)]
 #[aliases("remove", "delete")]
 async fn custom_command_remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
@@ -390,6 +405,7 @@ async fn custom_command_remove(ctx: &Context, msg: &Message, mut args: Args) ->
 }
 
 #[command]
+#[description = "Display the JSON of a custom command"]
 #[aliases("display", "show")]
 #[checks(is_admin)]
 async fn custom_command_display(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
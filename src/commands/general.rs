@@ -9,8 +9,10 @@ use serenity::utils::{colours, Colour};
 use crate::api::curseforge;
 use crate::check::*;
 use crate::constants::{CURSEFORGE_ID_LEGACY, CURSEFORGE_ID_RENEWED};
+use crate::utils::discord_timestamp;
 
 #[command]
+#[description = "Display where to get the Renewed submods"]
 #[only_in(guilds)]
 pub async fn renewed(ctx: &Context, msg: &Message) -> CommandResult {
     msg.channel_id
@@ -34,6 +36,7 @@ pub async fn renewed(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Display where to get the Legacy submods"]
 #[only_in(guilds)]
 pub async fn legacy(ctx: &Context, msg: &Message) -> CommandResult {
     msg.channel_id
@@ -68,6 +71,8 @@ fn pretty_large_int<T: Into<u128>>(x: T) -> String {
 }
 
 #[command]
+#[description = "Display the mod download link"]
+#[usage = "[legacy|renewed]"]
 #[aliases("download")]
 pub async fn curseforge(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let id = if args.single::<String>().unwrap_or_default().to_lowercase() == "renewed" {
@@ -151,6 +156,8 @@ pub async fn curseforge(ctx: &Context, msg: &Message, mut args: Args) -> Command
 }
 
 #[command]
+#[description = "Display the Forge version needed by the mod"]
+#[usage = "[legacy]"]
 #[only_in(guilds)]
 pub async fn forge(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let (version, mc) = if args.single::<String>().unwrap_or_default() == "legacy" {
@@ -191,6 +198,7 @@ Forge {} or later for Minecraft {}",
 }
 
 #[command]
+#[description = "Explain how to fix a mod file saved as a zip"]
 #[only_in(guilds)]
 pub async fn coremod(ctx: &Context, msg: &Message) -> CommandResult {
     msg.channel_id
@@ -213,6 +221,7 @@ To fix this, go to your `/.minecraft/mods` folder and change the file extension!
 }
 
 #[command]
+#[description = "Send the bot invite link"]
 #[checks(allowed_blacklist)]
 pub async fn invite(ctx: &Context, msg: &Message) -> CommandResult {
     let user_icon = ctx.cache.current_user_field(|user| user.face());
@@ -256,6 +265,7 @@ pub async fn invite(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Display the invite link to the community Discord"]
 pub async fn discord(ctx: &Context, msg: &Message) -> CommandResult {
     msg.channel_id
         .say(
@@ -268,6 +278,7 @@ https://discord.gg/kQAh9eh",
 }
 
 #[command]
+#[description = "Display the mod Facebook page link"]
 #[aliases("fb")]
 pub async fn facebook(ctx: &Context, msg: &Message) -> CommandResult {
     msg.channel_id
@@ -298,6 +309,7 @@ updates and teasers [here](https://www.facebook.com/erasofarda/)!",
 }
 
 #[command]
+#[description = "Display the mod donation links"]
 #[aliases("donation", "paypal")]
 pub async fn donate(ctx: &Context, msg: &Message) -> CommandResult {
     msg.channel_id
@@ -338,6 +350,8 @@ pub async fn donate(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Display information about a user"]
+#[usage = "[user]"]
 #[only_in(guilds)]
 #[checks(allowed_blacklist)]
 #[aliases("user")]
@@ -382,13 +396,13 @@ pub async fn user_info(ctx: &Context, msg: &Message, mut args: Args) -> CommandR
                 }
                 e.field(
                     "Account creation date",
-                    &user.id.created_at().format("%d %B %Y at %R"),
+                    discord_timestamp(&user.id.created_at(), 'f'),
                     true,
                 );
                 if let Some(joined_at) = member.joined_at {
                     e.field(
                         "Account join date",
-                        joined_at.format("%d %B %Y at %R"),
+                        discord_timestamp(&joined_at, 'f'),
                         true,
                     );
                 }
@@ -1,6 +1,18 @@
+use serenity::builder::CreateEmbed;
 use serenity::client::Context;
-use serenity::framework::standard::{macros::command, CommandResult};
-use serenity::model::channel::Message;
+use serenity::framework::standard::{
+    macros::command, Args, Command, CommandGroup, CommandResult, OnlyIn,
+};
+use serenity::model::{
+    channel::Message,
+    interactions::{
+        application_command::{
+            ApplicationCommand, ApplicationCommandInteraction, ApplicationCommandOptionType,
+        },
+        InteractionApplicationCommandCallbackDataFlags, InteractionResponseType,
+    },
+};
+use serenity::prelude::SerenityError;
 use serenity::utils::Colour;
 
 use crate::check::*;
@@ -8,14 +20,366 @@ use crate::constants::{MANAGE_BOT_PERMS, OWNER_ID};
 use crate::database::{
     config::{get_minecraft_ip, get_prefix},
     custom_commands::get_custom_commands_list,
+    settings::{get_bool_setting, DISABLE_IP_PERSISTENCE},
 };
-use crate::is_admin;
 use crate::utils::has_permission;
+use crate::{failure, is_admin, COMMAND_GROUPS};
+
+/// Name shown for a command: its first name without an underscore, as the
+/// code names of subcommands are prefixed, like `bug_status`
+fn display_name(command: &Command) -> &'static str {
+    command
+        .options
+        .names
+        .iter()
+        .find(|name| !name.contains('_'))
+        .copied()
+        .unwrap_or(command.options.names[0])
+}
+
+/// Whether a command is shown to a user by [`help_all`] and
+/// `!help <command>`
+fn is_listed(command: &Command, is_admin: bool, is_owner: bool) -> bool {
+    let options = command.options;
+    options.help_available
+        && (!options.owners_only || is_owner)
+        && (is_admin
+            || !options
+                .checks
+                .iter()
+                .any(|check| matches!(check.name, "is_admin" | "is_permanent_admin")))
+}
+
+/// Finds a command from the words typed after the prefix, like `bug status`
+/// or `q&a moderator`, ignoring the arguments that follow. Returns the command
+/// with its full name.
+fn find_command(words: &[&str]) -> Option<(String, &'static Command)> {
+    let first = words.first()?;
+    let (mut path, mut commands, words) = match COMMAND_GROUPS.iter().find(|group| {
+        group
+            .options
+            .prefixes
+            .iter()
+            .any(|prefix| prefix.eq_ignore_ascii_case(first))
+    }) {
+        Some(group) => (
+            vec![group.options.prefixes[0]],
+            group.options.commands.to_vec(),
+            &words[1..],
+        ),
+        None => (
+            vec![],
+            COMMAND_GROUPS
+                .iter()
+                .filter(|group| group.options.prefixes.is_empty())
+                .flat_map(|group| group.options.commands.iter().copied())
+                .collect(),
+            words,
+        ),
+    };
+
+    let mut found = None;
+    for word in words {
+        let command = commands.iter().copied().find(|command| {
+            command
+                .options
+                .names
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(word))
+        });
+        match command {
+            Some(command) => {
+                path.push(display_name(command));
+                commands = command.options.sub_commands.to_vec();
+                found = Some(command);
+            }
+            // the next words are arguments
+            None => break,
+        }
+    }
+    found.map(|command| (path.join(" "), command))
+}
+
+/// Joins lines into embed field values of at most 1024 bytes, without cutting
+/// lines
+fn field_values(lines: &[String]) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    for line in lines {
+        if !current.is_empty() && current.len() + line.len() + 1 > 1024 {
+            values.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+    if !current.is_empty() {
+        values.push(current);
+    }
+    values
+}
+
+/// Commands of a group shown to a user, one per line
+fn group_lines(group: &CommandGroup, prefix: &str, is_admin: bool, is_owner: bool) -> Vec<String> {
+    let group_prefix = group
+        .options
+        .prefixes
+        .first()
+        .map(|group_prefix| format!("{} ", group_prefix))
+        .unwrap_or_default();
+    group
+        .options
+        .commands
+        .iter()
+        .filter(|command| is_listed(command, is_admin, is_owner))
+        .map(|command| {
+            format!(
+                "`{}{}{}`  {}",
+                prefix,
+                group_prefix,
+                display_name(command),
+                command.options.desc.unwrap_or_default()
+            )
+        })
+        .collect()
+}
+
+/// Who can use a command and where, from its checks and options
+fn restrictions(command: &Command) -> Vec<&'static str> {
+    let options = command.options;
+    let mut restrictions = Vec::new();
+    if options.owners_only {
+        restrictions.push("Bot owner only");
+    }
+    for check in options.checks {
+        restrictions.push(match check.name {
+            "is_admin" => "Bot admins only",
+            "is_permanent_admin" => "Bot admins without a temporary grant only",
+            "is_bug_reporter" => "Bot admins and bug reporters only",
+            "is_lotr_discord" => "Only in the LOTR Mod Community Discord",
+            "is_minecraft_server" => "Only in servers with a Minecraft server",
+            _ => continue,
+        });
+    }
+    match options.only_in {
+        OnlyIn::Guild => restrictions.push("Not available in DMs"),
+        OnlyIn::Dm => restrictions.push("Only in DMs"),
+        _ => (),
+    }
+    restrictions
+}
+
+/// Builds the usage of a command found by [`find_command`]
+fn command_help_embed<'a>(
+    e: &'a mut CreateEmbed,
+    prefix: &str,
+    name: &str,
+    command: &Command,
+    is_admin: bool,
+    is_owner: bool,
+) -> &'a mut CreateEmbed {
+    let options = command.options;
+    let aliases = options
+        .names
+        .iter()
+        .filter(|alias| **alias != display_name(command) && !alias.contains('_'))
+        .map(|alias| format!("`{}`", alias))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let subcommands = options
+        .sub_commands
+        .iter()
+        .filter(|subcommand| is_listed(subcommand, is_admin, is_owner))
+        .map(|subcommand| {
+            format!(
+                "`{}{} {}`  {}",
+                prefix,
+                name,
+                display_name(subcommand),
+                subcommand.options.desc.unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>();
+    let restrictions = restrictions(command).join("\n");
+
+    e.colour(Colour::DARK_GREEN);
+    e.title(format!(
+        "{}{}{}",
+        prefix,
+        name,
+        options
+            .usage
+            .map(|usage| format!(" {}", usage))
+            .unwrap_or_default()
+    ));
+    e.description(options.desc.unwrap_or("No description"));
+    if !aliases.is_empty() {
+        e.field("Aliases", aliases, false);
+    }
+    for value in field_values(&subcommands) {
+        e.field("Subcommands", value, false);
+    }
+    if !restrictions.is_empty() {
+        e.field("Restrictions", restrictions, false);
+    }
+    e
+}
+
+/// Builds the list of the commands shown to a user, by group
+fn command_list_embed<'a>(
+    e: &'a mut CreateEmbed,
+    prefix: &str,
+    is_admin: bool,
+    is_owner: bool,
+) -> &'a mut CreateEmbed {
+    e.colour(Colour::DARK_GREEN);
+    e.title("All commands");
+    for group in COMMAND_GROUPS {
+        let lines = group_lines(group, prefix, is_admin, is_owner);
+        for (i, value) in field_values(&lines).into_iter().enumerate() {
+            let name = match i {
+                0 => group.options.description.unwrap_or(group.name),
+                _ => "\u{200b}",
+            };
+            e.field(name, value, false);
+        }
+    }
+    e.footer(|f| {
+        f.text(format!(
+            "Use {}help <command> for the usage of a command",
+            prefix
+        ))
+    })
+}
+
+/// Sends the usage of the command named by `words`, like `bug status`, in DMs
+async fn display_command_help(ctx: &Context, msg: &Message, words: &[&str]) -> CommandResult {
+    let server_id = msg.guild_id.unwrap_or_default();
+    let prefix = get_prefix(ctx, server_id)
+        .await
+        .unwrap_or_else(|| "!".into());
+    let is_owner = msg.author.id == OWNER_ID;
+    let is_admin = is_admin_user(ctx, server_id, msg.author.id).await;
+
+    let (name, command) = match find_command(words) {
+        Some((name, command)) if is_listed(command, is_admin, is_owner) => (name, command),
+        _ => {
+            failure!(
+                ctx,
+                msg,
+                "Unknown command `{}`. Use  `{}help all`  to list the commands.",
+                words.join(" "),
+                prefix
+            );
+            return Ok(());
+        }
+    };
+
+    msg.author
+        .dm(ctx, |m| {
+            m.embed(|e| command_help_embed(e, &prefix, &name, command, is_admin, is_owner))
+        })
+        .await?;
+
+    if msg.guild_id.is_some() {
+        msg.reply(ctx, "Command help message sent to DMs!").await?;
+    }
+
+    Ok(())
+}
+
+/// Registers `/help`, which shows the command list or the usage of a command
+/// like `!help all` and `!help <command>`, without leaving the channel
+pub async fn register_help_slash_command(ctx: &Context) -> Result<(), SerenityError> {
+    ApplicationCommand::create_global_application_command(ctx, |c| {
+        c.name("help")
+            .description("List the commands, or display the usage of one")
+            .create_option(|o| {
+                o.name("command")
+                    .description("The command, like \"bug status\"")
+                    .kind(ApplicationCommandOptionType::String)
+                    .required(false)
+            })
+    })
+    .await?;
+    Ok(())
+}
+
+pub async fn handle_help_slash_command(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+) -> CommandResult {
+    let server_id = command.guild_id.unwrap_or_default();
+    let prefix = get_prefix(ctx, server_id)
+        .await
+        .unwrap_or_else(|| "!".into());
+    let is_owner = command.user.id == OWNER_ID;
+    let is_admin = is_admin_user(ctx, server_id, command.user.id).await;
+
+    let mut words: Vec<&str> = command
+        .data
+        .options
+        .first()
+        .and_then(|option| option.value.as_ref())
+        .and_then(serde_json::Value::as_str)
+        .map(|words| words.split_whitespace().collect())
+        .unwrap_or_default();
+    // `/help bug status` and `/help !bug status` both work
+    if let Some(first) = words.first_mut() {
+        let word: &str = first;
+        *first = word.strip_prefix(prefix.as_str()).unwrap_or(word);
+    }
+
+    let found = match find_command(&words) {
+        Some((name, found)) if is_listed(found, is_admin, is_owner) => Some((name, found)),
+        _ if words.is_empty() => None,
+        _ => {
+            command
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|d| {
+                            d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                                .content(format!(
+                                    "Unknown command `{}`. Use  `/help`  to list the commands.",
+                                    words.join(" ")
+                                ))
+                        })
+                })
+                .await?;
+            return Ok(());
+        }
+    };
+
+    command
+        .create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|d| {
+                    d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                        .create_embed(|e| match &found {
+                            Some((name, found)) => {
+                                command_help_embed(e, &prefix, name, found, is_admin, is_owner)
+                            }
+                            None => command_list_embed(e, &prefix, is_admin, is_owner),
+                        })
+                })
+        })
+        .await?;
+
+    Ok(())
+}
 
 #[command]
+#[description = "Send the list of commands in DMs, or the usage of one command"]
+#[usage = "[all | <command>]"]
 #[aliases("commands")]
-#[sub_commands(json, custom_commands, bugtracker, admin_help)]
-pub async fn help(ctx: &Context, msg: &Message) -> CommandResult {
+#[sub_commands(json, custom_commands, bugtracker, admin_help, help_all)]
+pub async fn help(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    if !args.is_empty() {
+        let words: Vec<_> = args.rest().split_whitespace().collect();
+        return display_command_help(ctx, msg, &words).await;
+    }
+
     let server_id = msg.guild_id.unwrap_or_default();
     let is_admin = msg.author.id == OWNER_ID
         || is_admin!(ctx, msg)
@@ -24,7 +388,8 @@ pub async fn help(ctx: &Context, msg: &Message) -> CommandResult {
     let prefix = get_prefix(ctx, server_id)
         .await
         .unwrap_or_else(|| "!".into());
-    let is_minecraft_server = get_minecraft_ip(ctx, server_id).await.is_some();
+    let is_minecraft_server = get_minecraft_ip(ctx, server_id).await.is_some()
+        || get_bool_setting(ctx, server_id, DISABLE_IP_PERSISTENCE).await;
 
     let cclist = get_custom_commands_list(ctx, server_id)
         .await
@@ -73,6 +438,8 @@ pub async fn help(ctx: &Context, msg: &Message) -> CommandResult {
 "`{prefix}curseforge [legacy|renewed]`  Display the mod download link (default: `legacy`)
 `{prefix}invite`  Send the bot invite link
 `{prefix}help{json}`  Send this message in DMs
+`{prefix}help all`  List all the commands, and  `{prefix}help <command>`  to display the usage of one
+`/help [command]`  The same, in a message only you can see
 `{prefix}donate`  Display the mod donation links
 `{prefix}facebook`  Display the mod Facebook page link
 `{prefix}instagram`  Display the mod Instagram page link
@@ -133,13 +500,40 @@ Available languages: `en`, `de`, `fr`, `es`, `nl`, `ja`, `zh`, `ru`
                         e.field(
                             "**Minecraft server commands**",
                             format!(
-                                "`{prefix}ip{}`  Display the server ip{}
-`{prefix}online [ip]`  Display the server status and a list of online players \
-(default: the server's set ip)
+                                "`{prefix}ip{}`  Display the server ip and whether the server is online{}
+`{prefix}online [ip] [detailed] [names:false]`  Display the server status and a list of online players \
+(default: the server's set ip). Use  `detailed`  to show the full player list, with links to the player profiles, and server details. \
+Use  `names:false`  to only show the player count. Admins can make this the default with  `{prefix}settings hide_player_names on`, \
+and add instructions to join the server with  `{prefix}settings join_instructions <text>`. \
+Player lists too long for the message are posted in a thread, which  `{prefix}settings delete_player_threads on`  \
+deletes after 10 minutes. The embed colours can be changed with  \
+`{prefix}settings online_colour <#RRGGBB>`  and  `{prefix}settings offline_colour <#RRGGBB>`.
+	Admins can be alerted when the player count crosses a threshold with  \
+`{prefix}online threshold <players> above|below [#channel] [once]`, listed with  \
+`{prefix}online threshold`  and removed with  `{prefix}online threshold remove <players> above|below`.
+	Admins can pin the server status with the 📌 button of  `{prefix}online`: it is posted in the channel \
+set with  `{prefix}settings status_channel <#channel>`  (default: the channel of the button), replacing the previous one, \
+and kept up to date.
+	Admins who don't want the server IP stored can use  `{prefix}settings disable_ip_persistence on`: the stored IP \
+is removed,  `{prefix}ip set`  is refused, and  `{prefix}online <server ip>`  needs the IP every time.
+`{prefix}compare <ip> <ip>`  Display the status of two servers side by side
+`{prefix}serverlist{}`  Display the status of all the servers registered on this guild. \
+Admins can show each server with its favicon with  `{prefix}settings serverlist_icons on`.
+`{prefix}uptime`  Display how often the server was online in the last 24 hours and 7 days
 ",
-                                if is_admin { " [set <server ip>]" } else { "" },
                                 if is_admin {
-                                    ", if it exists; use `set` to add one."
+                                    " [set <server ip> | queryport <port|off>]"
+                                } else {
+                                    ""
+                                },
+                                if is_admin {
+                                    ", if it exists; use `set` to add one. Use `queryport` if \
+the server only answers to the legacy query protocol."
+                                } else {
+                                    ""
+                                },
+                                if is_admin {
+                                    " [add <name> <server ip> | remove <name>]"
                                 } else {
                                     ""
                                 },
@@ -173,7 +567,34 @@ Available languages: `en`, `de`, `fr`, `es`, `nl`, `ja`, `zh`, `ru`
     Ok(())
 }
 
+/// Lists the commands of all the groups registered in the framework, so that
+/// the list is always up to date
 #[command]
+#[description = "List all the commands in DMs"]
+#[aliases("all")]
+pub async fn help_all(ctx: &Context, msg: &Message) -> CommandResult {
+    let server_id = msg.guild_id.unwrap_or_default();
+    let prefix = get_prefix(ctx, server_id)
+        .await
+        .unwrap_or_else(|| "!".into());
+    let is_owner = msg.author.id == OWNER_ID;
+    let is_admin = is_admin_user(ctx, server_id, msg.author.id).await;
+
+    msg.author
+        .dm(ctx, |m| {
+            m.embed(|e| command_list_embed(e, &prefix, is_admin, is_owner))
+        })
+        .await?;
+
+    if msg.guild_id.is_some() {
+        msg.reply(ctx, "Command list sent to DMs!").await?;
+    }
+
+    Ok(())
+}
+
+#[command]
+#[description = "Send the JSON documentation of the announcements in DMs"]
 #[checks(is_admin)]
 async fn json(ctx: &Context, msg: &Message) -> CommandResult {
     msg.author
@@ -232,6 +653,7 @@ async fn json(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Send the custom commands documentation in DMs"]
 #[checks(is_admin)]
 #[aliases("custom")]
 async fn custom_commands(ctx: &Context, msg: &Message) -> CommandResult {
@@ -301,11 +723,14 @@ The command returns a unique bug id.
  initial bug report content.**
 \tYou can optionnally use  `{prefix}track legacy [status] <bug title>`  \
 to create a legacy bug report.
+\tA summary can follow the title after a  `|`  with spaces around it, e.g.  `{prefix}track Crash on load | Happens \
+with Optifine`. It is shown instead of the message content, even if the message is deleted.
 `{prefix}bug link <bug id> [link url] [link title]`  Adds additional information to the bug \
 report referenced by its `bug id`. Can also be used with an inline reply to a message, \
 in which case you don't need to specify a url.
- \tThe command returns a unique link id which you can remove with the command  \
- `{prefix}bug link remove <bug id> <link id>`.
+ \tThe command returns the link number, which you can remove with the command  \
+ `{prefix}bug link remove <bug id> <link number>`. Links are numbered from 1 in each bug, \
+ and the numbers shift when one is removed.
 ",
                         prefix = prefix,
                     ),
@@ -323,12 +748,94 @@ chronological order starting from the latest one, and with a default limit of 10
  (or `vanilla`) and `closed`.
  \tYou can optionnally use  `{prefix}bugs [legacy|renewed] [latest|oldest] [status] [limit]`  \
  to display legacy only or renewed only bugs.
+`{prefix}bugs by <user> [legacy|renewed] [status]`  Displays the bugs submitted by a user, \
+with the same options as above. Only bugs tracked since reporters are stored are listed.
+`{prefix}bugs label <label> [legacy|renewed] [status]`  Displays the bugs with a label, \
+with the same options as above. Use  `{prefix}bugs labels`  to list all the labels, and  \
+`{prefix}stats labels`  to count the open bugs of each label.
+`{prefix}bugs withimage|noimage [legacy|renewed] [status]`  Displays the bugs whose original \
+message had an attachment, or had none, with the same options as above. Bugs reported before \
+their message was saved are only listed after  `{prefix}bug refresh <bug id>`.
+`{prefix}bugs [legacy|renewed] snoozed [latest|oldest] [page]`  Displays the snoozed bugs, \
+which are hidden from the other lists.
+`{prefix}bugs leaderboard [days]`  Shows the users with the most bug reports, over the last \
+`days` days or since reporters are stored.
+`{prefix}bugs [legacy|renewed] index [latest|oldest|highest|lowest]`  Lists the open bugs as a \
+compact numbered list of ids and short titles, for quick reference (highest priority first by \
+default).
+`{prefix}bugs [legacy|renewed] stale [days]`  Lists the open bugs without any status or title \
+change in the last `days` days (default 30), oldest first.
+`{prefix}bugs resolved since <YYYY-MM-DD> [plain]`  Lists the bugs resolved since the given \
+date, one per line, for changelogs. `plain` outputs a code block instead of an embed.
+`{prefix}bugs [legacy|renewed] between <version> <version>`  Lists the bugs fixed in the given \
+range of versions (inclusive), e.g.  `{prefix}bugs between v41.0 v41.2`.
 `{prefix}bug <bug id>`  Displays a single bug.
+\tThe  `/bug`  slash command can also list bugs and change their status, title and links.
 `{prefix}bug rename <bug id> <new title>`  Change a bug's title.
+`{prefix}bug summary <bug id> [text]`  Change a bug's summary, shown instead of its original \
+message. Without a text, the original message is shown again.
 `{prefix}bug status <bug id> <new status>`  Change a bug's status.
+\tReplying to a bug embed of the bot with just a status, like  `resolved`  or  `critical`, \
+also changes it.
+`{prefix}bug triage <bug id> <status> <category>`  Change a bug's status and category \
+(`fa_renewed`, `fa_legacy`, `sa_renewed` or `sa_legacy`) at once.
+`{prefix}bug label add <bug id> <label>`  Tag a bug with a label of at most 20 letters, \
+digits, `-` or `_`. Labels are case-insensitive. Use  `{prefix}bug label remove <bug id> <label>`  \
+to remove it.
+`{prefix}bug subscribe <#channel> label <label>`  Post the bugs given a label in a channel. \
+Use  `{prefix}bug unsubscribe <#channel> label <label>`  to stop, and  \
+`{prefix}bug subscribe <#channel>`  to list the labels of a channel.
+`{prefix}bug snooze <bug id> <YYYY-MM-DD|off>`  Hide an open bug from  `{prefix}bugs`  until \
+the given date. Its subscribers are notified when it wakes up.
 `{prefix}bug toggle <bug id>`  Switch a bug's edition between renewed and legacy.
+`{prefix}bug clone <bug id>`  Copy a bug to the other edition (legacy or renewed), \
+with a new bug id.
+`{prefix}bug setmessage <bug id>`  Used as a reply, change the original message of a bug \
+to the replied message.
+`{prefix}bug refresh <bug id>`  Update the saved copy of a bug's original message after it \
+was edited. The saved copy is shown if the original message gets deleted.
+`{prefix}bug merge <bug id> into <bug id>`  Move the links and subscribers of the first bug \
+to the second one, and close the first bug.
+`{prefix}bug notify pause <minutes>`  Stop sending notifications to subscribers and watchers \
+for a while, e.g. during a triage session. Use  `{prefix}bug notify resume`  to resume early.
+`{prefix}bug notify <bug id>`  Send the current status of a bug to its subscribers again, e.g. \
+after an outage, and report how many DMs could not be sent.
+`{prefix}bug colour <status> [#RRGGBB|reset]`  Display or change the embed colour of a status \
+on this server.
+`{prefix}bug marker <status> [emoji|reset]`  Display or change the emoji shown before the bugs \
+of a status on this server, e.g. if the default ones are not available.
+`{prefix}bug branding [name <name> | icon <url> | reset]`  Display or change the name and \
+icon shown on the bugtracker embeds of this server.
+`{prefix}bug prefix [<prefix> | reset]`  Display or change the prefix of the bug ids on this \
+server, e.g. `EoA` in `EoA-12`.
+`{prefix}bug webhook add <name> <webhook url> [avatar url] [display name]`  Save a webhook \
+to post bugs to another server, optionally with its own name and avatar. The command message \
+is deleted, as the URL is a secret. Use  `{prefix}bug webhook [list | remove <name>]`  to \
+manage them.
+`{prefix}bug post <bug id> <webhook name>`  Post a bug's embed through a saved webhook.
+`{prefix}bug recent changes [n]`  Show the latest `n` status changes across all bugs \
+(default 10, at most 25).
+`{prefix}bug diff <bug id> <YYYY-MM-DD>`  Show how a bug's status and title changed since \
+the given date.
+`{prefix}bug audit links [--remove] [--check-urls]`  List the links pointing to deleted \
+Discord messages (bot owner only). `--remove` deletes them, `--check-urls` also checks \
+other links.
+`{prefix}bug md <bug id>`  Show a bug as a markdown report, to copy into a GitHub issue.
+`{prefix}bug discussion <bug id>`  Summarize the thread started on a bug report: how many people \
+took part and when it was last active.
+`{prefix}bug raw <bug id>`  Show the stored fields of a bug as JSON (bot owner only).
+`{prefix}bug archive before <YYYY-MM-DD>`  Move the bugs resolved or closed before that date \
+out of the bug lists and statistics. They can still be displayed (bot owner only).
+`{prefix}bug import`  Create bugs from an attached CSV file with the columns  \
+`title, status, category[, link]`  (bot owner only). Invalid rows are skipped and reported \
+with their line number. The imported bugs have no reporter.
 
-`{prefix}bug statistics` Show bugtracker statistics.
+`{prefix}bug statistics` Show bugtracker statistics, with buttons listing the bugs of each status.
+`{prefix}bug watch <bug id>`  Get a DM for any change to a bug. Use  `{prefix}bug unwatch <bug id>`  \
+to stop.
+`{prefix}digest`  Show a summary of the bugtracker activity in the last 7 days.
+`{prefix}digest channel [channel mention|off]`  Display or change the channel where the digest \
+is posted every week.
 ",
                         prefix = prefix
                     ),
@@ -337,10 +844,13 @@ chronological order starting from the latest one, and with a default limit of 10
                 e.field(
                     "**Closing a bug report**",
                     format!(
-                        "`{prefix}resolve <bug id>`  Marks a bug as resolved. \
-Equivalent to  `{prefix}bug status <bug id> resolved`.
+                        "`{prefix}resolve <bug id> [version]`  Marks a bug as resolved. \
+Equivalent to  `{prefix}bug status <bug id> resolved`. The optional version, e.g. `v41.2`, is \
+the one that fixes the bug, for  `{prefix}bugs between`.
 `{prefix}bug close <bug id>`  Marks a bug as closed. \
 Equivalent to  `{prefix}bug status <bug id> closed`.
+\tAdd  `--dry-run`  to any of these commands or to  `{prefix}bug status`  to see what would \
+change without changing anything.
 ",
                         prefix = prefix,
                     ),
@@ -360,16 +870,33 @@ Equivalent to  `{prefix}bug status <bug id> closed`.
 }
 
 #[command]
+#[description = "Send the bugtracker commands in DMs"]
 #[checks(is_admin)]
 #[aliases("bug", "bugs")]
-pub async fn bugtracker(ctx: &Context, msg: &Message) -> CommandResult {
+pub async fn bugtracker(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    if !args.is_empty() {
+        let words: Vec<_> = ["bug"]
+            .into_iter()
+            .chain(args.rest().split_whitespace())
+            .collect();
+        return display_command_help(ctx, msg, &words).await;
+    }
     display_bugtracker_help(ctx, msg).await
 }
 
 #[command]
+#[description = "Send the admin commands in DMs"]
 #[checks(is_admin)]
 #[aliases("admin")]
-async fn admin_help(ctx: &Context, msg: &Message) -> CommandResult {
+async fn admin_help(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    if !args.is_empty() {
+        let words: Vec<_> = ["admin"]
+            .into_iter()
+            .chain(args.rest().split_whitespace())
+            .collect();
+        return display_command_help(ctx, msg, &words).await;
+    }
+
     let prefix = get_prefix(ctx, msg.guild_id.unwrap_or_default())
         .await
         .unwrap_or_else(|| "!".into());
@@ -386,8 +913,21 @@ async fn admin_help(ctx: &Context, msg: &Message) -> CommandResult {
 `{prefix}admin add <user mention>`  Give a user admin rights for the bot
 `{prefix}admin remove <user mention>`  Removes admin rights for a user
 `{prefix}admin list`  Display a list of bot admins
+`{prefix}admin grant <user mention> <minutes>`  Give a user admin rights for a limited time, \
+e.g. for an event (bot owner only). Use  `{prefix}admin revoke <user mention>`  to end it early
 `{prefix}blacklist [user or channel mention]`  Prevent some commands to be used by the user or \
-in the channel (except for bot admins). When used without arguments, displays the blacklist.", 
+in the channel (except for bot admins). When used without arguments, displays the blacklist.
+`{prefix}autodelete [on|off]`  Display or toggle the deletion of the bot's error messages after \
+a few seconds
+`{prefix}locale [language]`  Display or change the language of the bot's most common responses
+`{prefix}settings [key] [value|reset]`  Display all the server settings, or display or change one \
+of them. Setting  `auto_close_days`  closes the bugs resolved for that many days. Setting  \
+`bug_reporter_role`  lets the members with that role report bugs. Setting  \
+`archive_bug_threads`  to  `on`  or  `lock`  archives the thread of a bug report when it is \
+resolved or closed with the buttons.
+`{prefix}perms`  List the permissions the bot is missing in the current channel
+`{prefix}ip resolve [server ip]`  Show how the server IP resolves, to tell DNS issues apart from \
+a server that is down", 
                         prefix=prefix
                     ),
                     false,
@@ -442,3 +982,39 @@ or leave empty to get a list of commands
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_command() {
+        let (name, command) = find_command(&["BUG", "status", "12"]).unwrap();
+        assert_eq!(name, "bug status");
+        assert_eq!(command.options.names[0], "bug_status");
+        assert_eq!(find_command(&["ip", "resolve"]).unwrap().0, "ip resolve");
+        assert_eq!(
+            find_command(&["q&a", "moderator"]).unwrap().0,
+            "q&a moderator"
+        );
+        // the commands of a group with a prefix need it
+        assert!(find_command(&["moderator"]).is_none());
+        assert!(find_command(&["unknown", "status"]).is_none());
+    }
+
+    #[test]
+    fn test_commands_have_descriptions() {
+        let mut commands: Vec<&Command> = COMMAND_GROUPS
+            .iter()
+            .flat_map(|group| group.options.commands.iter().copied())
+            .collect();
+        while let Some(command) = commands.pop() {
+            assert!(
+                command.options.desc.is_some(),
+                "`{}` has no description",
+                command.options.names[0]
+            );
+            commands.extend(command.options.sub_commands.iter().copied());
+        }
+    }
+}
@@ -9,6 +9,8 @@ use crate::success;
 use crate::utils::NotInGuild;
 
 #[command]
+#[description = "Send a floppa picture"]
+#[usage = "[n]"]
 #[only_in(guilds)]
 #[checks(allowed_blacklist)]
 #[bucket = "basic"]
@@ -24,6 +26,7 @@ async fn floppa(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 }
 
 #[command]
+#[description = "Send the aeugh video"]
 #[only_in(guilds)]
 #[checks(allowed_blacklist)]
 #[bucket = "basic"]
@@ -39,6 +42,7 @@ async fn aeugh(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Send the dagohon video"]
 #[only_in(guilds)]
 #[checks(allowed_blacklist)]
 #[bucket = "basic"]
@@ -54,6 +58,8 @@ async fn dagohon(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Display a colour, or a random one"]
+#[usage = "[#RRGGBB]"]
 #[checks(allowed_blacklist)]
 #[bucket = "basic"]
 #[only_in(guilds)]
@@ -82,6 +88,9 @@ async fn colour(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 }
 
 #[command]
+#[description = "Add a floppa picture"]
+#[usage = "<image url>"]
+#[checks(not_in_maintenance)]
 async fn floppadd(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
 
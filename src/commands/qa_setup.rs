@@ -7,8 +7,10 @@ use crate::check::*;
 use crate::database::qa_data;
 
 #[command]
+#[description = "Add or remove Q&A moderators"]
+#[usage = "<user mentions>"]
 #[only_in(guilds)]
-#[checks(is_admin, is_lotr_discord)]
+#[checks(is_admin, is_lotr_discord, not_in_maintenance)]
 #[aliases("moderator")]
 pub async fn qa_moderator(ctx: &Context, msg: &Message) -> CommandResult {
     let guild_id = msg.guild_id.expect("Should be only used in guilds");
@@ -33,8 +35,10 @@ pub async fn qa_moderator(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Set the channel where Q&A answers are posted"]
+#[usage = "<channel>"]
 #[only_in(guilds)]
-#[checks(is_admin, is_lotr_discord)]
+#[checks(is_admin, is_lotr_discord, not_in_maintenance)]
 #[aliases("answers")]
 pub async fn qa_answer_channel(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let guild_id = msg.guild_id.expect("Should be only used in guilds");
@@ -54,8 +58,10 @@ pub async fn qa_answer_channel(ctx: &Context, msg: &Message, args: Args) -> Comm
 }
 
 #[command]
+#[description = "Set the channel where Q&A questions are asked"]
+#[usage = "<channel>"]
 #[only_in(guilds)]
-#[checks(is_admin, is_lotr_discord)]
+#[checks(is_admin, is_lotr_discord, not_in_maintenance)]
 #[aliases("questions")]
 pub async fn qa_question_channel(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let guild_id = msg.guild_id.expect("Should be only used in guilds");
@@ -75,7 +81,8 @@ pub async fn qa_question_channel(ctx: &Context, msg: &Message, args: Args) -> Co
 }
 
 #[command]
-#[checks(is_admin, is_lotr_discord)]
+#[description = "Disable the Q&A"]
+#[checks(is_admin, is_lotr_discord, not_in_maintenance)]
 #[only_in(guilds)]
 #[aliases("disable")]
 pub async fn qa_disable(ctx: &Context, msg: &Message) -> CommandResult {
@@ -89,6 +96,7 @@ pub async fn qa_disable(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Display the Q&A setup"]
 #[checks(is_admin, is_lotr_discord)]
 #[only_in(guilds)]
 #[aliases("info")]
@@ -153,6 +161,7 @@ pub async fn qa_summary(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Print the Q&A channels cache in the bot logs"]
 #[owners_only]
 #[checks(is_admin)]
 #[aliases("cache")]
@@ -8,7 +8,7 @@ use serenity::model::prelude::*;
 use crate::check::*;
 use crate::constants::OWNER_ID;
 use crate::database::roles;
-use crate::utils::{get_json_from_message, has_permission, NotInGuild};
+use crate::utils::{discord_timestamp, get_json_from_message, has_permission, NotInGuild};
 use crate::{failure, handle_json_error, is_admin, role_cache, success, warn};
 
 use Reason::*;
@@ -177,6 +177,8 @@ async fn display_roles(ctx: &Context, msg: &Message, in_dms: bool) -> CommandRes
 }
 
 #[command]
+#[description = "Claim a role defined by the admins of this server"]
+#[usage = "<role name>"]
 #[only_in(guilds)]
 #[checks(user_blacklist)]
 #[sub_commands(add, delete, listroles, display, cache)]
@@ -260,7 +262,7 @@ pub async fn role(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
                         "You have not been on the server for enough time to be able to claim the \
 **{}** role! It will unlock on {}",
                         role.name,
-                        date.format("<t:%s:F>")
+                        discord_timestamp(&date, 'F')
                     );
                 }
                 IncompatibleRole(incompatible_role_name) => {
@@ -317,8 +319,9 @@ are trying to claim.",
 }
 
 #[command]
+#[description = "Make a role claimable"]
 #[only_in(guilds)]
-#[checks(is_admin)]
+#[checks(is_admin, not_in_maintenance)]
 pub async fn add(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
     if let Ok(role_id) = args.parse::<RoleId>() {
@@ -356,8 +359,9 @@ pub async fn add(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 }
 
 #[command]
+#[description = "Make a role no longer claimable"]
 #[only_in(guilds)]
-#[checks(is_admin)]
+#[checks(is_admin, not_in_maintenance)]
 #[aliases("remove")]
 pub async fn delete(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
@@ -373,6 +377,7 @@ pub async fn delete(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 }
 
 #[command]
+#[description = "List the roles that can be claimed"]
 #[only_in(guilds)]
 #[checks(allowed_blacklist)]
 #[aliases("roles")]
@@ -381,6 +386,7 @@ pub async fn listroles(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Display the claimable roles, or the aliases of one"]
 #[only_in(guilds)]
 #[checks(allowed_blacklist)]
 #[aliases("show")]
@@ -421,6 +427,7 @@ pub async fn display(ctx: &Context, msg: &Message, args: Args) -> CommandResult
 }
 
 #[command]
+#[description = "Log the role cache to the console"]
 #[owners_only]
 #[checks(is_admin)]
 async fn cache(ctx: &Context) -> CommandResult {
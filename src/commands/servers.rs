@@ -1,55 +1,151 @@
+use chrono::{Duration, Utc};
+use serenity::builder::{CreateComponents, CreateEmbed};
 use serenity::client::Context;
 use serenity::framework::standard::{macros::command, Args, CommandResult};
-use serenity::model::channel::Message;
+use serenity::futures::stream::{self, StreamExt};
+use serenity::http::error::{DiscordJsonError, ErrorResponse};
+use serenity::model::channel::{Channel, ChannelType, Message, ReactionType};
+use serenity::model::id::{ChannelId, GuildId, MessageId};
+use serenity::model::interactions::{
+    message_component::{ButtonStyle, MessageComponentInteraction},
+    InteractionApplicationCommandCallbackDataFlags, InteractionResponseType,
+};
+use serenity::prelude::{HttpError, SerenityError};
 use serenity::utils::Colour;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::api::minecraft::get_server_status;
+use crate::api::minecraft::{
+    get_server_diagnostics, get_server_status, get_server_status_with_fallback, MinecraftServer,
+    ServerAddress, ServerStatus, ServerStatusError, DEFAULT_PORT,
+};
 use crate::check::*;
-use crate::database::config::{delete_minecraft_ip, get_minecraft_ip, set_minecraft_ip};
-use crate::utils::{parse_motd, NotInGuild};
-use crate::{failure, success};
+use crate::commands::bug_reports::{UNKNOWN_CHANNEL, UNKNOWN_MESSAGE};
+use crate::constants::{
+    FAVICON_API, IP_STATUS_TIMEOUT, MAX_CONCURRENT_STATUS_QUERIES, MAX_EMBEDS_PER_MESSAGE,
+    MAX_JOIN_INSTRUCTIONS_LENGTH, MAX_PLAYER_ALERTS, MAX_PLAYER_ALERT_THRESHOLD,
+    MAX_PLAYER_LIST_FIELDS, MINECRAFT_API_TIMEOUT, NAMEMC_PROFILE, PLAYER_THREAD_LIFETIME,
+    STATUS_LOG_RETENTION_DAYS, STATUS_POLL_INTERVAL,
+};
+use crate::database::config::{
+    add_minecraft_server, add_player_alert, delete_minecraft_ip, get_all_player_alerts,
+    get_minecraft_ip, get_player_alerts, get_server_uptime, list_all_minecraft_ips,
+    list_all_minecraft_servers, list_minecraft_servers, log_server_status, prune_server_status_log,
+    remove_minecraft_server, remove_player_alert, set_minecraft_ip, set_player_alert_armed,
+    AlertDirection, PlayerAlert,
+};
+use crate::database::settings::{
+    get_bool_setting, get_colour_setting, get_setting, remove_setting, set_setting,
+    DELETE_PLAYER_THREADS, DISABLE_IP_PERSISTENCE, HIDE_PLAYER_NAMES, JOIN_INSTRUCTIONS,
+    OFFLINE_COLOUR, ONLINE_COLOUR, QUERY_PORT, SERVERLIST_ICONS, STATUS_CHANNEL, STATUS_MESSAGE,
+};
+use crate::database::DbError;
+use crate::i18n::{get_locale, translate};
+use crate::shutdown::get_shutdown;
+use crate::utils::{discord_timestamp, parse_motd, InteractionEasyResponse, NotInGuild};
+use crate::{db_failure, failure, success, t};
+
+/// Favicon of a server, fetched by Discord when the embed is shown. The
+/// favicon API does not follow SRV records, so it is given the address the
+/// status query was resolved to, when known.
+fn favicon_url(ip: &str, server: &MinecraftServer) -> String {
+    let address = server
+        .resolved_address()
+        .unwrap_or_else(|| ServerAddress::normalize(ip));
+    format!("{}{}", FAVICON_API, address.query_string())
+}
+
+/// Custom id of the 📌 button of [`online`], handled by [`handle_status_pin`]
+pub const STATUS_PIN_CUSTOM_ID: &str = "online_pin";
 
 #[command]
+#[description = "Display the Minecraft server IP and whether it is online"]
 #[only_in(guilds)]
 #[aliases("ip")]
 #[bucket = "basic"]
-#[sub_commands(set_ip, remove_ip)]
+#[sub_commands(set_ip, remove_ip, query_port, ip_whereis, ip_resolve)]
 #[checks(is_minecraft_server)]
 async fn server_ip(ctx: &Context, msg: &Message) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
 
     if let Some(ip) = get_minecraft_ip(ctx, server_id).await {
+        // the status is only a hint: don't hold the IP back if the query is slow
+        let status =
+            match tokio::time::timeout(IP_STATUS_TIMEOUT, get_server_status(ctx, &ip)).await {
+                Ok(Ok(server)) => Some(format!(
+                    "🟢 {} • {}",
+                    t!(ctx, msg, "online.online"),
+                    t!(
+                        ctx,
+                        msg,
+                        "online.players",
+                        server.players.online,
+                        server.players.max
+                    )
+                )),
+                Ok(Err(ServerStatusError::Offline)) => {
+                    Some(format!("🔴 {}", t!(ctx, msg, "online.offline")))
+                }
+                Ok(Err(_)) | Err(_) => None,
+            };
+        let description = match status {
+            Some(status) => format!("`{}`\n{}", ip, status),
+            None => format!("`{}`", ip),
+        };
+
         msg.channel_id
             .send_message(ctx, |m| {
                 m.embed(|e| {
                     e.colour(Colour::TEAL);
                     e.title("Server IP:");
-                    e.description(format!("`{}`", ip));
+                    e.description(description);
                     e
                 })
             })
             .await?;
-    } else {
+    } else if get_bool_setting(ctx, server_id, DISABLE_IP_PERSISTENCE).await {
         failure!(
             ctx,
             msg,
-            "No registered Minecraft IP for this server. Set one using  `!ip set <server ip>`."
+            "This server does not store its Minecraft server IP, pass it to each command instead: \
+`!online <server ip>`"
         )
+    } else {
+        failure!(ctx, msg, t!(ctx, msg, "ip.not_set"))
     }
 
     Ok(())
 }
 
 #[command]
+#[description = "Set the Minecraft server IP of this server"]
 #[only_in(guilds)]
-#[checks(is_admin)]
+#[checks(is_admin, not_in_maintenance)]
 #[aliases("set")]
 pub async fn set_ip(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
 
+    if get_bool_setting(ctx, server_id, DISABLE_IP_PERSISTENCE).await {
+        failure!(
+            ctx,
+            msg,
+            "This server does not store its Minecraft server IP: pass it to each command instead, \
+e.g. `!online <server ip>`. Admins can store it again after \
+`!settings disable_ip_persistence off`."
+        );
+        return Ok(());
+    }
+
     if let Some(ip) = args.current() {
+        if let Err(e) = ServerAddress::validate(ip) {
+            failure!(ctx, msg, "The server IP {}.", e);
+            return Ok(());
+        }
         println!("Setting up IP to {} on {}", ip, server_id);
-        set_minecraft_ip(ctx, server_id, ip).await?;
+        if let Err(e) = set_minecraft_ip(ctx, server_id, ip).await {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
         success!(ctx, msg, "Set Minecraft server IP to  `{}`", ip)
     } else {
         failure!(ctx, msg, "You must provide an IP address to set.")
@@ -58,80 +154,454 @@ pub async fn set_ip(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     Ok(())
 }
 
+/// Lists the guilds where an IP is registered, as their IP or in their server
+/// list. The IPs are stored as they were entered, so every registration is
+/// normalized and compared the same way status queries are.
 #[command]
+#[description = "List the servers where an IP is registered"]
+#[owners_only]
+#[aliases("whereis")]
+pub async fn ip_whereis(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let address = match args.current() {
+        Some(ip) => ServerAddress::normalize(ip),
+        None => {
+            failure!(ctx, msg, "You must provide an IP address to look for.");
+            return Ok(());
+        }
+    };
+
+    let (ips, servers) =
+        match tokio::join!(list_all_minecraft_ips(ctx), list_all_minecraft_servers(ctx)) {
+            (Ok(ips), Ok(servers)) => (ips, servers),
+            (Err(e), _) | (_, Err(e)) => {
+                db_failure!(ctx, msg, e);
+                return Err(e.into());
+            }
+        };
+
+    let mut registrations: Vec<(GuildId, String)> = ips
+        .into_iter()
+        .filter(|(_, ip)| ServerAddress::normalize(ip) == address)
+        .map(|(guild_id, _)| (guild_id, "server IP".to_string()))
+        .chain(
+            servers
+                .into_iter()
+                .filter(|(_, _, ip)| ServerAddress::normalize(ip) == address)
+                .map(|(guild_id, name, _)| (guild_id, format!("server list: `{}`", name))),
+        )
+        .collect();
+    if registrations.is_empty() {
+        failure!(ctx, msg, "`{}` is not registered in any server.", address);
+        return Ok(());
+    }
+    registrations.sort_by_key(|(guild_id, _)| *guild_id);
+
+    let mut guild_count = 0;
+    let mut description = String::new();
+    let mut hidden = 0;
+    let mut previous = None;
+    for (guild_id, registration) in &registrations {
+        if previous != Some(guild_id) {
+            guild_count += 1;
+        }
+        let line = format!(
+            "{} (`{}`): {}\n",
+            ctx.cache
+                .guild_field(*guild_id, |g| g.name.clone())
+                .unwrap_or_else(|| "Unknown guild".to_string()),
+            guild_id,
+            registration
+        );
+        // keep some room for the hidden count
+        if hidden > 0 || description.len() + line.len() > 4000 {
+            hidden += 1;
+        } else {
+            description.push_str(&line);
+        }
+        previous = Some(guild_id);
+    }
+    if hidden > 0 {
+        description.push_str(&format!("...and {} more", hidden));
+    }
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.title(format!(
+                    "`{}` is registered {} time{} in {} server{}",
+                    address,
+                    registrations.len(),
+                    if registrations.len() > 1 { "s" } else { "" },
+                    guild_count,
+                    if guild_count > 1 { "s" } else { "" }
+                ));
+                e.description(description)
+            })
+            .reference_message(msg)
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[description = "Remove the Minecraft server IP of this server"]
 #[only_in(guilds)]
-#[checks(is_admin)]
+#[checks(is_admin, not_in_maintenance)]
 #[aliases("remove", "unset")]
 pub async fn remove_ip(ctx: &Context, msg: &Message) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
 
     let ip = get_minecraft_ip(ctx, server_id).await;
-    if let Some(ip) = ip {
-        delete_minecraft_ip(ctx, server_id).await?;
-        success!(
+    match (delete_minecraft_ip(ctx, server_id).await, ip) {
+        (Ok(()), Some(ip)) => success!(
             ctx,
             msg,
             "Successfully removed ip  `{}`  from this server",
             ip
-        )
-    } else {
-        failure!(ctx, msg, "No registered Minecraft IP for this server.")
+        ),
+        (Ok(()), None) | (Err(DbError::NotFound), _) => {
+            failure!(ctx, msg, "No registered Minecraft IP for this server.")
+        }
+        (Err(e), _) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
     }
     Ok(())
 }
 
 #[command]
+#[description = "Set or remove the legacy query port of the Minecraft server"]
 #[only_in(guilds)]
-#[checks(is_minecraft_server)]
-#[bucket = "basic"]
-pub async fn online(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+#[checks(is_admin, not_in_maintenance)]
+#[aliases("queryport")]
+pub async fn query_port(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let server_id = msg.guild_id.ok_or(NotInGuild)?;
 
-    let ip = if !args.is_empty() {
-        args.single::<String>()?
-    } else if let Some(ip) = get_minecraft_ip(ctx, server_id).await {
-        ip
+    if args.current() == Some("off") {
+        remove_setting(ctx, server_id, QUERY_PORT).await?;
+        success!(ctx, msg, "Removed the query port of this server");
+    } else if let Ok(port) = args.single::<u16>() {
+        set_setting(ctx, server_id, QUERY_PORT, &port.to_string()).await?;
+        success!(
+            ctx,
+            msg,
+            "Set the query port to  `{}`. It will be used when the server looks offline.",
+            port
+        );
+    } else if let Some(port) = get_setting(ctx, server_id, QUERY_PORT).await {
+        msg.reply(ctx, format!("The query port of this server is  `{}`", port))
+            .await?;
     } else {
         failure!(
             ctx,
             msg,
-            "No registered Minecraft IP for this server. Set one using  `!ip set <server ip>`."
+            "No query port set. Set one using  `!ip queryport <port>`."
         );
+    }
+
+    Ok(())
+}
+
+#[command]
+#[description = "Display the Minecraft server status and online players"]
+#[usage = "[ip] [detailed] [names:false]"]
+#[checks(is_minecraft_server)]
+#[bucket = "basic"]
+#[sub_commands(online_threshold)]
+pub async fn online(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let mut detailed = false;
+    let mut names = None;
+    let mut ip_arg = None;
+    for arg in args.iter::<String>().flatten() {
+        match arg.to_lowercase().as_str() {
+            "detailed" | "detailed:true" => detailed = true,
+            "detailed:false" => detailed = false,
+            "names" | "names:true" => names = Some(true),
+            "names:false" => names = Some(false),
+            _ => ip_arg = ip_arg.or(Some(arg)),
+        }
+    }
+    if let Some(Err(e)) = ip_arg.as_deref().map(ServerAddress::validate) {
+        failure!(ctx, msg, "The server IP {}.", e);
         return Ok(());
+    }
+    let show_names = match (names, msg.guild_id) {
+        (Some(names), _) => names,
+        (None, Some(server_id)) => !get_bool_setting(ctx, server_id, HIDE_PLAYER_NAMES).await,
+        (None, None) => true,
     };
-    let server = get_server_status(ctx, &ip).await;
-    if let Some(server) = server {
-        msg.channel_id
-            .send_message(ctx, |m| {
-                m.embed(|e| {
-                    e.colour(Colour::DARK_GREEN);
-                    e.thumbnail(format!("https://eu.mc-api.net/v3/server/favicon/{}", &ip));
-                    e.title("Server online!");
-                    e.description(format!(
-                        "{}\n\n**IP:**  `{}`",
-                        parse_motd(&server.motd.raw.join("\n")),
-                        &ip,
-                    ));
+
+    // an explicit IP works without a guild, e.g. in DMs
+    let (ip, query_port, join_instructions, own_server) = match (ip_arg, msg.guild_id) {
+        (Some(ip), None) => (ip, None, None, false),
+        (ip_arg, Some(server_id)) => {
+            let registered_ip = get_minecraft_ip(ctx, server_id).await;
+            let ip = if let Some(ip) = ip_arg {
+                ip
+            } else if let Some(ip) = registered_ip.clone() {
+                ip
+            } else if get_bool_setting(ctx, server_id, DISABLE_IP_PERSISTENCE).await {
+                failure!(
+                    ctx,
+                    msg,
+                    "This server does not store its Minecraft server IP, you must provide it: \
+`!online <server ip>`"
+                );
+                return Ok(());
+            } else {
+                failure!(ctx, msg, t!(ctx, msg, "ip.not_set"));
+                return Ok(());
+            };
+            // the query port and join instructions are only known for the
+            // guild's own server
+            if registered_ip.as_ref() == Some(&ip) {
+                let query_port = get_setting(ctx, server_id, QUERY_PORT)
+                    .await
+                    .and_then(|port| port.parse().ok());
+                let join_instructions = get_setting(ctx, server_id, JOIN_INSTRUCTIONS).await;
+                (ip, query_port, join_instructions, true)
+            } else {
+                (ip, None, None, false)
+            }
+        }
+        (None, None) => {
+            failure!(
+                ctx,
+                msg,
+                "You must provide a server IP in direct messages: `!online <server ip>`"
+            );
+            return Ok(());
+        }
+    };
+    let server = get_server_status_with_fallback(ctx, &ip, query_port).await;
+    let locale = get_locale(ctx, msg.guild_id).await;
+    let embed = status_embed(ctx, &locale, msg.guild_id, &ip, &server, show_names).await;
+    match &server {
+        Ok(server) => {
+            let players_title = translate(
+                &locale,
+                "online.players",
+                &[&server.players.online, &server.players.max],
+            );
+            let names: Vec<String> = server
+                .players
+                .list
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .map(|player| {
+                    let name = player.name.replace('_', "\\_");
+                    match (&player.uuid, detailed) {
+                        (Some(uuid), true) => format!("[{}]({}{})", name, NAMEMC_PROFILE, uuid),
+                        _ => name,
+                    }
+                })
+                .collect();
+            // lists too long for the embed go to a thread, in the channels that have threads
+            let overflows = if detailed {
+                chunk_names(&names, 1024).len() > MAX_PLAYER_LIST_FIELDS
+            } else {
+                names.join(", ").len() > 1024
+            };
+            let thread_channel = if show_names && overflows {
+                msg.channel_id
+                    .to_channel(ctx)
+                    .await
+                    .ok()
+                    .and_then(Channel::guild)
+                    .filter(|channel| matches!(channel.kind, ChannelType::Text | ChannelType::News))
+            } else {
+                None
+            };
+            // `in_thread` replaces the player list with a pointer to the thread
+            let build_embed = |in_thread: bool| {
+                let mut e = embed.clone();
+                if in_thread {
+                    e.field(&players_title, "Full list in thread →", false);
+                }
+                if detailed {
+                    if show_names && !in_thread {
+                        let chunks = split_player_list(&names);
+                        if chunks.is_empty() {
+                            e.field(&players_title, "[]()", false);
+                        }
+                        for (i, chunk) in chunks.into_iter().enumerate() {
+                            if i == 0 {
+                                e.field(&players_title, chunk, false);
+                            } else {
+                                e.field("\u{200b}", chunk, false);
+                            }
+                        }
+                    }
                     e.field(
-                        format!(
-                            "Players: {}/{}",
-                            &server.players.online, &server.players.max
-                        ),
-                        &server
-                            .players
-                            .list
-                            .as_ref()
-                            .map(|s| {
-                                let res = s.join(", ").replace("_", "\\_");
-                                if res.len() > 1024 {
-                                    "Too many usernames to display!".into()
-                                } else {
-                                    res
-                                }
-                            })
-                            .unwrap_or_else(|| "[]()".into()),
+                        "Favicon",
+                        if server.icon.is_some() { "Yes" } else { "No" },
+                        true,
+                    );
+                    if let Some(version) = &server.version {
+                        e.field("Version", version, true);
+                    }
+                    if let Some(software) = &server.software {
+                        e.field("Software", software, true);
+                    }
+                } else if show_names && !in_thread {
+                    e.field(&players_title, player_list(server), false);
+                }
+                if let Some(mods) = server.mods.as_ref().filter(|mods| mods.count() != 0) {
+                    e.field(
+                        "Mods",
+                        match mods.lotr_version() {
+                            Some(version) => {
+                                format!("{} mods\nLOTR Mod {}", mods.count(), version)
+                            }
+                            None => format!("{} mods", mods.count()),
+                        },
+                        true,
+                    );
+                }
+                // instructions saved before the limit was lowered may be longer
+                if let Some(instructions) = &join_instructions {
+                    e.field(
+                        "How to join",
+                        instructions
+                            .chars()
+                            .take(MAX_JOIN_INSTRUCTIONS_LENGTH)
+                            .collect::<String>(),
                         false,
                     );
+                }
+                e
+            };
+            let response = msg
+                .channel_id
+                .send_message(ctx, |m| {
+                    m.set_embed(build_embed(thread_channel.is_some()));
+                    if own_server {
+                        m.components(status_pin_button);
+                    }
+                    m.reference_message(msg);
+                    m.allowed_mentions(|a| a.empty_parse());
+                    m
+                })
+                .await?;
+
+            if let Some(channel) = thread_channel {
+                // without the profile links, which would each get a preview
+                let names: Vec<String> = server
+                    .players
+                    .list
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|player| player.name.replace('_', "\\_"))
+                    .collect();
+                if let Err(e) = post_player_thread(ctx, channel.id, &response, &ip, &names).await {
+                    println!(
+                        "=== ERROR ===\nCould not post the player list thread: {}\n=== END ===",
+                        e
+                    );
+                    // the embed points to the thread, show the truncated list instead
+                    let mut response = response;
+                    response
+                        .edit(ctx, |m| m.set_embed(build_embed(false)))
+                        .await?;
+                }
+            }
+        }
+        Err(_) => {
+            msg.channel_id
+                .send_message(ctx, |m| {
+                    m.set_embed(embed);
+                    if own_server {
+                        m.components(status_pin_button);
+                    }
+                    m.reference_message(msg);
+                    m.allowed_mentions(|a| a.empty_parse());
+                    m
+                })
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// One column of the [`compare`] embed
+fn compare_column(status: &ServerStatus) -> String {
+    match status {
+        Ok(server) => {
+            let mut column = format!(
+                "🟢 Online\n**Players:** {}/{}",
+                server.players.online, server.players.max
+            );
+            if let Some(version) = &server.version {
+                column.push_str(&format!("\n**Version:** {}", version));
+            }
+            if let Some(software) = &server.software {
+                column.push_str(&format!("\n**Software:** {}", software));
+            }
+            if let Some(version) = server.mods.as_ref().and_then(|mods| mods.lotr_version()) {
+                column.push_str(&format!("\n**LOTR Mod:** {}", version));
+            }
+            column
+        }
+        Err(ServerStatusError::Offline) => "🔴 Offline".into(),
+        Err(ServerStatusError::Dns) => "🔴 Unknown host".into(),
+        Err(_) => "🟠 Unreachable".into(),
+    }
+}
+
+/// Lists, adds or removes the alerts posted when the player count of the main
+/// server crosses a threshold
+#[command]
+#[description = "List, add or remove the player count alerts"]
+#[only_in(guilds)]
+#[checks(is_admin, not_in_maintenance)]
+#[aliases("threshold", "alert")]
+pub async fn online_threshold(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+    let alerts = match get_player_alerts(ctx, server_id).await {
+        Ok(alerts) => alerts,
+        Err(e) => {
+            db_failure!(ctx, msg, e);
+            return Err(e.into());
+        }
+    };
+
+    if args.is_empty() {
+        if alerts.is_empty() {
+            failure!(
+                ctx,
+                msg,
+                "No player count alert. Add one using  `!online threshold <players> above|below [#channel] [once]`."
+            );
+            return Ok(());
+        }
+        let lines: Vec<String> = alerts
+            .iter()
+            .map(|alert| {
+                format!(
+                    "• {} **{}** players in <#{}>{}",
+                    alert.direction,
+                    alert.threshold,
+                    alert.channel_id,
+                    if alert.once { " (once)" } else { "" }
+                )
+            })
+            .collect();
+        msg.channel_id
+            .send_message(ctx, |m| {
+                m.embed(|e| {
+                    e.colour(Colour::TEAL);
+                    e.title("Player count alerts");
+                    e.description(lines.join("\n"));
+                    e.footer(|f| {
+                        f.text(format!(
+                            "The server is checked every {} minutes",
+                            STATUS_POLL_INTERVAL.as_secs() / 60
+                        ))
+                    });
                     e
                 });
                 m.reference_message(msg);
@@ -139,20 +609,1142 @@ pub async fn online(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
                 m
             })
             .await?;
-    } else {
+        return Ok(());
+    }
+
+    let remove = args
+        .current()
+        .map_or(false, |arg| arg.eq_ignore_ascii_case("remove"));
+    if remove {
+        args.advance();
+    }
+    let (threshold, direction) = match (
+        args.single::<u32>(),
+        args.single::<String>()
+            .ok()
+            .and_then(|direction| direction.parse::<AlertDirection>().ok()),
+    ) {
+        (Ok(threshold), Some(direction)) => (threshold, direction),
+        _ => {
+            failure!(
+                ctx,
+                msg,
+                "You must provide a player count and  `above`  or  `below`: `!online threshold <players> above|below [#channel] [once]`"
+            );
+            return Ok(());
+        }
+    };
+
+    if remove {
+        match remove_player_alert(ctx, server_id, threshold, direction).await {
+            Ok(()) => success!(
+                ctx,
+                msg,
+                "Removed the alert {} {} players",
+                direction,
+                threshold
+            ),
+            Err(DbError::NotFound) => failure!(
+                ctx,
+                msg,
+                "There is no alert {} {} players on this guild.",
+                direction,
+                threshold
+            ),
+            Err(e) => {
+                db_failure!(ctx, msg, e);
+                return Err(e.into());
+            }
+        }
+        return Ok(());
+    }
+
+    if threshold == 0 && direction == AlertDirection::Above {
+        failure!(ctx, msg, "The server always has at least 0 players!");
+        return Ok(());
+    }
+    if threshold > MAX_PLAYER_ALERT_THRESHOLD {
+        failure!(
+            ctx,
+            msg,
+            "The player count must be at most {}.",
+            MAX_PLAYER_ALERT_THRESHOLD
+        );
+        return Ok(());
+    }
+    let replaced = alerts
+        .iter()
+        .any(|alert| alert.threshold == threshold && alert.direction == direction);
+    if !replaced && alerts.len() >= MAX_PLAYER_ALERTS {
+        failure!(
+            ctx,
+            msg,
+            "This guild already has {} player count alerts, remove one first.",
+            MAX_PLAYER_ALERTS
+        );
+        return Ok(());
+    }
+    let mut channel_id = msg.channel_id;
+    let mut once = false;
+    for arg in args.iter::<String>().flatten() {
+        if arg.eq_ignore_ascii_case("once") {
+            once = true;
+        } else if let Ok(channel) = arg.parse::<ChannelId>() {
+            channel_id = channel;
+        } else {
+            failure!(ctx, msg, "Unknown argument  `{}`", arg);
+            return Ok(());
+        }
+    }
+    match channel_id.to_channel(ctx).await {
+        Ok(Channel::Guild(channel)) if channel.guild_id == server_id => (),
+        _ => {
+            failure!(
+                ctx,
+                msg,
+                "<#{}> is not a channel of this guild.",
+                channel_id
+            );
+            return Ok(());
+        }
+    }
+
+    let alert = PlayerAlert {
+        threshold,
+        direction,
+        channel_id,
+        once,
+        // only fires when the threshold is crossed after this
+        armed: false,
+    };
+    if let Err(e) = add_player_alert(ctx, server_id, &alert).await {
+        db_failure!(ctx, msg, e);
+        return Err(e.into());
+    }
+    success!(
+        ctx,
+        msg,
+        "The server crossing {} {} players will be announced in <#{}>{}",
+        direction,
+        threshold,
+        channel_id,
+        if once { ", once" } else { "" }
+    );
+
+    Ok(())
+}
+
+/// Shows each step of reaching a server, to tell DNS issues apart from a
+/// server that is down
+#[command]
+#[description = "Show each step of reaching the Minecraft server"]
+#[only_in(guilds)]
+#[checks(is_admin)]
+#[aliases("resolve")]
+#[bucket = "basic"]
+pub async fn ip_resolve(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+    let ip = match args.current() {
+        Some(ip) => {
+            if let Err(e) = ServerAddress::validate(ip) {
+                failure!(ctx, msg, "The server IP {}.", e);
+                return Ok(());
+            }
+            ip.to_string()
+        }
+        None => match get_minecraft_ip(ctx, server_id).await {
+            Some(ip) => ip,
+            None => {
+                failure!(ctx, msg, t!(ctx, msg, "ip.not_set"));
+                return Ok(());
+            }
+        },
+    };
+    let address = ServerAddress::normalize(&ip);
+
+    msg.channel_id.broadcast_typing(ctx).await?;
+    let (local, diagnostics) = tokio::join!(
+        tokio::time::timeout(
+            MINECRAFT_API_TIMEOUT,
+            tokio::net::lookup_host((address.host.as_str(), address.port))
+        ),
+        get_server_diagnostics(ctx, &ip)
+    );
+
+    let mut lines = Vec::new();
+    match &diagnostics {
+        _ if address.port != DEFAULT_PORT => {
+            lines.push("➖ SRV records are not used with an explicit port".to_string())
+        }
+        Ok(diagnostics) => lines.push(match &diagnostics.srv_target {
+            Some(target) => format!(
+                "✅ SRV record `_minecraft._tcp.{}` points to `{}`",
+                address.host, target
+            ),
+            None => format!("➖ No SRV record `_minecraft._tcp.{}`", address.host),
+        }),
+        Err(_) => (),
+    }
+    lines.push(match local {
+        Ok(Ok(addresses)) => {
+            let mut ips = addresses.map(|a| a.ip().to_string()).collect::<Vec<_>>();
+            ips.sort();
+            ips.dedup();
+            ips.truncate(5);
+            if ips.is_empty() {
+                format!("❌ `{}` has no A or AAAA record", address.host)
+            } else {
+                format!(
+                    "✅ `{}` resolves to `{}` for the bot",
+                    address.host,
+                    ips.join("`, `")
+                )
+            }
+        }
+        Ok(Err(_)) => format!("❌ The bot could not resolve `{}`", address.host),
+        Err(_) => format!("❌ Resolving `{}` timed out", address.host),
+    });
+    match &diagnostics {
+        Ok(diagnostics) => {
+            lines.push(match &diagnostics.resolved {
+                Some(resolved) => format!("✅ The status API reached `{}`", resolved),
+                None => "❌ The status API could not resolve the host".to_string(),
+            });
+            lines.push(match diagnostics.players {
+                Some((online, max)) if diagnostics.ping => format!(
+                    "✅ The server answered the status ping ({}/{} players)",
+                    online, max
+                ),
+                _ if diagnostics.ping => "✅ The server answered the status ping".to_string(),
+                _ => "❌ The server did not answer the status ping".to_string(),
+            });
+        }
+        Err(e) => lines.push(format!("❌ {}, try again in a few minutes", e)),
+    }
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.colour(match &diagnostics {
+                    Ok(diagnostics) if diagnostics.ping => Colour::DARK_GREEN,
+                    Ok(_) => Colour::RED,
+                    Err(_) => Colour::ORANGE,
+                });
+                e.title(format!("Diagnostics for `{}`", address));
+                e.description(lines.join("\n"))
+            })
+            .reference_message(msg)
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[description = "Display the status of two servers side by side"]
+#[usage = "<ip> <ip>"]
+#[checks(is_minecraft_server)]
+#[bucket = "basic"]
+pub async fn compare(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let (ip1, ip2) = match (args.single::<String>(), args.single::<String>()) {
+        (Ok(ip1), Ok(ip2)) => (ip1, ip2),
+        _ => {
+            failure!(ctx, msg, "Usage: `!compare <server ip> <server ip>`");
+            return Ok(());
+        }
+    };
+    for ip in [&ip1, &ip2] {
+        if let Err(e) = ServerAddress::validate(ip) {
+            failure!(ctx, msg, "The server IP {}.", e);
+            return Ok(());
+        }
+    }
+
+    let (status1, status2) =
+        tokio::join!(get_server_status(ctx, &ip1), get_server_status(ctx, &ip2));
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.colour(match (status1.is_ok(), status2.is_ok()) {
+                    (true, true) => Colour::DARK_GREEN,
+                    (false, false) => Colour::RED,
+                    _ => Colour::ORANGE,
+                });
+                e.title("Server comparison");
+                e.field(format!("`{}`", ip1), compare_column(&status1), true);
+                e.field(format!("`{}`", ip2), compare_column(&status2), true);
+                e
+            });
+            m.reference_message(msg);
+            m.allowed_mentions(|a| a.empty_parse());
+            m
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[command]
+#[description = "Display the status of all the servers of this guild"]
+#[only_in(guilds)]
+#[aliases("servers")]
+#[bucket = "basic"]
+#[sub_commands(serverlist_add, serverlist_remove)]
+#[checks(is_minecraft_server)]
+pub async fn serverlist(ctx: &Context, msg: &Message) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    let mut servers = list_minecraft_servers(ctx, server_id)
+        .await
+        .unwrap_or_default();
+    if let Some(ip) = get_minecraft_ip(ctx, server_id).await {
+        servers.insert(0, ("Main server".into(), ip));
+    }
+
+    if servers.is_empty() {
+        failure!(
+            ctx,
+            msg,
+            "No registered Minecraft server for this guild. Add one using  `!serverlist add <name> <server ip>`."
+        );
+        return Ok(());
+    }
+
+    // queries can take a few seconds, let users know the bot is working on it
+    msg.channel_id.broadcast_typing(ctx).await?;
+
+    let statuses: Vec<_> = stream::iter(servers.iter())
+        .map(|(_, ip)| get_server_status(ctx, ip))
+        .buffered(MAX_CONCURRENT_STATUS_QUERIES)
+        .collect()
+        .await;
+
+    let online_count = statuses.iter().filter(|status| status.is_ok()).count();
+
+    // one embed per server, so that each can show its favicon
+    if servers.len() <= MAX_EMBEDS_PER_MESSAGE
+        && get_bool_setting(ctx, server_id, SERVERLIST_ICONS).await
+    {
         msg.channel_id
             .send_message(ctx, |m| {
-                m.embed(|e| {
-                    e.colour(Colour::RED);
-                    e.title("Server offline...");
-                    e.description(format!("**IP:**  `{}`", &ip));
-                    e
-                });
+                m.content(format!(
+                    "**Servers: {}/{} online**",
+                    online_count,
+                    servers.len()
+                ));
+                for ((name, ip), status) in servers.iter().zip(&statuses) {
+                    m.add_embed(|e| {
+                        e.title(name);
+                        match status {
+                            Ok(server) => {
+                                e.colour(Colour::DARK_GREEN);
+                                e.description(format!(
+                                    "`{}` — {}/{} players",
+                                    ip, server.players.online, server.players.max
+                                ));
+                                // only the servers that have a favicon get a thumbnail
+                                if server.icon.is_some() {
+                                    e.thumbnail(favicon_url(ip, server));
+                                }
+                            }
+                            Err(ServerStatusError::Offline) => {
+                                e.colour(Colour::RED);
+                                e.description(format!("`{}` — offline", ip));
+                            }
+                            Err(ServerStatusError::Dns) => {
+                                e.colour(Colour::RED);
+                                e.description(format!("`{}` — unknown host", ip));
+                            }
+                            Err(_) => {
+                                e.colour(Colour::ORANGE);
+                                e.description(format!("`{}` — status unavailable", ip));
+                            }
+                        }
+                        e
+                    });
+                }
                 m.reference_message(msg);
                 m.allowed_mentions(|a| a.empty_parse());
                 m
             })
             .await?;
+        return Ok(());
     }
+
+    let lines: Vec<String> = servers
+        .iter()
+        .zip(statuses)
+        .map(|((name, ip), status)| match status {
+            Ok(server) => format!(
+                ":green_circle: **{}** `{}` — {}/{} players",
+                name, ip, server.players.online, server.players.max
+            ),
+            Err(ServerStatusError::Offline) => {
+                format!(":red_circle: **{}** `{}` — offline", name, ip)
+            }
+            Err(ServerStatusError::Dns) => {
+                format!(":red_circle: **{}** `{}` — unknown host", name, ip)
+            }
+            Err(_) => format!(":orange_circle: **{}** `{}` — status unavailable", name, ip),
+        })
+        .collect();
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.colour(if online_count > 0 {
+                    Colour::DARK_GREEN
+                } else {
+                    Colour::RED
+                });
+                e.title(format!("Servers: {}/{} online", online_count, lines.len()));
+                e.description(lines.join("\n"));
+                e
+            });
+            m.reference_message(msg);
+            m.allowed_mentions(|a| a.empty_parse());
+            m
+        })
+        .await?;
+
     Ok(())
 }
+
+#[command]
+#[description = "Add a server to the server list"]
+#[only_in(guilds)]
+#[checks(is_admin, not_in_maintenance)]
+#[aliases("add", "set")]
+pub async fn serverlist_add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    let (name, ip) = match (args.single::<String>(), args.single::<String>()) {
+        (Ok(name), Ok(ip)) => (name, ip),
+        _ => {
+            failure!(
+                ctx,
+                msg,
+                "You must provide a server name and an IP address: `!serverlist add <name> <server ip>`"
+            );
+            return Ok(());
+        }
+    };
+    if name.chars().count() > 64 {
+        failure!(ctx, msg, "The server name must be 64 characters or less.");
+        return Ok(());
+    }
+    if let Err(e) = ServerAddress::validate(&ip) {
+        failure!(ctx, msg, "The server IP {}.", e);
+        return Ok(());
+    }
+    if get_bool_setting(ctx, server_id, DISABLE_IP_PERSISTENCE).await {
+        failure!(
+            ctx,
+            msg,
+            "This server does not store Minecraft server IPs: pass them to each command instead, \
+e.g. `!online <server ip>`. Admins can store them again after \
+`!settings disable_ip_persistence off`."
+        );
+        return Ok(());
+    }
+
+    if let Err(e) = add_minecraft_server(ctx, server_id, &name, &ip).await {
+        db_failure!(ctx, msg, e);
+        return Err(e.into());
+    }
+    success!(ctx, msg, "Added server **{}** with IP  `{}`", name, ip);
+
+    Ok(())
+}
+
+#[command]
+#[description = "Remove a server from the server list"]
+#[only_in(guilds)]
+#[checks(is_admin, not_in_maintenance)]
+#[aliases("remove", "delete")]
+pub async fn serverlist_remove(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    if let Some(name) = args.current() {
+        match remove_minecraft_server(ctx, server_id, name).await {
+            Ok(()) => success!(ctx, msg, "Removed server **{}**", name),
+            Err(DbError::NotFound) => failure!(
+                ctx,
+                msg,
+                "There is no server named **{}** on this guild.",
+                name
+            ),
+            Err(e) => {
+                db_failure!(ctx, msg, e);
+                return Err(e.into());
+            }
+        }
+    } else {
+        failure!(
+            ctx,
+            msg,
+            "You must provide the name of the server to remove."
+        );
+    }
+
+    Ok(())
+}
+
+#[command]
+#[description = "Display how often the server was online lately"]
+#[only_in(guilds)]
+#[bucket = "basic"]
+#[checks(is_minecraft_server)]
+pub async fn uptime(ctx: &Context, msg: &Message) -> CommandResult {
+    let server_id = msg.guild_id.ok_or(NotInGuild)?;
+
+    let ip = match get_minecraft_ip(ctx, server_id).await {
+        Some(ip) => ip,
+        None => {
+            failure!(ctx, msg, t!(ctx, msg, "ip.not_set"));
+            return Ok(());
+        }
+    };
+
+    let mut lines = Vec::new();
+    for (label, window) in [
+        ("24 hours", Duration::hours(24)),
+        ("7 days", Duration::days(STATUS_LOG_RETENTION_DAYS)),
+    ] {
+        let (total, online) = match get_server_uptime(ctx, server_id, Utc::now() - window).await {
+            Ok(counts) => counts,
+            Err(e) => {
+                db_failure!(ctx, msg, e);
+                return Err(e.into());
+            }
+        };
+        if total == 0 {
+            lines.push(format!("**Last {}:** Not enough history yet", label));
+        } else {
+            lines.push(format!(
+                "**Last {}:** {:.1}%  ({}/{} checks online)",
+                label,
+                online as f64 * 100. / total as f64,
+                online,
+                total
+            ));
+        }
+    }
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.colour(Colour::TEAL);
+                e.title("Server uptime");
+                e.description(format!("{}\n\n**IP:**  `{}`", lines.join("\n"), ip));
+                e.footer(|f| {
+                    f.text(format!(
+                        "The server is checked every {} minutes",
+                        STATUS_POLL_INTERVAL.as_secs() / 60
+                    ))
+                });
+                e
+            });
+            m.reference_message(msg);
+            m.allowed_mentions(|a| a.empty_parse());
+            m
+        })
+        .await?;
+
+    Ok(())
+}
+
+static STATUS_POLLER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Starts checking the registered Minecraft server of every guild at a regular
+/// interval, and logs the results for [`!uptime`][uptime]. Checks that fail
+/// because of the status API are not logged. The player counts of the online
+/// servers fire the [player count alerts][online_threshold]. Calling this more
+/// than once (e.g. on reconnects) has no effect. The [status boards][handle_status_pin]
+/// are updated with the results.
+pub fn start_status_poller(ctx: Context) {
+    if STATUS_POLLER_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let shutdown = get_shutdown(&ctx).await;
+        let mut interval = tokio::time::interval(STATUS_POLL_INTERVAL);
+        while let Some(_work) = shutdown.tick(&mut interval).await {
+            let servers = match list_all_minecraft_ips(&ctx).await {
+                Ok(servers) => servers,
+                Err(e) => {
+                    println!(
+                        "=== ERROR ===\nCould not list the servers to check: {}\n=== END ===",
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            let statuses: Vec<_> = stream::iter(servers.iter())
+                .map(|(server_id, ip)| get_guild_server_status(&ctx, *server_id, ip))
+                .buffered(MAX_CONCURRENT_STATUS_QUERIES)
+                .collect()
+                .await;
+
+            let mut players = HashMap::new();
+            for ((server_id, ip), status) in servers.iter().zip(statuses) {
+                if let Err(e) = update_status_board(&ctx, *server_id, ip, &status).await {
+                    println!(
+                        "=== ERROR ===\nCould not update the status board of {}: {}\n=== END ===",
+                        server_id, e
+                    );
+                }
+                let online = match status {
+                    Ok(server) => {
+                        players.insert(*server_id, (ip.as_str(), server.players.online));
+                        true
+                    }
+                    Err(ServerStatusError::Offline | ServerStatusError::Dns) => false,
+                    Err(_) => continue,
+                };
+                if let Err(e) = log_server_status(&ctx, *server_id, online).await {
+                    println!(
+                        "=== ERROR ===\nCould not log the status of {}: {}\n=== END ===",
+                        server_id, e
+                    );
+                }
+            }
+
+            check_player_alerts(&ctx, &players).await;
+
+            let retention = Utc::now() - Duration::days(STATUS_LOG_RETENTION_DAYS);
+            if let Err(e) = prune_server_status_log(&ctx, retention).await {
+                println!(
+                    "=== ERROR ===\nCould not prune the server status log: {}\n=== END ===",
+                    e
+                );
+            }
+        }
+    });
+}
+
+/// Parses the [`STATUS_MESSAGE`] setting
+fn parse_status_board(value: &str) -> Option<(ChannelId, MessageId)> {
+    let (channel_id, message_id) = value.split_once(':')?;
+    Some((
+        ChannelId(channel_id.parse().ok()?),
+        MessageId(message_id.parse().ok()?),
+    ))
+}
+
+/// Whether a Discord call failed because the message, or its channel, was deleted
+fn is_deleted_message(error: &SerenityError) -> bool {
+    match error {
+        SerenityError::Http(e) => matches!(
+            e.as_ref(),
+            HttpError::UnsuccessfulRequest(ErrorResponse {
+                error: DiscordJsonError {
+                    code: UNKNOWN_CHANNEL | UNKNOWN_MESSAGE,
+                    ..
+                },
+                ..
+            })
+        ),
+        _ => false,
+    }
+}
+
+/// The 📌 button of [`online`], shown for the guild's own server
+fn status_pin_button(c: &mut CreateComponents) -> &mut CreateComponents {
+    c.create_action_row(|a| {
+        a.create_button(|b| {
+            b.style(ButtonStyle::Secondary)
+                .emoji(ReactionType::Unicode("📌".into()))
+                .custom_id(STATUS_PIN_CUSTOM_ID)
+        })
+    })
+}
+
+/// Embed of a server status, shared by [`online`] and the status board. The
+/// player list is left to the callers, [`online`] can show it in detail or in
+/// a thread.
+async fn status_embed(
+    ctx: &Context,
+    locale: &str,
+    guild_id: Option<GuildId>,
+    ip: &str,
+    status: &ServerStatus,
+    show_names: bool,
+) -> CreateEmbed {
+    let mut e = CreateEmbed::default();
+    match status {
+        Ok(server) => {
+            e.colour(get_colour_setting(ctx, guild_id, ONLINE_COLOUR, Colour::DARK_GREEN).await);
+            e.thumbnail(favicon_url(ip, server));
+            e.title(translate(locale, "online.online", &[]));
+            e.description(format!(
+                "{}\n\n**IP:**  `{}`{}",
+                parse_motd(&server.motd.raw.join("\n")),
+                ip,
+                if show_names {
+                    String::new()
+                } else {
+                    format!(
+                        "\n**{}**",
+                        translate(
+                            locale,
+                            "online.players",
+                            &[&server.players.online, &server.players.max],
+                        )
+                    )
+                }
+            ));
+        }
+        Err(error) => {
+            let (title, details) = match error {
+                ServerStatusError::Offline => ("online.offline", None),
+                ServerStatusError::Dns => {
+                    ("online.unknown_host", Some("online.unknown_host.details"))
+                }
+                ServerStatusError::Timeout
+                | ServerStatusError::Connection
+                | ServerStatusError::Upstream => {
+                    ("online.unavailable", Some("online.unavailable.details"))
+                }
+            };
+            e.colour(if error.is_transient() {
+                Colour::ORANGE
+            } else {
+                get_colour_setting(ctx, guild_id, OFFLINE_COLOUR, Colour::RED).await
+            });
+            e.title(translate(locale, title, &[]));
+            match details {
+                Some(details) => e.description(format!(
+                    "{}\n\n**IP:**  `{}`",
+                    translate(locale, details, &[]),
+                    ip
+                )),
+                None => e.description(format!("**IP:**  `{}`", ip)),
+            };
+        }
+    }
+    e
+}
+
+/// Player names of a server as a single embed field value
+fn player_list(server: &MinecraftServer) -> String {
+    let names = server
+        .players
+        .list
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|player| player.name.replace('_', "\\_"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    match names.len() {
+        0 => "[]()".to_string(),
+        len if len > 1024 => "Too many usernames to display!".to_string(),
+        _ => names,
+    }
+}
+
+/// Embed of a guild's status board, posted by [`handle_status_pin`] and
+/// rebuilt by [`update_status_board`]
+async fn status_board_embed(
+    ctx: &Context,
+    guild_id: GuildId,
+    ip: &str,
+    status: &ServerStatus,
+) -> CreateEmbed {
+    let locale = get_locale(ctx, Some(guild_id)).await;
+    let show_names = !get_bool_setting(ctx, guild_id, HIDE_PLAYER_NAMES).await;
+    let mut e = status_embed(ctx, &locale, Some(guild_id), ip, status, show_names).await;
+    if let (Ok(server), true) = (status, show_names) {
+        e.field(
+            translate(
+                &locale,
+                "online.players",
+                &[&server.players.online, &server.players.max],
+            ),
+            player_list(server),
+            false,
+        );
+    }
+    e.field(
+        translate(&locale, "online.last_update", &[]),
+        discord_timestamp(&Utc::now(), 'R'),
+        false,
+    );
+    e
+}
+
+/// Status of a guild's own server, with the legacy query fallback of its
+/// [`QUERY_PORT`] setting
+async fn get_guild_server_status(ctx: &Context, guild_id: GuildId, ip: &str) -> ServerStatus {
+    let query_port = get_setting(ctx, guild_id, QUERY_PORT)
+        .await
+        .and_then(|port| port.parse().ok());
+    get_server_status_with_fallback(ctx, ip, query_port).await
+}
+
+/// Handles the 📌 button of [`online`]: posts the status of the guild's server
+/// in the [`STATUS_CHANNEL`], or the channel of the button, as a status board
+/// kept up to date by the [status poller][start_status_poller]. The previous
+/// board of the guild is deleted, if it still exists.
+pub async fn handle_status_pin(
+    ctx: &Context,
+    interaction: &MessageComponentInteraction,
+) -> CommandResult {
+    let guild_id = interaction.guild_id.ok_or(NotInGuild)?;
+    if !is_admin_user(ctx, guild_id, interaction.user.id).await {
+        interaction
+            .say_ephemeral(ctx, ":x: Only the admins can pin the server status.")
+            .await;
+        return Ok(());
+    }
+    if in_maintenance(ctx).await {
+        interaction
+            .say_ephemeral(ctx, ":x: The bot is in maintenance mode.")
+            .await;
+        return Ok(());
+    }
+    let ip = match get_minecraft_ip(ctx, guild_id).await {
+        Some(ip) => ip,
+        None => {
+            interaction
+                .say_ephemeral(ctx, ":x: This server has no Minecraft server IP anymore.")
+                .await;
+            return Ok(());
+        }
+    };
+    let channel_id = get_setting(ctx, guild_id, STATUS_CHANNEL)
+        .await
+        .and_then(|channel_id| channel_id.parse().ok())
+        .map_or(interaction.channel_id, ChannelId);
+    // the setting is only checked to be a channel id when it is set
+    match channel_id.to_channel(ctx).await {
+        Ok(Channel::Guild(channel)) if channel.guild_id == guild_id => (),
+        _ => {
+            interaction
+                .say_ephemeral(
+                    ctx,
+                    format!(
+                        ":x: <#{}> is not a channel of this server, change the `status_channel` \
+setting.",
+                        channel_id
+                    ),
+                )
+                .await;
+            return Ok(());
+        }
+    }
+    // deferred, the status can take longer than the interaction deadline to
+    // fetch
+    interaction
+        .create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                .interaction_response_data(|d| {
+                    d.flags(InteractionApplicationCommandCallbackDataFlags::EPHEMERAL)
+                })
+        })
+        .await?;
+
+    let result = pin_status_board(ctx, guild_id, channel_id, &ip).await;
+    let content = match &result {
+        Ok(()) => format!(
+            "📌 The server status will be kept up to date in <#{}>.",
+            channel_id
+        ),
+        Err(_) => format!(":x: Could not pin the server status in <#{}>.", channel_id),
+    };
+    interaction
+        .edit_original_interaction_response(ctx, |r| r.content(content))
+        .await?;
+    result
+}
+
+/// Replaces the status board of a guild with a new one in `channel_id`
+async fn pin_status_board(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    ip: &str,
+) -> CommandResult {
+    remove_status_board(ctx, guild_id).await?;
+
+    let status = get_guild_server_status(ctx, guild_id, ip).await;
+    let embed = status_board_embed(ctx, guild_id, ip, &status).await;
+    let board = channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+    set_setting(
+        ctx,
+        guild_id,
+        STATUS_MESSAGE,
+        &format!("{}:{}", channel_id, board.id),
+    )
+    .await
+}
+
+/// Deletes the status board of a guild, returns whether it had one
+pub async fn remove_status_board(ctx: &Context, guild_id: GuildId) -> CommandResult<bool> {
+    let (channel_id, message_id) = match get_setting(ctx, guild_id, STATUS_MESSAGE)
+        .await
+        .as_deref()
+        .and_then(parse_status_board)
+    {
+        Some(board) => board,
+        None => return Ok(false),
+    };
+    if let Err(e) = channel_id.delete_message(ctx, message_id).await {
+        if !is_deleted_message(&e) {
+            return Err(e.into());
+        }
+    }
+    remove_setting(ctx, guild_id, STATUS_MESSAGE).await?;
+    Ok(true)
+}
+
+/// Updates the status board of a guild, if it has one. When the status API
+/// fails, the board keeps the last known status. A deleted board is
+/// forgotten until the 📌 button is used again.
+async fn update_status_board(
+    ctx: &Context,
+    guild_id: GuildId,
+    ip: &str,
+    status: &ServerStatus,
+) -> CommandResult {
+    if matches!(status, Err(error) if error.is_transient()) {
+        return Ok(());
+    }
+    let (channel_id, message_id) = match get_setting(ctx, guild_id, STATUS_MESSAGE)
+        .await
+        .as_deref()
+        .and_then(parse_status_board)
+    {
+        Some(board) => board,
+        None => return Ok(()),
+    };
+    let embed = status_board_embed(ctx, guild_id, ip, status).await;
+    match channel_id
+        .edit_message(ctx, message_id, |m| m.set_embed(embed))
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) if is_deleted_message(&e) => remove_setting(ctx, guild_id, STATUS_MESSAGE).await,
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Posts the [player count alerts][online_threshold] reached since the last
+/// check, `players` being the IP and player count of the online servers
+async fn check_player_alerts(ctx: &Context, players: &HashMap<GuildId, (&str, u32)>) {
+    let alerts = match get_all_player_alerts(ctx).await {
+        Ok(alerts) => alerts,
+        Err(e) => {
+            println!(
+                "=== ERROR ===\nCould not list the player count alerts: {}\n=== END ===",
+                e
+            );
+            return;
+        }
+    };
+
+    for (server_id, mut alert) in alerts {
+        // offline servers don't count as empty, to not fire on crashes
+        let (ip, count) = match players.get(&server_id) {
+            Some(players) => *players,
+            None => continue,
+        };
+        let was_armed = alert.armed;
+        let fired = alert.update(count);
+        let mut sent = false;
+        if fired {
+            match alert
+                .channel_id
+                .send_message(ctx, |m| {
+                    m.content(format!(
+                        "🔔 `{}` now has **{}** player{} ({} {})",
+                        ip,
+                        count,
+                        if count == 1 { "" } else { "s" },
+                        alert.direction,
+                        alert.threshold
+                    ));
+                    m.allowed_mentions(|a| a.empty_parse());
+                    m
+                })
+                .await
+            {
+                Ok(_) => sent = true,
+                Err(e) => println!(
+                    "=== ERROR ===\nCould not post the player count alert of {}: {}\n=== END ===",
+                    server_id, e
+                ),
+            }
+        }
+
+        // a `once` alert that could not be posted stays, and fires again on
+        // the next crossing
+        let res = if sent && alert.once {
+            remove_player_alert(ctx, server_id, alert.threshold, alert.direction).await
+        } else if alert.armed != was_armed {
+            set_player_alert_armed(ctx, server_id, &alert).await
+        } else {
+            continue;
+        };
+        if let Err(e) = res {
+            println!(
+                "=== ERROR ===\nCould not update the player count alert of {}: {}\n=== END ===",
+                server_id, e
+            );
+        }
+    }
+}
+
+/// Joins names into chunks of at most `max_len` bytes, without cutting names
+fn chunk_names(names: &[String], max_len: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for name in names {
+        if !current.is_empty() && current.len() + name.len() + 2 > max_len {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str(", ");
+        }
+        current.push_str(name);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Posts the full player list in a thread on the [`online`] response, for
+/// lists too long for the embed. The thread is deleted after
+/// [`PLAYER_THREAD_LIFETIME`], or on shutdown, if the guild has enabled the
+/// [`DELETE_PLAYER_THREADS`] setting.
+async fn post_player_thread(
+    ctx: &Context,
+    channel_id: ChannelId,
+    response: &Message,
+    ip: &str,
+    names: &[String],
+) -> CommandResult {
+    let name: String = format!("Players on {}", ip).chars().take(100).collect();
+    let thread = channel_id
+        .create_public_thread(ctx, response.id, |t| t.name(name).auto_archive_duration(60))
+        .await?;
+    for chunk in chunk_names(names, 2000) {
+        thread
+            .send_message(ctx, |m| {
+                m.content(chunk);
+                m.allowed_mentions(|a| a.empty_parse());
+                m
+            })
+            .await?;
+    }
+
+    if let Some(guild_id) = response.guild_id {
+        if get_bool_setting(ctx, guild_id, DELETE_PLAYER_THREADS).await {
+            let ctx = ctx.clone();
+            tokio::spawn(async move {
+                let shutdown = get_shutdown(&ctx).await;
+                let _work = match shutdown.track() {
+                    Some(work) => work,
+                    None => return,
+                };
+                // deleted early on shutdown rather than left behind
+                tokio::select! {
+                    _ = tokio::time::sleep(PLAYER_THREAD_LIFETIME) => (),
+                    _ = shutdown.started() => (),
+                }
+                if let Err(e) = thread.delete(&ctx).await {
+                    println!(
+                        "=== ERROR ===\nCould not delete the player list thread: {}\n=== END ===",
+                        e
+                    );
+                }
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Splits a player list into embed field values of at most 1024 characters,
+/// without cutting entries. Stops after [`MAX_PLAYER_LIST_FIELDS`] fields so the
+/// embed stays under Discord's total size limit.
+fn split_player_list(names: &[String]) -> Vec<String> {
+    let mut chunks: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for (i, name) in names.iter().enumerate() {
+        if !current.is_empty() && current.len() + name.len() + 2 > 1024 {
+            if chunks.len() + 1 == MAX_PLAYER_LIST_FIELDS {
+                let mut remaining = names.len() - i;
+                loop {
+                    let more = format!("\n...and {} more", remaining);
+                    if current.len() + more.len() <= 1024 {
+                        current.push_str(&more);
+                        break;
+                    }
+                    match current.rfind(", ") {
+                        Some(index) => current.truncate(index),
+                        None => current.clear(),
+                    }
+                    remaining += 1;
+                }
+                break;
+            }
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str(", ");
+        }
+        current.push_str(name);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(ip: Option<&str>, port: Option<u16>) -> MinecraftServer {
+        serde_json::from_value(serde_json::json!({
+            "online": true,
+            "ip": ip,
+            "port": port,
+            "motd": { "raw": [] },
+            "players": { "online": 0, "max": 20 },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_favicon_url() {
+        // SRV record: the host name points to another address
+        assert_eq!(
+            favicon_url(
+                "Play.Example.com",
+                &server(Some("203.0.113.7"), Some(25570))
+            ),
+            format!("{}203.0.113.7:25570", FAVICON_API)
+        );
+        // literal address, on the default port
+        assert_eq!(
+            favicon_url("203.0.113.7", &server(Some("203.0.113.7"), Some(25565))),
+            format!("{}203.0.113.7", FAVICON_API)
+        );
+        assert_eq!(
+            favicon_url("::1", &server(Some("::1"), Some(25570))),
+            format!("{}[::1]:25570", FAVICON_API)
+        );
+        // legacy query, which does not resolve the address
+        assert_eq!(
+            favicon_url("Play.Example.com:25570", &server(None, None)),
+            format!("{}play.example.com:25570", FAVICON_API)
+        );
+    }
+}
@@ -72,6 +72,8 @@ async fn eoa_wiki(ctx: &Context, msg: &Message, args: &mut Args, ns: Namespace)
 }
 
 #[command]
+#[description = "Search the Eras of Arda wiki"]
+#[usage = "[language] <query>"]
 #[sub_commands(
     eoa_user,
     eoa_category,
@@ -118,6 +120,8 @@ async fn eoa_random(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Search the LOTR Mod wiki"]
+#[usage = "[language] <query>"]
 #[aliases("lotr")]
 #[sub_commands(lotr_user, lotr_category, lotr_template, lotr_file, lotr_random)]
 pub async fn lotrmod(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
@@ -156,6 +160,8 @@ async fn lotr_random(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
+#[description = "Search Tolkien Gateway"]
+#[usage = "<query>"]
 #[aliases("tolkiengateway")]
 pub async fn tolkien(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let wiki = Wikis::TolkienGateway;
@@ -168,6 +174,8 @@ pub async fn tolkien(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
 }
 
 #[command]
+#[description = "Search the Minecraft wiki"]
+#[usage = "<query>"]
 #[aliases("mc")]
 pub async fn minecraft(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let wiki = Wikis::Minecraft;
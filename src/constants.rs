@@ -12,6 +12,99 @@ pub const EOA_DISCORD: GuildId = GuildId(325180225979809792);
 /// Maximum size, in bytes, of a JSON file for [announcements][crate::announcement]
 /// and [custom commands][crate::commands::custom_commands]
 pub const MAX_JSON_FILE_SIZE: u64 = 10240;
+/// Maximum size, in bytes, of a CSV file for a [bug import][crate::commands::bug_reports::bug_import]
+pub const MAX_CSV_FILE_SIZE: u64 = 262144;
+/// Maximum length of a [bug label][crate::database::bug_reports::normalize_label]
+pub const MAX_LABEL_LENGTH: usize = 20;
+
+/// Number of users shown on the [bug reporter leaderboard][crate::commands::bug_reports::buglist]
+pub const LEADERBOARD_SIZE: u32 = 10;
+/// Longest period, in days, the bug commands look back over
+pub const MAX_LOOKBACK_DAYS: u32 = 3650;
+/// Maximum number of bugs fetched for the
+/// [compact bug index][crate::commands::bug_reports::buglist], of which as many
+/// as fit in the embed are shown
+pub const BUG_INDEX_LIMIT: u32 = 100;
+/// Width the titles of the [compact bug index][crate::commands::bug_reports::buglist]
+/// are cut to
+pub const BUG_INDEX_TITLE_WIDTH: usize = 40;
+
+/// Maximum length of a [bug webhook][crate::commands::bug_reports::bug_webhook] name
+pub const MAX_WEBHOOK_NAME_LENGTH: usize = 32;
+
+/// Maximum length of the [join instructions][crate::database::settings::JOIN_INSTRUCTIONS],
+/// low enough that the `!online` embed stays under Discord's total size limit
+/// next to a full player list
+pub const MAX_JOIN_INSTRUCTIONS_LENGTH: usize = 512;
+
+/// Delay before [failure messages][crate::failure] are deleted, in guilds that
+/// enabled it
+pub const FAILURE_DELETION_DELAY: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// How long a command waits for a database connection before giving up with
+/// [`DbError::Busy`][crate::database::DbError::Busy]
+pub const DB_ACQUIRE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+/// Minimum delay between two "busy" replies in the same channel, see
+/// [`db_failure!`][crate::db_failure]
+pub const BUSY_NOTICE_COOLDOWN: std::time::Duration = std::time::Duration::from_secs(30);
+/// How long [confirmation buttons][crate::utils::confirm] wait for an answer
+pub const CONFIRMATION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long a [shutdown][crate::shutdown] waits for the background work in flight
+pub const SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// Maximum number of errors posted in the [error log][crate::error_log]
+/// channel every [`ERROR_LOG_WINDOW`]
+pub const ERROR_LOG_RATE_LIMIT: u32 = 5;
+pub const ERROR_LOG_WINDOW: std::time::Duration = std::time::Duration::from_secs(60);
+/// How many times [`retry_discord!`][crate::retry_discord] retries a Discord
+/// call after a transient error
+pub const DISCORD_RETRIES: u32 = 2;
+/// Delay before the first retry of [`retry_discord!`][crate::retry_discord],
+/// doubled for the next one
+pub const DISCORD_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Interval between two weekly [bug digests][crate::commands::bug_reports::digest]
+pub const DIGEST_INTERVAL: std::time::Duration = std::time::Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Interval between two checks for [snoozed][crate::commands::bug_reports::bug_snooze]
+/// bugs to wake up
+pub const SNOOZE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Activity shown by the bot, unless another one is chosen with
+/// [`!presence`][crate::commands::admin::presence]
+pub const DEFAULT_ACTIVITY: &str =
+    "The Eras of Arda Community: creating addons and submods for the Lord of the Rings Mod.";
+/// Interval between two refreshes of the open bug count shown by
+/// [`!presence bugs`][crate::commands::admin::presence]
+pub const PRESENCE_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// Interval between two checks for resolved bugs to
+/// [close automatically][crate::commands::bug_reports::start_auto_close]
+pub const AUTO_CLOSE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+
+/// Interval between two status checks of the guilds' Minecraft servers, logged
+/// for the [`!uptime`][crate::commands::servers::uptime] command
+pub const STATUS_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+/// How long server status checks are kept, matching the longest
+/// [`!uptime`][crate::commands::servers::uptime] window
+pub const STATUS_LOG_RETENTION_DAYS: i64 = 7;
+/// Maximum number of [player count alerts][crate::commands::servers::online_threshold]
+/// per guild
+pub const MAX_PLAYER_ALERTS: usize = 10;
+/// Highest player count a [player count alert][crate::commands::servers::online_threshold]
+/// can be set to
+pub const MAX_PLAYER_ALERT_THRESHOLD: u32 = 100000;
+/// Longest [temporary admin grant][crate::commands::admin::admin_grant], in
+/// minutes
+pub const MAX_ADMIN_GRANT_MINUTES: u32 = 7 * 24 * 60;
+
+/// Number of recent messages read by
+/// [`!bug discussion`][crate::commands::bug_reports::bug_discussion], the most
+/// Discord returns in one request
+pub const BUG_DISCUSSION_MESSAGES: u64 = 100;
+/// Delay between two checked links in
+/// [`!bug audit links`][crate::commands::bug_reports::audit_links], to stay under the rate limits
+pub const LINK_AUDIT_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
 
 /// Bit filter for colours
 pub const BIT_FILTER_24BITS: u32 = !(!0 << 24);
@@ -48,6 +141,10 @@ pub const BOT_ICON: &str =
 /// Termite icon for bug reports
 pub const TERMITE_IMAGE: &str =
     "https://media.discordapp.net/attachments/781837314975989772/839479742457839646/termite.png";
+/// Default author name of the bugtracker embeds, see [`Branding`][crate::database::settings::Branding]
+pub const BUGTRACKER_NAME: &str = "Eras of Arda Bugtracker";
+/// Default prefix of the bug ids, e.g. `EoA-12`
+pub const DEFAULT_BUG_PREFIX: &str = "EoA";
 /// Forge logo for the !forge command
 pub const FORGE_ICON: &str =
     "https://pbs.twimg.com/profile_images/778706890914095109/fhMDH9o6_400x400.jpg";
@@ -90,6 +187,30 @@ pub const PAYPAL_LINK_EUROS: &str =
 pub const CURSE_API: &str = "https://api.curseforge.com/v1/mods/";
 /// A Minecraft server [public API](https://api.mcsrvstat.us/) for the [`!online`][crate::commands::servers::online] command
 pub const MINECRAFT_API: &str = "https://api.mcsrvstat.us/2/";
+/// Timeout for a single [`MINECRAFT_API`] query
+pub const MINECRAFT_API_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// Timeout for a legacy UDP [query][crate::api::minecraft::legacy_query] to a Minecraft server
+pub const LEGACY_QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+/// Player profile pages, linked in detailed [`!online`][crate::commands::servers::online] mode
+pub const NAMEMC_PROFILE: &str = "https://namemc.com/profile/";
+/// Server favicons, shown as the thumbnail of the server status embeds
+pub const FAVICON_API: &str = "https://eu.mc-api.net/v3/server/favicon/";
+/// Maximum number of embed fields used for the player list in detailed
+/// [`!online`][crate::commands::servers::online] mode
+pub const MAX_PLAYER_LIST_FIELDS: usize = 5;
+/// How long the threads holding long [`!online`][crate::commands::servers::online]
+/// player lists are kept, in guilds that delete them
+pub const PLAYER_THREAD_LIFETIME: std::time::Duration = std::time::Duration::from_secs(600);
+/// How long [`!ip`][crate::commands::servers::server_ip] waits for the server
+/// status before showing the IP alone
+pub const IP_STATUS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+/// Maximum number of concurrent status queries for the
+/// [`!serverlist`][crate::commands::servers::serverlist] command. Lists of up
+/// to this many servers are queried all at once, so they take as long as the
+/// slowest query.
+pub const MAX_CONCURRENT_STATUS_QUERIES: usize = 10;
+/// Maximum number of embeds Discord accepts in a message
+pub const MAX_EMBEDS_PER_MESSAGE: usize = 10;
 /// Google API for custom google search
 pub const GOOGLE_API: &str = "https://www.googleapis.com/customsearch/v1?";
 
@@ -110,6 +231,8 @@ pub const TABLE_USER_BLACKLIST: &str = "user_blacklist";
 pub const TABLE_CHANNEL_BLACKLIST: &str = "channel_blacklist";
 /// SQL table name for Minecraft [servers IPs][crate::database::config]
 pub const TABLE_MC_SERVER_IP: &str = "mc_server_ip";
+/// SQL table name for named Minecraft [servers][crate::database::config]
+pub const TABLE_MC_SERVER_LIST: &str = "mc_server_list";
 /// SQL table name for [custom commands][crate::database::custom_commands]
 pub const TABLE_CUSTOM_COMMANDS: &str = "custom_commands";
 /// SQL table name for [bug reports][crate::database::bug_reports]
@@ -118,15 +241,34 @@ pub const TABLE_BUG_REPORTS: &str = "bug_reports";
 pub const TABLE_BUG_REPORTS_LINKS: &str = "bug_reports__links";
 /// SQL table name for [bug report notifications][crate::database::bug_reports]
 pub const TABLE_BUG_REPORTS_NOTIFICATIONS: &str = "bug_reports__notifications";
+/// SQL table name for [bug report status history][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_HISTORY: &str = "bug_reports__history";
+/// SQL table name for [bug report watchers][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_WATCHERS: &str = "bug_reports__watchers";
+/// SQL table name for [bug report labels][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_LABELS: &str = "bug_reports__labels";
+/// SQL table name for [bug report webhooks][crate::database::bug_reports]
+pub const TABLE_BUG_REPORTS_WEBHOOKS: &str = "bug_reports__webhooks";
+/// SQL table name for [archived bug reports][crate::database::bug_reports::archive_bugs]
+pub const TABLE_BUG_REPORTS_ARCHIVE: &str = "bug_reports__archive";
+/// SQL table name for the channels fed the bugs of a label, see
+/// [`!bug subscribe`][crate::commands::bug_reports::subscribe]
+pub const TABLE_BUG_REPORTS_LABEL_SUBSCRIPTIONS: &str = "bug_reports__label_subscriptions";
 /// SQL table name for [role handling][crate::database::roles]
 pub const TABLE_ROLES: &str = "roles";
 /// SQL table name for [role aliases handling][crate::database::roles]
 pub const TABLE_ROLES_ALIASES: &str = "roles__aliases";
 /// SQL table name for guild list and database cleanup
 pub const TABLE_LIST_GUILDS: &str = "list_guilds";
+/// SQL table name for per-guild [settings][crate::database::settings]
+pub const TABLE_GUILD_SETTINGS: &str = "guild_settings";
+/// SQL table name for the Minecraft [server status log][crate::database::config]
+pub const TABLE_SERVER_STATUS_LOG: &str = "server_status_log";
+/// SQL table name for the [player count alerts][crate::commands::servers::online_threshold]
+pub const TABLE_MC_PLAYER_ALERTS: &str = "mc_player_alerts";
 
 /// Reserved command names that cannot be used as [custom commands][crate::commands::custom_commands]
-pub const RESERVED_NAMES: [&str; 52] = [
+pub const RESERVED_NAMES: [&str; 68] = [
     "legacy",
     "renewed",
     "download",
@@ -145,6 +287,13 @@ pub const RESERVED_NAMES: [&str; 52] = [
     "admins",
     "blacklist",
     "announce",
+    "autodelete",
+    "locale",
+    "language",
+    "settings",
+    "setting",
+    "perms",
+    "permissions",
     "floppadmin",
     "guilds",
     "listguilds",
@@ -159,6 +308,10 @@ pub const RESERVED_NAMES: [&str; 52] = [
     "online",
     "ip",
     "server_ip",
+    "serverlist",
+    "servers",
+    "uptime",
+    "compare",
     "wiki",
     "tolkien",
     "tolkiengateway",
@@ -169,6 +322,7 @@ pub const RESERVED_NAMES: [&str; 52] = [
     "bugs",
     "buglist",
     "resolve",
+    "digest",
     "clean_database",
     "user",
     "user_info",
@@ -179,4 +333,8 @@ pub const RESERVED_NAMES: [&str; 52] = [
     "ig",
     "q&a",
     "shutdown",
+    "maintenance",
+    "presence",
+    "errorlog",
+    "doctor",
 ];
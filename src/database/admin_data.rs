@@ -1,17 +1,22 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
 use mysql_async::prelude::*;
 use serenity::client::Context;
 use serenity::framework::standard::CommandResult;
 use serenity::model::prelude::*;
 
 use crate::constants::TABLE_ADMINS;
+use crate::database::utc;
 use crate::get_database_conn;
 
+/// Whether the user is a bot admin, ignoring expired
+/// [temporary grants][grant_admin]
 pub async fn is_admin_function(ctx: &Context, server_id: GuildId, user: UserId) -> Option<bool> {
     let mut conn = get_database_conn!(ctx);
 
     let res = conn
         .query_first(format!(
-            "SELECT EXISTS(SELECT perm_id FROM {} WHERE server_id={} AND user_id={} LIMIT 1)",
+            "SELECT EXISTS(SELECT perm_id FROM {} WHERE server_id={} AND user_id={} \
+AND (expires_at IS NULL OR expires_at > UTC_TIMESTAMP()) LIMIT 1)",
             TABLE_ADMINS, server_id.0, user.0
         ))
         .await
@@ -20,20 +25,58 @@ pub async fn is_admin_function(ctx: &Context, server_id: GuildId, user: UserId)
     res
 }
 
-pub async fn get_admins(ctx: &Context, server_id: GuildId) -> Option<Vec<UserId>> {
+/// Whether the user is a bot admin without a [temporary grant][grant_admin]
+pub async fn is_permanent_admin(ctx: &Context, server_id: GuildId, user: UserId) -> Option<bool> {
     let mut conn = get_database_conn!(ctx);
 
+    conn.exec_first(
+        format!(
+            "SELECT EXISTS(SELECT perm_id FROM {} WHERE server_id = :server_id \
+AND user_id = :user_id AND expires_at IS NULL LIMIT 1)",
+            TABLE_ADMINS
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "user_id" => user.0,
+        },
+    )
+    .await
+    .ok()?
+}
+
+/// Bot admins of a guild, with the expiry of the temporary ones. The expired
+/// grants are deleted first.
+pub async fn get_admins(
+    ctx: &Context,
+    server_id: GuildId,
+) -> Option<Vec<(UserId, Option<DateTime<Utc>>)>> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "DELETE FROM {} WHERE server_id = :server_id AND expires_at <= UTC_TIMESTAMP()",
+            TABLE_ADMINS
+        ),
+        params! {
+            "server_id" => server_id.0
+        },
+    )
+    .await
+    .ok()?;
+
     let res = conn
         .exec_map(
             format!(
-                "SELECT user_id FROM {} WHERE server_id=:server_id",
+                "SELECT user_id, expires_at FROM {} WHERE server_id=:server_id",
                 TABLE_ADMINS
             )
             .as_str(),
             params! {
                 "server_id" => server_id.0
             },
-            UserId,
+            |(user_id, expires_at): (u64, Option<NaiveDateTime>)| {
+                (UserId(user_id), expires_at.map(utc))
+            },
         )
         .await
         .ok()?;
@@ -87,3 +130,66 @@ pub async fn remove_admin(ctx: &Context, server_id: GuildId, user_id: UserId) ->
 
     Ok(())
 }
+
+/// Makes a user bot admin until `until`, replacing their previous temporary
+/// grant if any
+pub async fn grant_admin(
+    ctx: &Context,
+    server_id: GuildId,
+    user_id: UserId,
+    until: NaiveDateTime,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "DELETE FROM {} WHERE server_id = :server_id AND user_id = :user_id \
+AND expires_at IS NOT NULL",
+            TABLE_ADMINS
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "user_id" => user_id.0,
+        },
+    )
+    .await?;
+
+    conn.exec_drop(
+        format!(
+            "INSERT INTO {} (server_id, user_id, expires_at) VALUES (:server_id, :user_id, :until)",
+            TABLE_ADMINS
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "user_id" => user_id.0,
+            "until" => until,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Ends the temporary grant of a user early. Returns whether they had one.
+pub async fn revoke_admin(
+    ctx: &Context,
+    server_id: GuildId,
+    user_id: UserId,
+) -> CommandResult<bool> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "DELETE FROM {} WHERE server_id = :server_id AND user_id = :user_id \
+AND expires_at > UTC_TIMESTAMP()",
+            TABLE_ADMINS
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "user_id" => user_id.0,
+        },
+    )
+    .await?;
+
+    Ok(conn.affected_rows() > 0)
+}
@@ -1,23 +1,69 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
 use const_format::formatcp;
 use mysql_async::prelude::*;
+use mysql_async::Row;
+use serde::Serialize;
 use serenity::client::Context;
 use serenity::framework::standard::{CommandError, CommandResult};
 use serenity::model::prelude::*;
+use serenity::prelude::TypeMapKey;
 use serenity::utils::Colour;
+use std::collections::HashMap;
 
 use crate::constants::{
-    TABLE_BUG_REPORTS, TABLE_BUG_REPORTS_LINKS, TABLE_BUG_REPORTS_NOTIFICATIONS,
+    BOT_ID, MAX_LABEL_LENGTH, TABLE_BUG_REPORTS, TABLE_BUG_REPORTS_ARCHIVE,
+    TABLE_BUG_REPORTS_HISTORY, TABLE_BUG_REPORTS_LABELS, TABLE_BUG_REPORTS_LABEL_SUBSCRIPTIONS,
+    TABLE_BUG_REPORTS_LINKS, TABLE_BUG_REPORTS_NOTIFICATIONS, TABLE_BUG_REPORTS_WATCHERS,
+    TABLE_BUG_REPORTS_WEBHOOKS,
 };
-use crate::get_database_conn;
+use crate::database::settings::{
+    get_all_settings, get_bug_prefix, get_setting, status_colour, status_marker, BugPrefix,
+};
+use crate::database::{config::get_prefix, utc, DbError};
+use crate::shutdown::get_shutdown;
+use crate::utils::{discord_timestamp, edit_distance};
+use crate::{get_database_conn, try_get_database_conn};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BugOrder {
     Chronological(bool),
     Priority(bool),
     None,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct ParseOrderError;
+
+impl std::str::FromStr for BugOrder {
+    type Err = ParseOrderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use BugOrder::*;
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "latest" => Chronological(false),
+            "oldest" => Chronological(true),
+            "highest" => Priority(false),
+            "lowest" => Priority(true),
+            "none" => None,
+            _ => return Err(ParseOrderError),
+        })
+    }
+}
+
+impl BugOrder {
+    pub fn as_str(self) -> &'static str {
+        use BugOrder::*;
+
+        match self {
+            Chronological(false) => "latest",
+            Chronological(true) => "oldest",
+            Priority(false) => "highest",
+            Priority(true) => "lowest",
+            None => "none",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BugCategory {
     FaLegacy,
@@ -38,6 +84,13 @@ impl std::fmt::Display for BugCategory {
     }
 }
 
+/// Serialized as stored in the database
+impl Serialize for BugCategory {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ParseCategoryError;
 
@@ -69,6 +122,20 @@ impl BugCategory {
     }
 }
 
+impl BugCategory {
+    /// The same age in the other edition (legacy or renewed)
+    pub fn other_edition(self) -> Self {
+        use BugCategory::*;
+
+        match self {
+            FaLegacy => FaRenewed,
+            FaRenewed => FaLegacy,
+            SaLegacy => SaRenewed,
+            SaRenewed => SaLegacy,
+        }
+    }
+}
+
 impl Default for BugCategory {
     fn default() -> BugCategory {
         BugCategory::FaRenewed
@@ -97,6 +164,13 @@ impl std::fmt::Display for BugStatus {
     }
 }
 
+/// Serialized as stored in the database
+impl Serialize for BugStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ParseStatusError;
 
@@ -104,13 +178,13 @@ impl std::str::FromStr for BugStatus {
     type Err = ParseStatusError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s.to_lowercase().as_str() {
-            "resolved" => Resolved,
-            "low" => Low,
-            "medium" => Medium,
-            "high" => High,
-            "critical" => Critical,
-            "closed" => Closed,
+        Ok(match s.trim().to_lowercase().as_str() {
+            "resolved" | "resolve" | "fixed" | "fix" | "done" => Resolved,
+            "low" | "lo" => Low,
+            "medium" | "med" | "mid" => Medium,
+            "high" | "hi" => High,
+            "critical" | "crit" => Critical,
+            "closed" | "close" | "wontfix" | "won'tfix" | "invalid" => Closed,
             "forgevanilla" | "forge" | "vanilla" => ForgeVanilla,
             _ => return Err(Self::Err {}),
         })
@@ -124,6 +198,50 @@ impl std::default::Default for BugStatus {
 }
 
 impl BugStatus {
+    /// Every word accepted by the [`FromStr`][std::str::FromStr] implementation
+    pub const KEYWORDS: [&'static str; 22] = [
+        "resolved",
+        "resolve",
+        "fixed",
+        "fix",
+        "done",
+        "low",
+        "lo",
+        "medium",
+        "med",
+        "mid",
+        "high",
+        "hi",
+        "critical",
+        "crit",
+        "closed",
+        "close",
+        "wontfix",
+        "won'tfix",
+        "invalid",
+        "forgevanilla",
+        "forge",
+        "vanilla",
+    ];
+
+    /// Whether the bug still needs work: not resolved, closed or left to
+    /// Forge or Vanilla
+    pub fn is_open(self) -> bool {
+        !matches!(self, Resolved | Closed | ForgeVanilla)
+    }
+
+    /// The status keyword one typo away from `word`, for "did you mean"
+    /// replies. Words shorter than 3 letters are too ambiguous to guess.
+    pub fn suggest(word: &str) -> Option<&'static str> {
+        let word = word.to_lowercase();
+        if word.chars().count() < 3 {
+            return None;
+        }
+        Self::KEYWORDS
+            .into_iter()
+            .find(|keyword| edit_distance(&word, keyword) == 1)
+    }
+
     #[inline]
     pub const fn as_str(self) -> &'static str {
         match self {
@@ -150,6 +268,22 @@ impl BugStatus {
         }
     }
 
+    /// Colour of the status in the given guild, using the guild's
+    /// [override][crate::database::settings::status_colour] when set.
+    pub async fn guild_colour(self, ctx: &Context, guild_id: Option<GuildId>) -> Colour {
+        if let Some(guild_id) = guild_id {
+            if let Some(colour) = get_setting(ctx, guild_id, &status_colour(self))
+                .await
+                .and_then(|colour| u32::from_str_radix(&colour, 16).ok())
+            {
+                return Colour(colour);
+            }
+        }
+        self.colour()
+    }
+
+    /// Default marker of the status, see [`StatusMarkers`] for the guild's
+    /// overrides
     pub const fn marker(self) -> &'static str {
         match self {
             Resolved => ":green_circle:",
@@ -175,29 +309,106 @@ impl BugStatus {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Markers of the statuses in a guild, using the guild's
+/// [overrides][crate::database::settings::status_marker] when set, so that
+/// guilds without the default emoji can use their own
+#[derive(Debug, Clone, Default)]
+pub struct StatusMarkers(HashMap<String, String>);
+
+impl StatusMarkers {
+    pub async fn of(ctx: &Context, guild_id: Option<GuildId>) -> Self {
+        match guild_id {
+            Some(guild_id) => Self(
+                get_all_settings(ctx, guild_id)
+                    .await
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|(key, _)| key.starts_with("status_marker."))
+                    .collect(),
+            ),
+            None => Self::default(),
+        }
+    }
+
+    pub fn get(&self, status: BugStatus) -> &str {
+        self.0
+            .get(&status_marker(status))
+            .map_or(status.marker(), String::as_str)
+    }
+}
+
+/// A link of a bug. Links are shown by their position in the bug, so that
+/// the numbers stay contiguous when links are removed, and stored by their
+/// stable id.
+#[derive(Debug, Clone, Serialize)]
 pub struct BugLink {
     pub id: u64,
+    /// Position of the link in its bug, starting at 1
+    pub num: u64,
     pub url: String,
     pub title: String,
 }
 
 impl std::fmt::Display for BugLink {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "[{}]({}) (#{})", self.title, self.url, self.id)
+        write!(f, "[{}]({}) (#{})", self.title, self.url, self.num)
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BugReport {
     pub bug_id: u64,
+    pub guild_id: GuildId,
     pub channel_id: ChannelId,
     pub message_id: MessageId,
     pub title: String,
     pub status: BugStatus,
     pub timestamp: DateTime<Utc>,
+    pub status_since: DateTime<Utc>,
     pub category: BugCategory,
     pub links: Vec<BugLink>,
+    /// Freeform [labels][normalize_label], sorted
+    pub labels: Vec<String>,
+    /// Bug this one was [merged][merge_bugs] into
+    pub merged_into: Option<u64>,
+    /// Saved content of the original message, shown if it is deleted
+    pub saved_message: Option<SavedMessage>,
+    /// The bug is hidden from the default bug lists until then
+    pub snoozed_until: Option<DateTime<Utc>>,
+    /// The bug was moved to the [archive][archive_bugs], it is read-only
+    pub archived: bool,
+    /// Description written when tracking the bug, shown instead of the
+    /// content of the original message
+    pub summary: Option<String>,
+}
+
+impl BugReport {
+    /// Link to the original message of the bug, used to link bugs together
+    pub fn message_url(&self) -> String {
+        format!(
+            "https://discord.com/channels/{}/{}/{}",
+            self.guild_id, self.channel_id, self.message_id
+        )
+    }
+}
+
+/// Content of the original message of a bug, saved when the bug is tracked
+/// and by [`!bug refresh`][crate::commands::bug_reports::bug_refresh]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SavedMessage {
+    pub author: String,
+    pub content: String,
+    pub attachment_url: Option<String>,
+}
+
+impl SavedMessage {
+    pub fn of(message: &Message) -> Self {
+        Self {
+            author: message.author.name.clone(),
+            content: message.content.clone(),
+            attachment_url: message.attachments.get(0).map(|a| a.url.clone()),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -209,24 +420,22 @@ pub struct PartialBugReport {
     pub category: BugCategory,
 }
 
-impl std::fmt::Display for PartialBugReport {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl PartialBugReport {
+    /// Formats the bug as a line of a bug list
+    pub fn list_entry(&self, prefix: &BugPrefix) -> String {
         let duration = Utc::now().signed_duration_since(self.timestamp).num_days();
-        let format_str = match duration {
-            0..=6 => "<t:%s:R>",
-            _ => "<t:%s:d>",
+        let style = match duration {
+            0..=6 => 'R',
+            _ => 'd',
         };
-        write!(
-            f,
-            "EoA-{} — {}  ({})",
-            self.bug_id,
+        format!(
+            "{} — {}  ({})",
+            prefix.id(self.bug_id),
             self.title,
-            self.timestamp.format(format_str)
+            discord_timestamp(&self.timestamp, style)
         )
     }
-}
 
-impl PartialBugReport {
     pub fn new(
         bug_id: u64,
         title: String,
@@ -238,274 +447,2004 @@ impl PartialBugReport {
             bug_id,
             title,
             status: status.parse().unwrap_or_default(),
-            timestamp: DateTime::from_utc(timestamp, Utc),
+            timestamp: utc(timestamp),
             category,
         })
     }
 }
 
-pub async fn get_bug_from_id(ctx: &Context, bug_id: u64) -> Result<BugReport, CommandError> {
-    let mut conn = get_database_conn!(ctx);
+/// Columns read by [`get_bug_from_id`], from the bugs or the archive
+const BUG_COLUMNS: &str =
+    "guild_id, channel_id, message_id, title, status, timestamp, status_since, \
+category, merged_into, saved_author, saved_content, saved_attachment, snoozed_until, summary";
+
+/// Keeps the rows of a table keyed by `bug_id` whose bug belongs to
+/// `:guild_id`, archived or not
+const IN_GUILD: &str = formatcp!(
+    "bug_id IN (SELECT bug_id FROM {0} WHERE guild_id = :guild_id \
+UNION SELECT bug_id FROM {1} WHERE guild_id = :guild_id)",
+    TABLE_BUG_REPORTS,
+    TABLE_BUG_REPORTS_ARCHIVE
+);
+
+/// Fails unless the bug is one of the active bugs of the guild: with
+/// [`DbError::Archived`] if it was archived, since archived bugs can't be
+/// changed, and with [`DbError::NotFound`] otherwise
+async fn check_guild_bug(
+    conn: &mut impl Queryable,
+    bug_id: u64,
+    guild_id: GuildId,
+) -> Result<(), DbError> {
+    let archived: Option<bool> = conn
+        .exec_first(
+            formatcp!(
+                "SELECT FALSE FROM {} WHERE bug_id = :bug_id AND guild_id = :guild_id \
+UNION ALL SELECT TRUE FROM {} WHERE bug_id = :bug_id AND guild_id = :guild_id",
+                TABLE_BUG_REPORTS,
+                TABLE_BUG_REPORTS_ARCHIVE
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "guild_id" => guild_id.0
+            },
+        )
+        .await?;
+
+    match archived {
+        Some(false) => Ok(()),
+        Some(true) => Err(DbError::Archived),
+        None => Err(DbError::NotFound),
+    }
+}
 
-    let (channel_id, message_id, title, status, timestamp, category): (
-        u64,
-        u64,
-        String,
-        String,
-        NaiveDateTime,
-        String,
-    ) = conn
+/// Reads a column of a [`BUG_COLUMNS`] row, which has too many columns to be
+/// read as a tuple. Fails if the column is missing or has another type.
+fn take_column<T: FromValue>(row: &mut Row, name: &str) -> Result<T, DbError> {
+    match row.take_opt(name) {
+        Some(Ok(value)) => Ok(value),
+        Some(Err(_)) => Err(DbError::Other(mysql_async::Error::Other(
+            format!("Unexpected type for the {} column", name).into(),
+        ))),
+        None => Err(DbError::Other(mysql_async::Error::Other(
+            format!("Missing {} column", name).into(),
+        ))),
+    }
+}
+
+/// Returns a bug of the guild, looking into the [archive][archive_bugs] if it
+/// is not among the active bugs
+pub async fn get_bug_from_id(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+) -> Result<BugReport, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    let row: Option<Row> = conn
         .exec_first(
             formatcp!(
-                "SELECT channel_id, message_id, title, status, timestamp, category \
-FROM {} WHERE bug_id = :bug_id",
+                "SELECT {} FROM {} WHERE bug_id = :bug_id AND guild_id = :guild_id",
+                BUG_COLUMNS,
                 TABLE_BUG_REPORTS
             ),
             params! {
-                "bug_id" => bug_id
+                "bug_id" => bug_id,
+                "guild_id" => guild_id.0
             },
         )
-        .await?
-        .ok_or_else(|| CommandError::from("Bug report does not exist!"))?;
+        .await?;
+    let (mut row, archived) = match row {
+        Some(row) => (row, false),
+        None => {
+            let row: Row = conn
+                .exec_first(
+                    formatcp!(
+                        "SELECT {} FROM {} WHERE bug_id = :bug_id AND guild_id = :guild_id",
+                        BUG_COLUMNS,
+                        TABLE_BUG_REPORTS_ARCHIVE
+                    ),
+                    params! {
+                        "bug_id" => bug_id,
+                        "guild_id" => guild_id.0
+                    },
+                )
+                .await?
+                .ok_or(DbError::NotFound)?;
+            (row, true)
+        }
+    };
+    let saved_author: Option<String> = take_column(&mut row, "saved_author")?;
+    let saved_content: Option<String> = take_column(&mut row, "saved_content")?;
+    let saved_attachment: Option<String> = take_column(&mut row, "saved_attachment")?;
 
-    let links: Vec<BugLink> = conn
+    let mut links: Vec<BugLink> = conn
         .exec_map(
             formatcp!(
-                "SELECT link_id, link_url, link_title FROM {} WHERE bug_id = :bug_id",
+                "SELECT link_id, link_url, link_title FROM {} WHERE bug_id = :bug_id ORDER BY link_id",
                 TABLE_BUG_REPORTS_LINKS
             ),
             params! {
                 "bug_id" => bug_id
             },
-            |(id, url, title)| BugLink { id, url, title },
+            |(id, url, title)| BugLink {
+                id,
+                num: 0,
+                url,
+                title,
+            },
+        )
+        .await?;
+    for (link, num) in links.iter_mut().zip(1..) {
+        link.num = num;
+    }
+
+    let labels: Vec<String> = conn
+        .exec(
+            formatcp!(
+                "SELECT label FROM {} WHERE bug_id = :bug_id ORDER BY label",
+                TABLE_BUG_REPORTS_LABELS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
         )
         .await?;
 
     Ok(BugReport {
         bug_id,
-        channel_id: ChannelId(channel_id),
-        message_id: MessageId(message_id),
-        title,
-        status: status
+        guild_id: GuildId(take_column(&mut row, "guild_id")?),
+        channel_id: ChannelId(take_column(&mut row, "channel_id")?),
+        message_id: MessageId(take_column(&mut row, "message_id")?),
+        title: take_column(&mut row, "title")?,
+        status: take_column::<String>(&mut row, "status")?
             .parse()
             .expect("Expected a valid bug status from the database"),
-        timestamp: DateTime::from_utc(timestamp, Utc),
-        category: category
+        timestamp: utc(take_column(&mut row, "timestamp")?),
+        status_since: utc(take_column(&mut row, "status_since")?),
+        category: take_column::<String>(&mut row, "category")?
             .parse()
             .expect("Expected a valid bug category from the database"),
         links,
+        labels,
+        merged_into: take_column(&mut row, "merged_into")?,
+        saved_message: saved_author.map(|author| SavedMessage {
+            author,
+            content: saved_content.unwrap_or_default(),
+            attachment_url: saved_attachment,
+        }),
+        snoozed_until: take_column::<Option<NaiveDateTime>>(&mut row, "snoozed_until")?.map(utc),
+        archived,
+        summary: take_column::<Option<String>>(&mut row, "summary")?
+            .filter(|summary| !summary.is_empty()),
     })
 }
 
 pub async fn add_bug_report(
     ctx: &Context,
+    guild_id: GuildId,
     msg: &Message,
     title: String,
+    summary: Option<&str>,
     status: BugStatus,
     category: BugCategory,
 ) -> Result<u64, CommandError> {
     let mut conn = get_database_conn!(ctx);
 
+    let bug_id = insert_bug_report(
+        &mut conn,
+        guild_id,
+        msg,
+        Some(SavedMessage::of(msg)),
+        &title,
+        summary,
+        status,
+        category,
+        Some(msg.author.id),
+    )
+    .await?;
+
+    if let Err(e) = msg.react(ctx, status.reaction()).await {
+        println!("Could not add reaction to bug report: {}", e);
+    }
+
+    Ok(bug_id)
+}
+
+/// Adds a bug pointing to `msg`. Without `saved_message`, nothing is saved
+/// until `!bug refresh`, as for the bugs tracked before messages were saved.
+async fn insert_bug_report(
+    conn: &mut impl Queryable,
+    guild_id: GuildId,
+    msg: &Message,
+    saved_message: Option<SavedMessage>,
+    title: &str,
+    summary: Option<&str>,
+    status: BugStatus,
+    category: BugCategory,
+    reporter_id: Option<UserId>,
+) -> Result<u64, DbError> {
+    let has_attachment = saved_message
+        .as_ref()
+        .map(|saved_message| saved_message.attachment_url.is_some());
+    let (author, content, attachment) = match saved_message {
+        Some(saved_message) => (
+            Some(saved_message.author),
+            Some(saved_message.content),
+            saved_message.attachment_url,
+        ),
+        None => (None, None, None),
+    };
     conn.exec_drop(
         formatcp!(
-            "INSERT INTO {} (channel_id, message_id, title, status, category) \
-VALUES (:channel_id, :message_id, :title, :status, :category)",
+            "INSERT INTO {} (guild_id, channel_id, message_id, title, status, category, reporter_id, \
+saved_author, saved_content, saved_attachment, has_attachment, summary) VALUES (:guild_id, \
+:channel_id, :message_id, :title, :status, :category, :reporter_id, :author, :content, \
+:attachment, :has_attachment, :summary)",
             TABLE_BUG_REPORTS
         ),
         params! {
+            "guild_id" => guild_id.0,
             "channel_id" => msg.channel_id.0,
             "message_id" => msg.id.0,
             "title" => title,
             "status" => status.as_str(),
             "category" => category.as_str(),
+            "reporter_id" => reporter_id.map(|reporter_id| reporter_id.0),
+            "author" => author,
+            "content" => content,
+            "has_attachment" => has_attachment,
+            "attachment" => attachment,
+            "summary" => summary,
         },
     )
     .await?;
 
-    if let Err(e) = msg.react(ctx, status.reaction()).await {
-        println!("Could not add reaction to bug report: {}", e);
-    }
-
-    conn.query_first(formatcp!("SELECT MAX(bug_id) FROM {}", TABLE_BUG_REPORTS))
+    let bug_id = conn
+        .query_first(formatcp!("SELECT MAX(bug_id) FROM {}", TABLE_BUG_REPORTS))
         .await?
-        .ok_or_else(|| CommandError::from("Could not get newest bug id!"))
+        .ok_or(DbError::NotFound)?;
+
+    add_history(conn, bug_id, HistoryChange::Reported(status, title)).await;
+
+    Ok(bug_id)
 }
 
-pub async fn get_bug_list(
+/// A bug read from an import file, see [`import_bugs`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedBug {
+    pub title: String,
+    pub status: BugStatus,
+    pub category: BugCategory,
+    pub link: Option<String>,
+}
+
+/// Creates the bugs in a single transaction, so that either all of them or
+/// none are added. They all point to `msg`, without a saved message or a
+/// reporter: neither is the author of their report. Returns the new bug ids.
+pub async fn import_bugs(
     ctx: &Context,
-    status: Option<BugStatus>,
-    limit: u32,
-    display_order: BugOrder,
-    category: Option<BugCategory>,
-    page: u32,
-) -> Option<(Vec<PartialBugReport>, u32)> {
-    let mut conn = get_database_conn!(ctx);
+    guild_id: GuildId,
+    msg: &Message,
+    bugs: &[ImportedBug],
+) -> Result<Vec<u64>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+    let mut tx = conn.start_transaction(Default::default()).await?;
+
+    let mut bug_ids = Vec::with_capacity(bugs.len());
+    for bug in bugs {
+        let bug_id = insert_bug_report(
+            &mut tx,
+            guild_id,
+            msg,
+            None,
+            &bug.title,
+            None,
+            bug.status,
+            bug.category,
+            None,
+        )
+        .await?;
+        if let Some(link) = &bug.link {
+            tx.exec_drop(
+                formatcp!(
+                    "INSERT INTO {} (bug_id, link_url, link_title) VALUES (:bug_id, :link_url, :link_title)",
+                    TABLE_BUG_REPORTS_LINKS
+                ),
+                params! {
+                    "bug_id" => bug_id,
+                    "link_url" => link,
+                    "link_title" => "Original report",
+                },
+            )
+            .await?;
+        }
+        bug_ids.push(bug_id);
+    }
 
-    println!("getting total...");
+    tx.commit().await?;
 
-    let total: u32 = conn
-        .query_first(format!(
-            "SELECT COUNT(bug_id) FROM {} WHERE {} {category}",
-            TABLE_BUG_REPORTS,
-            if let Some(status) = status {
-                format!("status = '{}'", status.as_str())
-            } else {
-                "status != 'resolved' AND status != 'closed' AND status != 'forgevanilla'".into()
-            },
-            category = if let Some(c) = category {
-                format!("AND category = '{}'", c.as_str())
-            } else {
-                "".into()
+    Ok(bug_ids)
+}
+
+/// A change recorded in the bug history
+enum HistoryChange<'a> {
+    /// The bug was just reported with this status and title
+    Reported(BugStatus, &'a str),
+    /// The status changed from the first to the second value, by a user if known
+    Status(BugStatus, BugStatus, Option<UserId>),
+    /// The title changed from the first to the second value
+    Title(&'a str, &'a str),
+}
+
+async fn add_history(conn: &mut impl Queryable, bug_id: u64, change: HistoryChange<'_>) {
+    let (old_status, new_status, old_title, new_title, changed_by) = match change {
+        HistoryChange::Reported(status, title) => (None, Some(status), None, Some(title), None),
+        HistoryChange::Status(old, new, changed_by) => {
+            (Some(old), Some(new), None, None, changed_by)
+        }
+        HistoryChange::Title(old, new) => (None, None, Some(old), Some(new), None),
+    };
+
+    if let Err(e) = conn
+        .exec_drop(
+            formatcp!(
+                "INSERT INTO {} (bug_id, old_status, new_status, old_title, new_title, changed_by) \
+VALUES (:bug_id, :old_status, :new_status, :old_title, :new_title, :changed_by)",
+                TABLE_BUG_REPORTS_HISTORY
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "old_status" => old_status.map(BugStatus::as_str),
+                "new_status" => new_status.map(BugStatus::as_str),
+                "old_title" => old_title,
+                "new_title" => new_title,
+                "changed_by" => changed_by.map(|user_id| user_id.0),
             },
-        ))
+        )
         .await
-        .ok()??;
+    {
+        println!(
+            "=== ERROR ===\nCould not add history for bug {}: {}\n=== END ===",
+            bug_id, e
+        );
+    }
+}
 
-    println!("total: {}", total);
+/// A status change from the bug history, see [`get_recent_status_changes`]
+#[derive(Debug, Clone)]
+pub struct StatusChange {
+    pub bug_id: u64,
+    /// `None` when the bug was just reported
+    pub old_status: Option<BugStatus>,
+    pub new_status: BugStatus,
+    pub changed_by: Option<UserId>,
+    pub timestamp: DateTime<Utc>,
+}
 
-    conn.exec_map(
-        format!(
-            "SELECT bug_id, title, status, timestamp, category FROM {} \
-WHERE {} {category} ORDER BY {ordering} LIMIT :limit OFFSET :offset",
-            TABLE_BUG_REPORTS,
-            if let Some(status) = status {
-                format!("status = '{}'", status.as_str())
-            } else {
-                "status != 'resolved' AND status != 'closed' AND status != 'forgevanilla'".into()
-            },
-            category = if let Some(c) = category {
-                format!("AND category = '{}'", c.as_str())
-            } else {
-                "".into()
-            },
-            ordering = match display_order {
-                BugOrder::Chronological(false) | BugOrder::None => "timestamp DESC",
-                BugOrder::Chronological(true) => "timestamp ASC",
-                BugOrder::Priority(false) => "status DESC, timestamp DESC",
-                BugOrder::Priority(true) => "status ASC, timestamp DESC",
+/// Returns the latest status changes across the bugs of a guild, newest first
+pub async fn get_recent_status_changes(
+    ctx: &Context,
+    guild_id: GuildId,
+    limit: u32,
+) -> Result<Vec<StatusChange>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_map(
+            formatcp!(
+                "SELECT bug_id, old_status, new_status, changed_by, timestamp FROM {} \
+WHERE new_status IS NOT NULL AND {} ORDER BY timestamp DESC, history_id DESC LIMIT :limit",
+                TABLE_BUG_REPORTS_HISTORY,
+                IN_GUILD
+            ),
+            params! {
+                "guild_id" => guild_id.0,
+                "limit" => limit,
             },
-        ),
-        params! {
-            "limit" => limit,
-            "offset" => limit * page
-        },
-        |(bug_id, title, status, timestamp, category): (
-            u64,
-            String,
-            String,
-            NaiveDateTime,
-            String,
-        )| {
-            PartialBugReport::new(
+            |(bug_id, old_status, new_status, changed_by, timestamp): (
+                u64,
+                Option<String>,
+                String,
+                Option<u64>,
+                NaiveDateTime,
+            )| StatusChange {
                 bug_id,
-                title,
-                status
+                old_status: old_status.map(|status| {
+                    status
+                        .parse()
+                        .expect("Expected a valid bug status from the database")
+                }),
+                new_status: new_status
                     .parse()
                     .expect("Expected a valid bug status from the database"),
-                timestamp,
-                category
-                    .parse()
-                    .expect("Expected a valid bug category from the database"),
-            )
-        },
-    )
-    .await
-    .ok()
-    .map(|v| v.iter().filter_map(|x| x.clone()).collect())
-    .map(|v| (v, total))
+                changed_by: changed_by.map(UserId),
+                timestamp: utc(timestamp),
+            },
+        )
+        .await?)
 }
 
-pub async fn change_bug_status(
+#[derive(Debug, Clone)]
+pub struct BugSnapshot {
+    pub status: BugStatus,
+    pub title: String,
+}
+
+/// Reconstructs the status and title of a bug at the given time from its
+/// history. Returns `None` if the bug didn't exist yet.
+pub async fn get_bug_snapshot(
     ctx: &Context,
-    bug_id: u64,
-    new_status: BugStatus,
-) -> Result<BugStatus, CommandError> {
+    bug: &BugReport,
+    at: DateTime<Utc>,
+) -> Result<Option<BugSnapshot>, CommandError> {
+    if bug.timestamp > at {
+        return Ok(None);
+    }
+
     let mut conn = get_database_conn!(ctx);
 
-    let (old_status_string, channel_id, msg_id): (String, u64, u64) = conn
+    // the state at a given time is the old value of the first change after it
+    let status: Option<String> = conn
         .exec_first(
             formatcp!(
-                "SELECT status, channel_id, message_id FROM {} WHERE bug_id = :bug_id LIMIT 1",
-                TABLE_BUG_REPORTS
+                "SELECT old_status FROM {} WHERE bug_id = :bug_id AND old_status IS NOT NULL \
+AND timestamp > :at ORDER BY timestamp ASC, history_id ASC LIMIT 1",
+                TABLE_BUG_REPORTS_HISTORY
             ),
             params! {
-                "bug_id" => bug_id
+                "bug_id" => bug.bug_id,
+                "at" => at.naive_utc(),
             },
         )
-        .await?
-        .ok_or_else(|| CommandError::from("Could not find bug in database"))?;
+        .await?;
 
-    let old_status: BugStatus = old_status_string
-        .parse()
-        .expect("Expected a valid bug status from database!");
+    let title: Option<String> = conn
+        .exec_first(
+            formatcp!(
+                "SELECT old_title FROM {} WHERE bug_id = :bug_id AND old_title IS NOT NULL \
+AND timestamp > :at ORDER BY timestamp ASC, history_id ASC LIMIT 1",
+                TABLE_BUG_REPORTS_HISTORY
+            ),
+            params! {
+                "bug_id" => bug.bug_id,
+                "at" => at.naive_utc(),
+            },
+        )
+        .await?;
+
+    Ok(Some(BugSnapshot {
+        status: status.and_then(|s| s.parse().ok()).unwrap_or(bug.status),
+        title: title.unwrap_or_else(|| bug.title.clone()),
+    }))
+}
+
+pub async fn count_guild_bugs(ctx: &Context, guild_id: GuildId) -> Option<u64> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_first(
+        formatcp!(
+            "SELECT COUNT(bug_id) FROM {} WHERE guild_id = :guild_id",
+            TABLE_BUG_REPORTS
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+        },
+    )
+    .await
+    .ok()?
+}
+
+/// Deletes all bugs of a guild, along with their links, labels and
+/// notifications. Returns the number of deleted bugs.
+pub async fn reset_guild_bugs(ctx: &Context, guild_id: GuildId) -> Result<u64, CommandError> {
+    let mut conn = get_database_conn!(ctx);
+    let mut tx = conn.start_transaction(Default::default()).await?;
+
+    let mut deleted = 0;
+    for bugs_table in [TABLE_BUG_REPORTS, TABLE_BUG_REPORTS_ARCHIVE] {
+        for table in [
+            TABLE_BUG_REPORTS_LINKS,
+            TABLE_BUG_REPORTS_LABELS,
+            TABLE_BUG_REPORTS_NOTIFICATIONS,
+            TABLE_BUG_REPORTS_HISTORY,
+            TABLE_BUG_REPORTS_WATCHERS,
+        ] {
+            tx.exec_drop(
+                format!(
+                    "DELETE FROM {} WHERE bug_id IN (SELECT bug_id FROM {} WHERE guild_id = :guild_id)",
+                    table, bugs_table
+                ),
+                params! {
+                    "guild_id" => guild_id.0,
+                },
+            )
+            .await?;
+        }
+
+        tx.exec_drop(
+            format!("DELETE FROM {} WHERE guild_id = :guild_id", bugs_table),
+            params! {
+                "guild_id" => guild_id.0,
+            },
+        )
+        .await?;
+        deleted += tx.affected_rows();
+    }
+
+    tx.commit().await?;
+
+    Ok(deleted)
+}
+
+/// Moves the resolved and closed bugs of a guild whose status was set before
+/// `before` to the archive table, keeping their ids. Their links, labels and history
+/// stay where they are. Archived bugs are left out of the bug lists and
+/// statistics, but [`get_bug_from_id`] still finds them. Returns the number
+/// of archived bugs.
+pub async fn archive_bugs(
+    ctx: &Context,
+    guild_id: GuildId,
+    before: NaiveDateTime,
+) -> Result<u64, DbError> {
+    const ARCHIVED: &str =
+        "guild_id = :guild_id AND status IN ('resolved', 'closed') AND status_since < :before";
+
+    let mut conn = try_get_database_conn!(ctx);
+    let mut tx = conn.start_transaction(Default::default()).await?;
+
+    // listed, so that a column added to the bugs can't be copied to the
+    // wrong column of the archive
+    const COLUMNS: &str = "bug_id, guild_id, channel_id, message_id, title, status, timestamp, \
+status_since, resolved_at, category, reporter_id, merged_into, saved_author, saved_content, \
+saved_attachment, has_attachment, snoozed_until, summary, fixed_in";
+
+    tx.exec_drop(
+        formatcp!(
+            "INSERT INTO {0} ({1}) SELECT {1} FROM {2} WHERE {3}",
+            TABLE_BUG_REPORTS_ARCHIVE,
+            COLUMNS,
+            TABLE_BUG_REPORTS,
+            ARCHIVED
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "before" => before,
+        },
+    )
+    .await?;
+    let archived = tx.affected_rows();
+
+    tx.exec_drop(
+        formatcp!("DELETE FROM {} WHERE {}", TABLE_BUG_REPORTS, ARCHIVED),
+        params! {
+            "guild_id" => guild_id.0,
+            "before" => before,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(archived)
+}
+
+/// Filters of the bug list. The total and the bugs of a page are counted and
+/// selected with the same `WHERE` clause, so that the total (and with it the
+/// page count and the navigation buttons) always matches the listed bugs.
+///
+/// Pages are cut with `LIMIT` and `OFFSET`, so that large bugtrackers never
+/// load more than one page of bugs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BugListFilter<'a> {
+    /// Only the bugs with this status, or all the open bugs
+    pub status: Option<BugStatus>,
+    pub category: Option<BugCategory>,
+    pub reporter: Option<UserId>,
+    /// A [normalized][normalize_label] label
+    pub label: Option<&'a str>,
+    /// Only the bugs whose original message had an attachment when it was
+    /// saved, or only those without. Bugs reported before messages were saved
+    /// match neither until their copy is [refreshed][update_saved_message].
+    pub attachment: Option<bool>,
+    /// Only the [snoozed][snooze_bug] bugs, instead of hiding them
+    pub snoozed: bool,
+}
+
+const NOT_SNOOZED: &str = "(snoozed_until IS NULL OR snoozed_until <= UTC_TIMESTAMP())";
+const SNOOZED: &str = "snoozed_until > UTC_TIMESTAMP()";
+
+impl BugListFilter<'_> {
+    fn where_clause(self, guild_id: GuildId) -> String {
+        let mut conditions = vec![
+            format!("guild_id = {}", guild_id.0),
+            if let Some(status) = self.status {
+                format!("status = '{}'", status.as_str())
+            } else {
+                "status != 'resolved' AND status != 'closed' AND status != 'forgevanilla'".into()
+            },
+        ];
+        if let Some(c) = self.category {
+            conditions.push(format!("category = '{}'", c.as_str()));
+        }
+        if let Some(user_id) = self.reporter {
+            conditions.push(format!("reporter_id = {}", user_id.0));
+        }
+        if let Some(label) = self.label {
+            // labels are normalized to letters, digits, `-` and `_`
+            conditions.push(format!(
+                "bug_id IN (SELECT bug_id FROM {} WHERE label = '{}')",
+                TABLE_BUG_REPORTS_LABELS, label
+            ));
+        }
+        // the flag is saved with the message, which can be deleted since
+        match self.attachment {
+            Some(true) => conditions.push("has_attachment = TRUE".into()),
+            Some(false) => conditions.push("has_attachment = FALSE".into()),
+            None => (),
+        }
+        conditions.push(if self.snoozed { SNOOZED } else { NOT_SNOOZED }.into());
+        conditions.join(" AND ")
+    }
+
+    fn count_query(self, guild_id: GuildId) -> String {
+        format!(
+            "SELECT COUNT(bug_id) FROM {} WHERE {}",
+            TABLE_BUG_REPORTS,
+            self.where_clause(guild_id)
+        )
+    }
+
+    fn page_query(self, guild_id: GuildId, display_order: BugOrder) -> String {
+        format!(
+            "SELECT bug_id, title, status, timestamp, category FROM {} \
+WHERE {} ORDER BY {} LIMIT :limit OFFSET :offset",
+            TABLE_BUG_REPORTS,
+            self.where_clause(guild_id),
+            match display_order {
+                BugOrder::Chronological(false) | BugOrder::None => "timestamp DESC",
+                BugOrder::Chronological(true) => "timestamp ASC",
+                BugOrder::Priority(false) => "status DESC, timestamp DESC",
+                BugOrder::Priority(true) => "status ASC, timestamp DESC",
+            },
+        )
+    }
+}
+
+pub async fn get_bug_list(
+    ctx: &Context,
+    guild_id: GuildId,
+    filter: BugListFilter<'_>,
+    limit: u32,
+    display_order: BugOrder,
+    page: u32,
+) -> Option<(Vec<PartialBugReport>, u32)> {
+    let mut conn = get_database_conn!(ctx);
+
+    let total: u32 = conn
+        .query_first(filter.count_query(guild_id))
+        .await
+        .ok()??;
+
+    // only the rows of the page are fetched, past the last page there are none
+    let offset = limit.saturating_mul(page);
+    if offset >= total {
+        return Some((Vec::new(), total));
+    }
+
+    conn.exec_map(
+        filter.page_query(guild_id, display_order),
+        params! {
+            "limit" => limit,
+            "offset" => offset
+        },
+        |(bug_id, title, status, timestamp, category): (
+            u64,
+            String,
+            String,
+            NaiveDateTime,
+            String,
+        )| {
+            PartialBugReport::new(
+                bug_id,
+                title,
+                status
+                    .parse()
+                    .expect("Expected a valid bug status from the database"),
+                timestamp,
+                category
+                    .parse()
+                    .expect("Expected a valid bug category from the database"),
+            )
+        },
+    )
+    .await
+    .ok()
+    .map(|v| (v.into_iter().flatten().collect(), total))
+}
+
+pub async fn get_resolved_bugs_since(
+    ctx: &Context,
+    guild_id: GuildId,
+    since: NaiveDateTime,
+    category: Option<BugCategory>,
+) -> Option<Vec<PartialBugReport>> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_map(
+        format!(
+            "SELECT bug_id, title, status, timestamp, category FROM {} \
+WHERE guild_id = :guild_id AND status = 'resolved' AND resolved_at >= :since {category} \
+ORDER BY resolved_at ASC",
+            TABLE_BUG_REPORTS,
+            category = if let Some(c) = category {
+                format!("AND category = '{}'", c.as_str())
+            } else {
+                "".into()
+            },
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "since" => since
+        },
+        |(bug_id, title, status, timestamp, category): (
+            u64,
+            String,
+            String,
+            NaiveDateTime,
+            String,
+        )| {
+            PartialBugReport::new(
+                bug_id,
+                title,
+                status,
+                timestamp,
+                category
+                    .parse()
+                    .expect("Expected a valid bug category from the database"),
+            )
+        },
+    )
+    .await
+    .ok()
+    .map(|v| v.into_iter().flatten().collect())
+}
+
+/// Returns the resolved bugs with a [`fixed_in`][set_fixed_in] version, along
+/// with that version
+pub async fn get_fixed_bugs(
+    ctx: &Context,
+    guild_id: GuildId,
+    category: Option<BugCategory>,
+) -> Result<Vec<(PartialBugReport, String)>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_map(
+            format!(
+                "SELECT bug_id, title, status, timestamp, category, fixed_in FROM {} \
+WHERE guild_id = :guild_id AND status = 'resolved' AND fixed_in IS NOT NULL {category} \
+ORDER BY bug_id ASC",
+                TABLE_BUG_REPORTS,
+                category = if let Some(c) = category {
+                    format!("AND category = '{}'", c.as_str())
+                } else {
+                    "".into()
+                },
+            ),
+            params! {
+                "guild_id" => guild_id.0
+            },
+            |(bug_id, title, status, timestamp, category, fixed_in): (
+                u64,
+                String,
+                String,
+                NaiveDateTime,
+                String,
+                String,
+            )| {
+                PartialBugReport::new(
+                    bug_id,
+                    title,
+                    status,
+                    timestamp,
+                    category
+                        .parse()
+                        .expect("Expected a valid bug category from the database"),
+                )
+                .map(|bug| (bug, fixed_in))
+            },
+        )
+        .await?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// Returns the open bugs without any status or title change since `before`,
+/// with the time of their last activity, oldest first
+pub async fn get_stale_bugs(
+    ctx: &Context,
+    guild_id: GuildId,
+    before: NaiveDateTime,
+    category: Option<BugCategory>,
+) -> Result<Vec<(PartialBugReport, DateTime<Utc>)>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_map(
+            format!(
+                "SELECT bug_id, title, status, timestamp, category, last_activity FROM (\
+SELECT b.bug_id, b.title, b.status, b.timestamp, b.category, GREATEST(b.status_since, \
+COALESCE((SELECT MAX(h.timestamp) FROM {} h WHERE h.bug_id = b.bug_id), b.status_since)) \
+AS last_activity FROM {} b WHERE b.guild_id = :guild_id \
+AND b.status NOT IN ('resolved', 'closed', 'forgevanilla') {category}\
+) AS bugs WHERE last_activity < :before ORDER BY last_activity ASC",
+                TABLE_BUG_REPORTS_HISTORY,
+                TABLE_BUG_REPORTS,
+                category = if let Some(c) = category {
+                    format!("AND b.category = '{}'", c.as_str())
+                } else {
+                    "".into()
+                },
+            ),
+            params! {
+                "guild_id" => guild_id.0,
+                "before" => before
+            },
+            |(bug_id, title, status, timestamp, category, last_activity): (
+                u64,
+                String,
+                String,
+                NaiveDateTime,
+                String,
+                NaiveDateTime,
+            )| {
+                PartialBugReport::new(
+                    bug_id,
+                    title,
+                    status,
+                    timestamp,
+                    category
+                        .parse()
+                        .expect("Expected a valid bug category from the database"),
+                )
+                .map(|bug| (bug, utc(last_activity)))
+            },
+        )
+        .await?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+pub async fn change_bug_status(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    new_status: BugStatus,
+    changed_by: UserId,
+) -> Result<BugStatus, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+    check_guild_bug(&mut conn, bug_id, guild_id).await?;
+
+    let (old_status_string, channel_id, msg_id): (String, u64, u64) = conn
+        .exec_first(
+            formatcp!(
+                "SELECT status, channel_id, message_id FROM {} \
+WHERE bug_id = :bug_id AND guild_id = :guild_id LIMIT 1",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "guild_id" => guild_id.0
+            },
+        )
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+    let old_status: BugStatus = old_status_string
+        .parse()
+        .expect("Expected a valid bug status from database!");
+
+    conn.exec_drop(
+        match (old_status, new_status) {
+            (old, new) if old == new => formatcp!(
+                "UPDATE {} SET status = :status WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            (_, Resolved) => formatcp!(
+                "UPDATE {} SET status = :status, status_since = CURRENT_TIMESTAMP, \
+resolved_at = CURRENT_TIMESTAMP WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            _ => formatcp!(
+                "UPDATE {} SET status = :status, status_since = CURRENT_TIMESTAMP, \
+resolved_at = NULL WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+        },
+        params! {
+            "status" => new_status.as_str(),
+            "bug_id" => bug_id
+        },
+    )
+    .await?;
+
+    if old_status != new_status {
+        add_history(
+            &mut conn,
+            bug_id,
+            HistoryChange::Status(old_status, new_status, Some(changed_by)),
+        )
+        .await;
+        notify_watchers(
+            ctx,
+            bug_id,
+            guild_id,
+            format!("Status changed from `{}` to `{}`", old_status, new_status),
+        );
+    }
+
+    update_status_reaction(ctx, channel_id, msg_id, old_status, new_status).await;
+
+    Ok(old_status)
+}
+
+/// Replaces the status reaction on the original message of a bug
+async fn update_status_reaction(
+    ctx: &Context,
+    channel_id: u64,
+    msg_id: u64,
+    old_status: BugStatus,
+    new_status: BugStatus,
+) {
+    match ChannelId(channel_id).message(ctx, MessageId(msg_id)).await {
+        Ok(msg) => {
+            if let Err(e) = msg.delete_reaction_emoji(ctx, old_status.reaction()).await {
+                println!("Could not remove reaction from bug report: {}", e);
+            }
+            if let Err(e) = msg.react(ctx, new_status.reaction()).await {
+                println!("Could not add reaction to bug report: {}", e);
+            }
+        }
+        Err(e) => println!("Could not get message for bug report: {}", e),
+    }
+}
+
+/// Changes the status and category of a bug in a single update. Returns the
+/// old status and category.
+pub async fn triage_bug(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    new_status: BugStatus,
+    new_category: BugCategory,
+    changed_by: UserId,
+) -> Result<(BugStatus, BugCategory), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+    let mut tx = conn.start_transaction(Default::default()).await?;
+    check_guild_bug(&mut tx, bug_id, guild_id).await?;
+
+    let (old_status, old_category, channel_id, msg_id): (String, String, u64, u64) = tx
+        .exec_first(
+            formatcp!(
+                "SELECT status, category, channel_id, message_id FROM {} \
+WHERE bug_id = :bug_id AND guild_id = :guild_id LIMIT 1 FOR UPDATE",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "guild_id" => guild_id.0
+            },
+        )
+        .await?
+        .ok_or(DbError::NotFound)?;
+    let old_status: BugStatus = old_status
+        .parse()
+        .expect("Expected a valid bug status from database!");
+    let old_category: BugCategory = old_category
+        .parse()
+        .expect("Expected a valid bug category from the database");
+
+    tx.exec_drop(
+        match (old_status, new_status) {
+            (old, new) if old == new => formatcp!(
+                "UPDATE {} SET status = :status, category = :category WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            (_, Resolved) => formatcp!(
+                "UPDATE {} SET status = :status, category = :category, \
+status_since = CURRENT_TIMESTAMP, resolved_at = CURRENT_TIMESTAMP WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+            _ => formatcp!(
+                "UPDATE {} SET status = :status, category = :category, \
+status_since = CURRENT_TIMESTAMP, resolved_at = NULL WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS
+            ),
+        },
+        params! {
+            "status" => new_status.as_str(),
+            "category" => new_category.as_str(),
+            "bug_id" => bug_id
+        },
+    )
+    .await?;
+
+    if old_status != new_status {
+        add_history(
+            &mut tx,
+            bug_id,
+            HistoryChange::Status(old_status, new_status, Some(changed_by)),
+        )
+        .await;
+    }
+    tx.commit().await?;
+
+    let mut changes = Vec::new();
+    if old_status != new_status {
+        changes.push(format!(
+            "Status changed from `{}` to `{}`",
+            old_status, new_status
+        ));
+    }
+    if old_category != new_category {
+        changes.push(format!(
+            "Edition changed from {} to {}",
+            old_category, new_category
+        ));
+    }
+    if !changes.is_empty() {
+        notify_watchers(ctx, bug_id, guild_id, changes.join("\n"));
+    }
+
+    if old_status != new_status {
+        update_status_reaction(ctx, channel_id, msg_id, old_status, new_status).await;
+    }
+
+    Ok((old_status, old_category))
+}
+
+/// Adds a link to a bug, and returns its [position][BugLink::num]. Fails with
+/// [`DbError::NotFound`] if there is no such bug in the guild.
+pub async fn add_link(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    link_url: &str,
+    link_title: &str,
+) -> Result<u64, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    // the links table has no foreign key, check that the bug exists first
+    check_guild_bug(&mut conn, bug_id, guild_id).await?;
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT INTO {} (bug_id, link_url, link_title) VALUES (:bug_id, :link_url, :link_title)",
+            TABLE_BUG_REPORTS_LINKS
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "link_url" => link_url,
+            "link_title" => link_title
+        },
+    )
+    .await?;
+
+    notify_watchers(
+        ctx,
+        bug_id,
+        guild_id,
+        format!("Link added: [{}]({})", link_title, link_url),
+    );
+
+    // the new link has the highest id, so it is the last one
+    conn.exec_first(
+        formatcp!(
+            "SELECT COUNT(*) FROM {} WHERE bug_id = :bug_id",
+            TABLE_BUG_REPORTS_LINKS
+        ),
+        params! {
+            "bug_id" => bug_id
+        },
+    )
+    .await?
+    .ok_or(DbError::NotFound)
+}
+
+pub async fn remove_link(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    link: &BugLink,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    check_guild_bug(&mut conn, bug_id, guild_id).await?;
+
+    conn.exec_drop(
+        formatcp!(
+            "DELETE FROM {} WHERE bug_id = :bug_id AND link_id = :link_id",
+            TABLE_BUG_REPORTS_LINKS
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "link_id" => link.id
+        },
+    )
+    .await?;
+    if conn.affected_rows() == 0 {
+        return Err(DbError::NotFound);
+    }
+
+    notify_watchers(ctx, bug_id, guild_id, format!("Link #{} removed", link.num));
+
+    Ok(())
+}
+
+/// Lowercases a label, and checks that it is 1 to [`MAX_LABEL_LENGTH`]
+/// letters, digits, `-` or `_`. This keeps labels safe to use in queries and
+/// custom ids as is.
+pub fn normalize_label(label: &str) -> Option<String> {
+    let label = label.trim().to_lowercase();
+    if (1..=MAX_LABEL_LENGTH).contains(&label.len())
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Some(label)
+    } else {
+        None
+    }
+}
+
+/// Adds a [normalized][normalize_label] label to a bug. Returns `false` if the
+/// bug already had it.
+pub async fn add_label(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    label: &str,
+) -> Result<bool, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    check_guild_bug(&mut conn, bug_id, guild_id).await?;
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT IGNORE INTO {} (bug_id, label) VALUES (:bug_id, :label)",
+            TABLE_BUG_REPORTS_LABELS
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "label" => label
+        },
+    )
+    .await?;
+    if conn.affected_rows() == 0 {
+        return Ok(false);
+    }
+
+    notify_watchers(ctx, bug_id, guild_id, format!("Label added: `{}`", label));
+    notify_label_subscriptions(ctx, bug_id, guild_id, label.to_string());
+
+    Ok(true)
+}
+
+/// Returns the labels a channel is subscribed to, sorted
+pub async fn get_label_subscriptions(
+    ctx: &Context,
+    channel_id: ChannelId,
+) -> Result<Vec<String>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .exec(
+            formatcp!(
+                "SELECT label FROM {} WHERE channel_id = :channel_id ORDER BY label",
+                TABLE_BUG_REPORTS_LABEL_SUBSCRIPTIONS
+            ),
+            params! {
+                "channel_id" => channel_id.0
+            },
+        )
+        .await?)
+}
+
+/// Returns `false` if the channel was already subscribed to the label
+pub async fn add_label_subscription(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    label: &str,
+) -> Result<bool, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT IGNORE INTO {} (guild_id, channel_id, label) \
+VALUES (:guild_id, :channel_id, :label)",
+            TABLE_BUG_REPORTS_LABEL_SUBSCRIPTIONS
+        ),
+        params! {
+            "guild_id" => guild_id.0,
+            "channel_id" => channel_id.0,
+            "label" => label
+        },
+    )
+    .await?;
+
+    Ok(conn.affected_rows() > 0)
+}
+
+/// Fails with [`DbError::NotFound`] if the channel is not subscribed to the
+/// label
+pub async fn remove_label_subscription(
+    ctx: &Context,
+    channel_id: ChannelId,
+    label: &str,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "DELETE FROM {} WHERE channel_id = :channel_id AND label = :label",
+            TABLE_BUG_REPORTS_LABEL_SUBSCRIPTIONS
+        ),
+        params! {
+            "channel_id" => channel_id.0,
+            "label" => label
+        },
+    )
+    .await?;
+    if conn.affected_rows() == 0 {
+        return Err(DbError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// Channels of the guild of a bug that are subscribed to a label
+async fn get_subscribed_channels(
+    ctx: &Context,
+    bug_id: u64,
+    label: &str,
+) -> Result<Vec<ChannelId>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_map(
+            formatcp!(
+                "SELECT s.channel_id FROM {} s JOIN {} b ON b.guild_id = s.guild_id \
+WHERE b.bug_id = :bug_id AND s.label = :label",
+                TABLE_BUG_REPORTS_LABEL_SUBSCRIPTIONS,
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "label" => label
+            },
+            ChannelId,
+        )
+        .await?)
+}
+
+/// Posts a bug in the channels of its guild subscribed to a label it was just
+/// given
+fn notify_label_subscriptions(ctx: &Context, bug_id: u64, guild_id: GuildId, label: String) {
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let _work = match get_shutdown(&ctx).await.track() {
+            Some(work) => work,
+            None => return,
+        };
+        if notifications_paused(&ctx).await {
+            return;
+        }
+
+        let prefix = get_bug_prefix(&ctx, Some(guild_id)).await;
+        let markers = StatusMarkers::of(&ctx, Some(guild_id)).await;
+        let command_prefix = get_prefix(&ctx, guild_id)
+            .await
+            .unwrap_or_else(|| "!".into());
+        let channels = match get_subscribed_channels(&ctx, bug_id, &label).await {
+            Ok(channels) => channels,
+            Err(e) => {
+                println!(
+                    "=== ERROR ===\nCould not get the channels subscribed to `{}`: {}\n=== END ===",
+                    label, e
+                );
+                return;
+            }
+        };
+        if channels.is_empty() {
+            return;
+        }
+        let bug = match get_bug_from_id(&ctx, bug_id, guild_id).await {
+            Ok(bug) => bug,
+            Err(e) => {
+                println!(
+                    "=== ERROR ===\nCould not get {} for its label subscriptions: {}\n=== END ===",
+                    prefix.id(bug_id),
+                    e
+                );
+                return;
+            }
+        };
+        let colour = bug.status.guild_colour(&ctx, Some(guild_id)).await;
+
+        for channel_id in channels {
+            if let Err(e) = channel_id
+                .send_message(&ctx, |m| {
+                    m.embed(|e| {
+                        e.colour(colour);
+                        e.title(format!(
+                            "{} {}: {} [{}]",
+                            markers.get(bug.status),
+                            prefix.id(bug_id),
+                            bug.title,
+                            bug.category
+                        ));
+                        e.description(match &bug.summary {
+                            Some(summary) => format!("Labelled `{}`\n\n{}", label, summary),
+                            None => format!("Labelled `{}`", label),
+                        });
+                        e.footer(|f| {
+                            f.text(format!(
+                                "Status: {} • Use {}bug {} for the full report",
+                                bug.status,
+                                command_prefix,
+                                prefix.id(bug_id)
+                            ))
+                        })
+                    })
+                    .allowed_mentions(|a| a.empty_parse())
+                })
+                .await
+            {
+                println!(
+                    "Could not post {} in the label channel {}: {}",
+                    prefix.id(bug_id),
+                    channel_id,
+                    e
+                );
+            }
+        }
+    });
+}
+
+pub async fn remove_label(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    label: &str,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    check_guild_bug(&mut conn, bug_id, guild_id).await?;
+
+    conn.exec_drop(
+        formatcp!(
+            "DELETE FROM {} WHERE bug_id = :bug_id AND label = :label",
+            TABLE_BUG_REPORTS_LABELS
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "label" => label
+        },
+    )
+    .await?;
+    if conn.affected_rows() == 0 {
+        return Err(DbError::NotFound);
+    }
+
+    notify_watchers(ctx, bug_id, guild_id, format!("Label removed: `{}`", label));
+
+    Ok(())
+}
+
+/// Returns the ids of the bugs of a guild resolved before `before` and still
+/// resolved
+pub async fn get_resolved_bugs_before(
+    ctx: &Context,
+    guild_id: GuildId,
+    before: NaiveDateTime,
+) -> Result<Vec<u64>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .exec(
+            formatcp!(
+                "SELECT bug_id FROM {} WHERE guild_id = :guild_id AND status = 'resolved' \
+AND status_since < :before",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "guild_id" => guild_id.0,
+                "before" => before
+            },
+        )
+        .await?)
+}
+
+/// Closes a bug that is still resolved, returning `false` if its status changed
+/// in the meantime
+pub async fn close_resolved_bug(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    changed_by: UserId,
+) -> Result<bool, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    let (channel_id, msg_id): (u64, u64) = conn
+        .exec_first(
+            formatcp!(
+                "SELECT channel_id, message_id FROM {} \
+WHERE bug_id = :bug_id AND guild_id = :guild_id LIMIT 1",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "guild_id" => guild_id.0
+            },
+        )
+        .await?
+        .ok_or(DbError::NotFound)?;
+
+    conn.exec_drop(
+        formatcp!(
+            "UPDATE {} SET status = 'closed', status_since = CURRENT_TIMESTAMP, \
+resolved_at = NULL WHERE bug_id = :bug_id AND status = 'resolved'",
+            TABLE_BUG_REPORTS
+        ),
+        params! {
+            "bug_id" => bug_id
+        },
+    )
+    .await?;
+
+    if conn.affected_rows() == 0 {
+        return Ok(false);
+    }
+
+    add_history(
+        &mut conn,
+        bug_id,
+        HistoryChange::Status(Resolved, Closed, Some(changed_by)),
+    )
+    .await;
+    notify_watchers(
+        ctx,
+        bug_id,
+        guild_id,
+        format!("Status changed from `{}` to `{}`", Resolved, Closed),
+    );
+
+    update_status_reaction(ctx, channel_id, msg_id, Resolved, Closed).await;
+
+    Ok(true)
+}
+
+/// Returns the users with the most bug reports in a guild since `since`, with their
+/// number of reports. Merged duplicates and the bugs tracked by the bot itself
+/// are left out, and ties go to the user who reported first.
+pub async fn get_top_reporters(
+    ctx: &Context,
+    guild_id: GuildId,
+    since: Option<NaiveDateTime>,
+    limit: u32,
+) -> Result<Vec<(UserId, u32)>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_map(
+            formatcp!(
+                "SELECT reporter_id, COUNT(bug_id) AS bugs FROM {} WHERE guild_id = :guild_id \
+AND reporter_id IS NOT NULL AND reporter_id != :bot_id AND merged_into IS NULL AND timestamp >= :since GROUP BY reporter_id \
+ORDER BY bugs DESC, MIN(timestamp) ASC, reporter_id ASC LIMIT :limit",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "guild_id" => guild_id.0,
+                "bot_id" => BOT_ID.0,
+                "since" => since.unwrap_or_else(|| NaiveDateTime::from_timestamp(0, 0)),
+                "limit" => limit
+            },
+            |(reporter_id, bugs)| (UserId(reporter_id), bugs),
+        )
+        .await?)
+}
+
+/// Snoozes a bug until the given time, or wakes it up if `until` is `None`
+pub async fn snooze_bug(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    until: Option<NaiveDateTime>,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    check_guild_bug(&mut conn, bug_id, guild_id).await?;
+
+    conn.exec_drop(
+        formatcp!(
+            "UPDATE {} SET snoozed_until = :until WHERE bug_id = :bug_id",
+            TABLE_BUG_REPORTS
+        ),
+        params! {
+            "until" => until,
+            "bug_id" => bug_id
+        },
+    )
+    .await?;
+
+    notify_watchers(
+        ctx,
+        bug_id,
+        guild_id,
+        match until {
+            Some(until) => format!("Snoozed until {}", discord_timestamp(&utc(until), 'D')),
+            None => "No longer snoozed".into(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Clears the snoozes that have expired, and returns the ids of their bugs
+/// with their guild
+pub async fn take_expired_snoozes(ctx: &Context) -> Result<Vec<(u64, GuildId)>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    let bugs: Vec<(u64, GuildId)> = conn
+        .query_map(
+            formatcp!(
+                "SELECT bug_id, guild_id FROM {} WHERE snoozed_until <= UTC_TIMESTAMP()",
+                TABLE_BUG_REPORTS
+            ),
+            |(bug_id, guild_id)| (bug_id, GuildId(guild_id)),
+        )
+        .await?;
+    if bugs.is_empty() {
+        return Ok(bugs);
+    }
+
+    // only the fetched bugs, a snooze expiring in between is woken up next time
+    conn.query_drop(format!(
+        "UPDATE {} SET snoozed_until = NULL WHERE bug_id IN ({})",
+        TABLE_BUG_REPORTS,
+        bugs.iter()
+            .map(|(bug_id, _)| bug_id.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    ))
+    .await?;
+
+    Ok(bugs)
+}
+
+/// Returns every label in use in a guild with its number of bugs, most used
+/// first
+pub async fn get_label_counts(
+    ctx: &Context,
+    guild_id: GuildId,
+) -> Result<Vec<(String, u32)>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .exec(
+            formatcp!(
+                "SELECT label, COUNT(bug_id) AS bugs FROM {} WHERE {} GROUP BY label \
+ORDER BY bugs DESC, label",
+                TABLE_BUG_REPORTS_LABELS,
+                IN_GUILD
+            ),
+            params! {
+                "guild_id" => guild_id.0
+            },
+        )
+        .await?)
+}
+
+/// Returns every label carried by the open bugs of a guild, as `(label, open bugs, open bugs
+/// reported since)` tuples, most used first. A bug with several labels counts
+/// toward each of them.
+pub async fn get_open_label_counts(
+    ctx: &Context,
+    guild_id: GuildId,
+    since: NaiveDateTime,
+) -> Result<Vec<(String, u32, u32)>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .exec(
+            formatcp!(
+                "SELECT l.label, COUNT(l.bug_id) AS bugs, \
+COUNT(CASE WHEN b.timestamp >= :since THEN 1 END) \
+FROM {} AS l INNER JOIN {} AS b ON b.bug_id = l.bug_id \
+WHERE b.guild_id = :guild_id AND b.status IN ('low', 'medium', 'high', 'critical') \
+GROUP BY l.label ORDER BY bugs DESC, l.label",
+                TABLE_BUG_REPORTS_LABELS,
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "guild_id" => guild_id.0,
+                "since" => since
+            },
+        )
+        .await?)
+}
+
+/// Webhook used to [post][crate::commands::bug_reports::bug_post] bugs to
+/// another server, with an optional identity overriding the webhook's own
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BugWebhook {
+    pub name: String,
+    pub url: String,
+    pub username: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+pub async fn get_webhook(
+    ctx: &Context,
+    server_id: GuildId,
+    name: &str,
+) -> Result<BugWebhook, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_first(
+        formatcp!(
+            "SELECT name, url, username, avatar_url FROM {} \
+WHERE server_id = :server_id AND name = :name",
+            TABLE_BUG_REPORTS_WEBHOOKS
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "name" => name
+        },
+    )
+    .await?
+    .map(|(name, url, username, avatar_url)| BugWebhook {
+        name,
+        url,
+        username,
+        avatar_url,
+    })
+    .ok_or(DbError::NotFound)
+}
+
+pub async fn get_webhooks(ctx: &Context, server_id: GuildId) -> Result<Vec<BugWebhook>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_map(
+            formatcp!(
+                "SELECT name, url, username, avatar_url FROM {} WHERE server_id = :server_id \
+ORDER BY name",
+                TABLE_BUG_REPORTS_WEBHOOKS
+            ),
+            params! {
+                "server_id" => server_id.0
+            },
+            |(name, url, username, avatar_url)| BugWebhook {
+                name,
+                url,
+                username,
+                avatar_url,
+            },
+        )
+        .await?)
+}
+
+/// Adds a webhook, or replaces the one with the same name
+pub async fn set_webhook(
+    ctx: &Context,
+    server_id: GuildId,
+    webhook: &BugWebhook,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "REPLACE INTO {} (server_id, name, url, username, avatar_url) \
+VALUES (:server_id, :name, :url, :username, :avatar_url)",
+            TABLE_BUG_REPORTS_WEBHOOKS
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "name" => &webhook.name,
+            "url" => &webhook.url,
+            "username" => &webhook.username,
+            "avatar_url" => &webhook.avatar_url,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn remove_webhook(ctx: &Context, server_id: GuildId, name: &str) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
 
     conn.exec_drop(
         formatcp!(
-            "UPDATE {} SET status = :status WHERE bug_id = :bug_id",
+            "DELETE FROM {} WHERE server_id = :server_id AND name = :name",
+            TABLE_BUG_REPORTS_WEBHOOKS
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "name" => name
+        },
+    )
+    .await?;
+    if conn.affected_rows() == 0 {
+        return Err(DbError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// Returns every link of the bugs of a guild, with the id of its bug
+pub async fn get_all_links(
+    ctx: &Context,
+    guild_id: GuildId,
+) -> Result<Vec<(u64, BugLink)>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    let rows: Vec<(u64, u64, String, String)> = conn
+        .exec(
+            formatcp!(
+                "SELECT bug_id, link_id, link_url, link_title FROM {} WHERE {} \
+ORDER BY bug_id, link_id",
+                TABLE_BUG_REPORTS_LINKS,
+                IN_GUILD
+            ),
+            params! {
+                "guild_id" => guild_id.0
+            },
+        )
+        .await?;
+
+    let mut num = 0;
+    let mut previous_bug = None;
+    Ok(rows
+        .into_iter()
+        .map(|(bug_id, id, url, title)| {
+            if previous_bug != Some(bug_id) {
+                previous_bug = Some(bug_id);
+                num = 0;
+            }
+            num += 1;
+            (
+                bug_id,
+                BugLink {
+                    id,
+                    num,
+                    url,
+                    title,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Moves the links, subscribers and watchers of `bug` to `into`, then closes
+/// `bug` and marks it as merged. A link to the original message of `bug` is
+/// added to `into` as a merge note.
+pub async fn merge_bugs(ctx: &Context, bug: &BugReport, into: u64) -> Result<(), DbError> {
+    let prefix = get_bug_prefix(ctx, Some(bug.guild_id)).await;
+    let mut conn = try_get_database_conn!(ctx);
+    let mut tx = conn.start_transaction(Default::default()).await?;
+
+    check_guild_bug(&mut tx, into, bug.guild_id).await?;
+
+    tx.exec_drop(
+        formatcp!(
+            "UPDATE {} SET bug_id = :into WHERE bug_id = :bug_id",
+            TABLE_BUG_REPORTS_LINKS
+        ),
+        params! {
+            "into" => into,
+            "bug_id" => bug.bug_id,
+        },
+    )
+    .await?;
+
+    // users following both bugs must not be counted twice
+    for table in [TABLE_BUG_REPORTS_NOTIFICATIONS, TABLE_BUG_REPORTS_WATCHERS] {
+        tx.exec_drop(
+            format!(
+                "UPDATE {0} SET bug_id = :into WHERE bug_id = :bug_id AND user_id NOT IN \
+(SELECT user_id FROM (SELECT user_id FROM {0} WHERE bug_id = :into) AS merged)",
+                table
+            ),
+            params! {
+                "into" => into,
+                "bug_id" => bug.bug_id,
+            },
+        )
+        .await?;
+        tx.exec_drop(
+            format!("DELETE FROM {} WHERE bug_id = :bug_id", table),
+            params! {
+                "bug_id" => bug.bug_id,
+            },
+        )
+        .await?;
+    }
+
+    tx.exec_drop(
+        formatcp!(
+            "INSERT INTO {} (bug_id, link_url, link_title) VALUES (:bug_id, :link_url, :link_title)",
+            TABLE_BUG_REPORTS_LINKS
+        ),
+        params! {
+            "bug_id" => into,
+            "link_url" => bug.message_url(),
+            "link_title" => format!("Merged from {}: {}", prefix.id(bug.bug_id), bug.title),
+        },
+    )
+    .await?;
+
+    tx.exec_drop(
+        formatcp!(
+            "UPDATE {} SET status = :status, status_since = CURRENT_TIMESTAMP, resolved_at = NULL, \
+merged_into = :into WHERE bug_id = :bug_id",
             TABLE_BUG_REPORTS
         ),
         params! {
-            "status" => new_status.as_str(),
-            "bug_id" => bug_id
+            "status" => Closed.as_str(),
+            "into" => into,
+            "bug_id" => bug.bug_id,
         },
     )
     .await?;
 
-    match ChannelId(channel_id).message(ctx, MessageId(msg_id)).await {
+    if bug.status != Closed {
+        add_history(
+            &mut tx,
+            bug.bug_id,
+            HistoryChange::Status(bug.status, Closed, None),
+        )
+        .await;
+    }
+
+    tx.commit().await?;
+
+    match bug.channel_id.message(ctx, bug.message_id).await {
         Ok(msg) => {
-            if let Err(e) = msg.delete_reaction_emoji(ctx, old_status.reaction()).await {
+            if let Err(e) = msg.delete_reaction_emoji(ctx, bug.status.reaction()).await {
                 println!("Could not remove reaction from bug report: {}", e);
             }
-            if let Err(e) = msg.react(ctx, new_status.reaction()).await {
+            if let Err(e) = msg.react(ctx, Closed.reaction()).await {
                 println!("Could not add reaction to bug report: {}", e);
             }
         }
         Err(e) => println!("Could not get message for bug report: {}", e),
     }
 
-    Ok(old_status)
+    notify_watchers(
+        ctx,
+        into,
+        bug.guild_id,
+        format!("{} was merged into this bug", prefix.id(bug.bug_id)),
+    );
+
+    Ok(())
 }
 
-pub async fn add_link(ctx: &Context, bug_id: u64, link_url: &str, link_title: &str) -> Option<u64> {
-    let mut conn = get_database_conn!(ctx);
+pub async fn change_title(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    new_title: &str,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+    check_guild_bug(&mut conn, bug_id, guild_id).await?;
+
+    let old_title: String = conn
+        .exec_first(
+            formatcp!(
+                "SELECT title FROM {} WHERE bug_id = :bug_id AND guild_id = :guild_id",
+                TABLE_BUG_REPORTS
+            ),
+            params! {
+                "bug_id" => bug_id,
+                "guild_id" => guild_id.0
+            },
+        )
+        .await?
+        .ok_or(DbError::NotFound)?;
 
     conn.exec_drop(
         formatcp!(
-            "INSERT INTO {} (bug_id, link_url, link_title) VALUES (:bug_id, :link_url, :link_title)",
-            TABLE_BUG_REPORTS_LINKS
+            "UPDATE {} SET title = :new_title WHERE bug_id = :bug_id",
+            TABLE_BUG_REPORTS
         ),
         params! {
-            "bug_id" => bug_id,
-            "link_url" => link_url,
-            "link_title" => link_title
+            "new_title" => new_title,
+            "bug_id" => bug_id
         },
     )
-    .await.ok()?;
+    .await?;
 
-    conn.exec_first(
+    if old_title != new_title {
+        add_history(
+            &mut conn,
+            bug_id,
+            HistoryChange::Title(&old_title, new_title),
+        )
+        .await;
+    }
+
+    notify_watchers(
+        ctx,
+        bug_id,
+        guild_id,
+        format!("Title changed to \"{}\"", new_title),
+    );
+
+    Ok(())
+}
+
+/// Changes the summary of a bug, or removes it if `None`
+pub async fn change_summary(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    summary: Option<&str>,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    check_guild_bug(&mut conn, bug_id, guild_id).await?;
+
+    conn.exec_drop(
         formatcp!(
-            "SELECT MAX(link_id) FROM {} WHERE bug_id = :bug_id",
-            TABLE_BUG_REPORTS_LINKS
+            "UPDATE {} SET summary = :summary WHERE bug_id = :bug_id",
+            TABLE_BUG_REPORTS
         ),
         params! {
+            "summary" => summary,
             "bug_id" => bug_id
         },
     )
-    .await
-    .ok()?
+    .await?;
+
+    notify_watchers(
+        ctx,
+        bug_id,
+        guild_id,
+        match summary {
+            Some(_) => "Summary changed".to_string(),
+            None => "Summary removed".to_string(),
+        },
+    );
+
+    Ok(())
 }
 
-pub async fn remove_link(ctx: &Context, bug_id: u64, link_num: u64) -> CommandResult {
-    let mut conn = get_database_conn!(ctx);
+/// Records the mod version that fixes a bug, see
+/// [`!bugs between`][crate::commands::bug_reports::buglist]
+pub async fn set_fixed_in(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    version: &str,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+    check_guild_bug(&mut conn, bug_id, guild_id).await?;
 
     conn.exec_drop(
         formatcp!(
-            "DELETE FROM {} WHERE bug_id = :bug_id AND link_id = :link_id",
-            TABLE_BUG_REPORTS_LINKS
+            "UPDATE {} SET fixed_in = :version WHERE bug_id = :bug_id AND guild_id = :guild_id",
+            TABLE_BUG_REPORTS
         ),
         params! {
+            "version" => version,
             "bug_id" => bug_id,
-            "link_id" => link_num
+            "guild_id" => guild_id.0
         },
     )
     .await?;
@@ -513,16 +2452,53 @@ pub async fn remove_link(ctx: &Context, bug_id: u64, link_num: u64) -> CommandRe
     Ok(())
 }
 
-pub async fn change_title(ctx: &Context, bug_id: u64, new_title: &str) -> CommandResult {
+/// Points a bug report to a new originating message
+pub async fn set_bug_message(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    message: &Message,
+) -> CommandResult {
     let mut conn = get_database_conn!(ctx);
 
     conn.exec_drop(
         formatcp!(
-            "UPDATE {} SET title = :new_title WHERE bug_id = :bug_id",
+            "UPDATE {} SET guild_id = :guild_id, channel_id = :channel_id, \
+message_id = :message_id WHERE bug_id = :bug_id",
             TABLE_BUG_REPORTS
         ),
         params! {
-            "new_title" => new_title,
+            "guild_id" => guild_id.0,
+            "channel_id" => message.channel_id.0,
+            "message_id" => message.id.0,
+            "bug_id" => bug_id
+        },
+    )
+    .await?;
+
+    update_saved_message(ctx, bug_id, &SavedMessage::of(message)).await?;
+
+    Ok(())
+}
+
+pub async fn update_saved_message(
+    ctx: &Context,
+    bug_id: u64,
+    saved_message: &SavedMessage,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "UPDATE {} SET saved_author = :author, saved_content = :content, \
+saved_attachment = :attachment, has_attachment = :has_attachment WHERE bug_id = :bug_id",
+            TABLE_BUG_REPORTS
+        ),
+        params! {
+            "author" => &saved_message.author,
+            "content" => &saved_message.content,
+            "attachment" => &saved_message.attachment_url,
+            "has_attachment" => saved_message.attachment_url.is_some(),
             "bug_id" => bug_id
         },
     )
@@ -544,7 +2520,54 @@ pub struct Counts {
     pub second_age: u32,
 }
 
-pub async fn get_bug_statistics(ctx: &Context) -> Option<Counts> {
+#[derive(Debug, Clone, Copy)]
+pub struct BugDigest {
+    pub opened: u32,
+    pub resolved: u32,
+    pub closed: u32,
+    pub open_critical: u32,
+}
+
+/// Counts the bugs of a guild opened, resolved and closed since `since`, along
+/// with its current number of critical bugs.
+pub async fn get_bug_digest(
+    ctx: &Context,
+    guild_id: GuildId,
+    since: NaiveDateTime,
+) -> Option<BugDigest> {
+    let mut conn = get_database_conn!(ctx);
+
+    let (opened, resolved, closed, open_critical) = conn
+        .exec_first(
+            formatcp!(
+                "SELECT \
+(SELECT COUNT(bug_id) FROM {0} WHERE guild_id = :guild_id AND timestamp >= :since), \
+(SELECT COUNT(bug_id) FROM {0} WHERE guild_id = :guild_id AND status = 'resolved' \
+AND resolved_at >= :since), \
+(SELECT COUNT(DISTINCT bug_id) FROM {1} WHERE new_status = 'closed' AND timestamp >= :since \
+AND {2}), \
+(SELECT COUNT(bug_id) FROM {0} WHERE guild_id = :guild_id AND status = 'critical')",
+                TABLE_BUG_REPORTS,
+                TABLE_BUG_REPORTS_HISTORY,
+                IN_GUILD
+            ),
+            params! {
+                "guild_id" => guild_id.0,
+                "since" => since,
+            },
+        )
+        .await
+        .ok()??;
+
+    Some(BugDigest {
+        opened,
+        resolved,
+        closed,
+        open_critical,
+    })
+}
+
+pub async fn get_bug_statistics(ctx: &Context, guild_id: GuildId) -> Option<Counts> {
     let mut conn = get_database_conn!(ctx);
 
     let statuses = [
@@ -557,16 +2580,20 @@ pub async fn get_bug_statistics(ctx: &Context) -> Option<Counts> {
         "forgevanilla",
     ];
 
+    // the snoozed bugs are left out, like in the lists of `!bugs`
     let mut counts = [0; 9];
 
     for (i, s) in statuses.iter().enumerate() {
         let x = conn
             .exec_first(
                 formatcp!(
-                    "SELECT COUNT(bug_id) FROM {} WHERE status = :status",
-                    TABLE_BUG_REPORTS
+                    "SELECT COUNT(bug_id) FROM {} WHERE guild_id = :guild_id AND status = :status \
+AND {}",
+                    TABLE_BUG_REPORTS,
+                    NOT_SNOOZED
                 ),
                 params! {
+                    "guild_id" => guild_id.0,
                     "status" => s
                 },
             )
@@ -578,10 +2605,17 @@ pub async fn get_bug_statistics(ctx: &Context) -> Option<Counts> {
     counts[7] = counts.iter().sum();
 
     counts[8] = conn
-        .query_first(formatcp!(
-            "SELECT COUNT(bug_id) FROM {} WHERE category = 'sa_legacy' OR category = 'sa_renewed'",
-            TABLE_BUG_REPORTS
-        ))
+        .exec_first(
+            formatcp!(
+                "SELECT COUNT(bug_id) FROM {} WHERE guild_id = :guild_id \
+AND (category = 'sa_legacy' OR category = 'sa_renewed') AND {}",
+                TABLE_BUG_REPORTS,
+                NOT_SNOOZED
+            ),
+            params! {
+                "guild_id" => guild_id.0
+            },
+        )
         .await
         .ok()??;
 
@@ -601,22 +2635,25 @@ pub async fn get_bug_statistics(ctx: &Context) -> Option<Counts> {
 pub async fn change_category(
     ctx: &Context,
     bug_id: u64,
+    guild_id: GuildId,
     category: BugCategory,
-) -> Option<BugCategory> {
-    let mut conn = get_database_conn!(ctx);
+) -> Result<BugCategory, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+    check_guild_bug(&mut conn, bug_id, guild_id).await?;
 
     let old_category = conn
         .exec_first::<String, _, _>(
             formatcp!(
-                "SELECT category FROM {} WHERE bug_id = :bug_id",
+                "SELECT category FROM {} WHERE bug_id = :bug_id AND guild_id = :guild_id",
                 TABLE_BUG_REPORTS
             ),
             params! {
                 "bug_id" => bug_id,
+                "guild_id" => guild_id.0,
             },
         )
-        .await
-        .ok()??
+        .await?
+        .ok_or(DbError::NotFound)?
         .parse()
         .expect("Expected a valid bug category from the database");
 
@@ -630,10 +2667,18 @@ pub async fn change_category(
             "bug_id" => bug_id
         },
     )
-    .await
-    .ok()?;
+    .await?;
+
+    if old_category != category {
+        notify_watchers(
+            ctx,
+            bug_id,
+            guild_id,
+            format!("Edition changed from {} to {}", old_category, category),
+        );
+    }
 
-    Some(old_category)
+    Ok(old_category)
 }
 
 pub async fn is_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -> Option<bool> {
@@ -656,6 +2701,7 @@ FROM {} WHERE bug_id = :bug_id AND user_id = :user_id)",
 
 pub async fn get_notifications_for_user(
     ctx: &Context,
+    guild_id: GuildId,
     user_id: UserId,
     closed: bool,
 ) -> CommandResult<Vec<u64>> {
@@ -664,10 +2710,12 @@ pub async fn get_notifications_for_user(
     Ok(if closed {
         conn.exec(
             formatcp!(
-                "SELECT bug_id FROM {} WHERE user_id = :user_id",
+                "SELECT bug_id FROM {} WHERE user_id = :user_id AND {}",
                 TABLE_BUG_REPORTS_NOTIFICATIONS,
+                IN_GUILD
             ),
             params! {
+                "guild_id" => guild_id.0,
                 "user_id" => user_id.0
             },
         )
@@ -681,9 +2729,12 @@ ON t1.bug_id = t2.bug_id \
 AND t2.status != 'closed' \
 AND t2.status != 'resolved' \
 AND t2.status != 'forgevanilla' \
-WHERE t1.user_id = :user_id"
+WHERE t1.user_id = :user_id AND t2.guild_id = :guild_id"
             ),
-            params! {"user_id" => user_id.0},
+            params! {
+                "guild_id" => guild_id.0,
+                "user_id" => user_id.0
+            },
         )
         .await?
     })
@@ -706,13 +2757,42 @@ pub async fn get_notified_users(ctx: &Context, bug_id: u64) -> CommandResult<Vec
         .await?)
 }
 
-pub async fn add_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -> CommandResult {
+/// Fails with [`DbError::NotFound`] if there is no such bug in the guild
+pub async fn add_notified_user(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT INTO {} (bug_id, user_id) SELECT :bug_id, :user_id FROM DUAL WHERE {}",
+            TABLE_BUG_REPORTS_NOTIFICATIONS,
+            IN_GUILD
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "guild_id" => guild_id.0,
+            "user_id" => user_id.0
+        },
+    )
+    .await?;
+    if conn.affected_rows() == 0 {
+        return Err(DbError::NotFound.into());
+    }
+
+    Ok(())
+}
+
+pub async fn remove_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -> CommandResult {
     let mut conn = get_database_conn!(ctx);
 
     Ok(conn
         .exec_drop(
             formatcp!(
-                "INSERT INTO {} (bug_id, user_id) VALUES (:bug_id, :user_id)",
+                "DELETE FROM {} WHERE bug_id = :bug_id AND user_id = :user_id LIMIT 1",
                 TABLE_BUG_REPORTS_NOTIFICATIONS
             ),
             params! {
@@ -723,14 +2803,77 @@ pub async fn add_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -> C
         .await?)
 }
 
-pub async fn remove_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -> CommandResult {
+pub async fn is_watcher(ctx: &Context, bug_id: u64, user_id: UserId) -> Option<bool> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_first(
+        formatcp!(
+            "SELECT EXISTS(SELECT watch_id FROM {} WHERE bug_id = :bug_id AND user_id = :user_id)",
+            TABLE_BUG_REPORTS_WATCHERS
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "user_id" => user_id.0
+        },
+    )
+    .await
+    .ok()?
+}
+
+pub async fn get_watchers(ctx: &Context, bug_id: u64) -> CommandResult<Vec<UserId>> {
+    let mut conn = get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_map(
+            formatcp!(
+                "SELECT user_id FROM {} WHERE bug_id = :bug_id",
+                TABLE_BUG_REPORTS_WATCHERS
+            ),
+            params! {
+                "bug_id" => bug_id
+            },
+            UserId,
+        )
+        .await?)
+}
+
+/// Fails with [`DbError::NotFound`] if there is no such bug in the guild
+pub async fn add_watcher(
+    ctx: &Context,
+    bug_id: u64,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        formatcp!(
+            "INSERT INTO {} (bug_id, user_id) SELECT :bug_id, :user_id FROM DUAL WHERE {}",
+            TABLE_BUG_REPORTS_WATCHERS,
+            IN_GUILD
+        ),
+        params! {
+            "bug_id" => bug_id,
+            "guild_id" => guild_id.0,
+            "user_id" => user_id.0
+        },
+    )
+    .await?;
+    if conn.affected_rows() == 0 {
+        return Err(DbError::NotFound);
+    }
+
+    Ok(())
+}
+
+pub async fn remove_watcher(ctx: &Context, bug_id: u64, user_id: UserId) -> CommandResult {
     let mut conn = get_database_conn!(ctx);
 
     Ok(conn
         .exec_drop(
             formatcp!(
-                "DELETE FROM {} WHERE bug_id = :bug_id AND user_id = :user_id LIMIT 1",
-                TABLE_BUG_REPORTS_NOTIFICATIONS
+                "DELETE FROM {} WHERE bug_id = :bug_id AND user_id = :user_id",
+                TABLE_BUG_REPORTS_WATCHERS
             ),
             params! {
                 "bug_id" => bug_id,
@@ -739,3 +2882,263 @@ pub async fn remove_notified_user(ctx: &Context, bug_id: u64, user_id: UserId) -
         )
         .await?)
 }
+
+/// Time until which bugtracker notifications are paused. Not persisted, a
+/// restart resumes notifications.
+pub struct NotificationsPausedUntil;
+
+impl TypeMapKey for NotificationsPausedUntil {
+    type Value = DateTime<Utc>;
+}
+
+/// Whether bugtracker notifications are currently paused
+pub async fn notifications_paused(ctx: &Context) -> bool {
+    ctx.data
+        .read()
+        .await
+        .get::<NotificationsPausedUntil>()
+        .map_or(false, |until| *until > Utc::now())
+}
+
+/// Sends a DM describing `change` to every user watching the bug.
+///
+/// Runs in the background so that bug edits are not slowed down, and users
+/// with closed DMs are skipped.
+fn notify_watchers(ctx: &Context, bug_id: u64, guild_id: GuildId, change: String) {
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let _work = match get_shutdown(&ctx).await.track() {
+            Some(work) => work,
+            None => return,
+        };
+        if notifications_paused(&ctx).await {
+            return;
+        }
+
+        let prefix = get_bug_prefix(&ctx, Some(guild_id)).await;
+        let watchers = match get_watchers(&ctx, bug_id).await {
+            Ok(watchers) => watchers,
+            Err(e) => {
+                println!(
+                    "=== ERROR ===\nCould not get watchers of {}: {}\n=== END ===",
+                    prefix.id(bug_id),
+                    e
+                );
+                return;
+            }
+        };
+
+        for user_id in watchers {
+            let res = match user_id.create_dm_channel(&ctx).await {
+                Ok(channel) => channel
+                    .send_message(&ctx, |m| {
+                        m.embed(|e| {
+                            e.title(format!("{} was edited", prefix.id(bug_id)));
+                            e.description(&change);
+                            e.footer(|f| {
+                                f.text(format!("Use !bug unwatch {} to stop watching", bug_id))
+                            })
+                        })
+                    })
+                    .await
+                    .map(|_| ()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = res {
+                println!(
+                    "Could not notify watcher {} of {}: {}",
+                    user_id,
+                    prefix.id(bug_id),
+                    e
+                );
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BugStatus::{self, *};
+    use super::{normalize_label, BugCategory, BugListFilter, BugOrder};
+    use serenity::model::id::{GuildId, UserId};
+
+    #[test]
+    fn test_bug_status_from_str() {
+        let cases = [
+            (
+                Resolved,
+                &["resolved", "Resolved", "resolve", "fixed", "FIX", "done"][..],
+            ),
+            (Low, &["low", "LOW", "lo"]),
+            (Medium, &["medium", "med", "Mid"]),
+            (High, &["high", "hi", "HI"]),
+            (Critical, &["critical", "crit", "Crit"]),
+            (
+                Closed,
+                &[
+                    "closed", "close", "wontfix", "WontFix", "won'tfix", "invalid",
+                ],
+            ),
+            (ForgeVanilla, &["forgevanilla", "forge", "Vanilla"]),
+        ];
+
+        for (status, aliases) in cases {
+            for alias in aliases {
+                assert_eq!(alias.parse::<BugStatus>().ok(), Some(status), "{}", alias);
+            }
+            // the canonical name is always accepted
+            assert_eq!(status.as_str().parse::<BugStatus>().ok(), Some(status));
+        }
+
+        for invalid in ["", "l", "meh", "crits", "EoA-12", "legacy", "12"] {
+            assert!(invalid.parse::<BugStatus>().is_err(), "{}", invalid);
+        }
+
+        for keyword in BugStatus::KEYWORDS {
+            assert!(keyword.parse::<BugStatus>().is_ok(), "{}", keyword);
+        }
+    }
+
+    #[test]
+    fn test_normalize_label() {
+        assert_eq!(normalize_label(" Rendering ").as_deref(), Some("rendering"));
+        assert_eq!(
+            normalize_label("world_gen-2").as_deref(),
+            Some("world_gen-2")
+        );
+        for invalid in [
+            "",
+            "two words",
+            "crash!",
+            "it's",
+            "é",
+            "twenty-one-characters",
+        ] {
+            assert_eq!(normalize_label(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_bug_status_suggest() {
+        assert_eq!(BugStatus::suggest("hihg"), Some("high"));
+        assert_eq!(BugStatus::suggest("Critcal"), Some("critical"));
+        assert_eq!(BugStatus::suggest("resolvd"), Some("resolved"));
+        assert_eq!(BugStatus::suggest("clsoed"), Some("closed"));
+        // too short, or too far from any status
+        for word in ["hu", "ow", "latest", "limit", "legacy", "banana"] {
+            assert_eq!(BugStatus::suggest(word), None, "{}", word);
+        }
+    }
+
+    /// Evaluates a `WHERE` clause made of `column = 'value'` and
+    /// `column != 'value'` conditions on a row, and of the snooze conditions
+    /// on its `snoozed` column
+    fn eval_where(clause: &str, row: &[(&str, String)]) -> bool {
+        let snoozed = &row.iter().find(|(c, _)| *c == "snoozed").unwrap().1 == "true";
+        clause.split(" AND ").all(|condition| {
+            if condition == NOT_SNOOZED || condition == SNOOZED {
+                return (condition == SNOOZED) == snoozed;
+            }
+            let (column, negated, value) = match condition.split_once(" != ") {
+                Some((column, value)) => (column, true, value),
+                None => {
+                    let (column, value) = condition.split_once(" = ").unwrap();
+                    (column, false, value)
+                }
+            };
+            let value = value.trim_matches('\'');
+            let actual = &row.iter().find(|(c, _)| *c == column).unwrap().1;
+            (actual == value) != negated
+        })
+    }
+
+    #[test]
+    fn test_bug_list_filter() {
+        let statuses = [Resolved, Low, Medium, High, Critical, Closed, ForgeVanilla];
+        let categories = [
+            BugCategory::FaLegacy,
+            BugCategory::FaRenewed,
+            BugCategory::SaLegacy,
+            BugCategory::SaRenewed,
+        ];
+        let reporters = [UserId(1), UserId(2)];
+        let guild_id = GuildId(1);
+
+        // one bug for each combination, and a few more open legacy bugs
+        let mut bugs = Vec::new();
+        for status in statuses {
+            for category in categories {
+                for reporter in reporters {
+                    for snoozed in [false, true] {
+                        bugs.push((guild_id, status, category, reporter, snoozed));
+                    }
+                }
+            }
+        }
+        bugs.extend([
+            (guild_id, High, BugCategory::FaLegacy, UserId(1), false),
+            (guild_id, Low, BugCategory::FaLegacy, UserId(2), false),
+            (guild_id, Critical, BugCategory::SaLegacy, UserId(1), true),
+            // bugs of another guild are never listed
+            (GuildId(2), High, BugCategory::FaLegacy, UserId(1), false),
+            (GuildId(2), Low, BugCategory::FaLegacy, UserId(2), true),
+        ]);
+
+        let is_open = |status| !matches!(status, Resolved | Closed | ForgeVanilla);
+
+        for status in statuses.map(Some).into_iter().chain([None]) {
+            for category in categories.map(Some).into_iter().chain([None]) {
+                for reporter in reporters.map(Some).into_iter().chain([None]) {
+                    for snoozed in [false, true] {
+                        let filter = BugListFilter {
+                            status,
+                            category,
+                            reporter,
+                            label: None,
+                            attachment: None,
+                            snoozed,
+                        };
+
+                        // the count and the rows are filtered the same way
+                        let count_query = filter.count_query(guild_id);
+                        let page_query = filter.page_query(guild_id, BugOrder::Priority(false));
+                        let where_clause = count_query.split_once(" WHERE ").unwrap().1;
+                        let page_where = page_query.split_once(" WHERE ").unwrap().1;
+                        let page_where = page_where.split_once(" ORDER BY ").unwrap().0;
+                        assert_eq!(where_clause, page_where, "{:?}", filter);
+                        // only one page of rows is fetched
+                        assert!(page_query.ends_with(" LIMIT :limit OFFSET :offset"));
+                        assert!(!count_query.contains("LIMIT"));
+
+                        let expected = bugs
+                            .iter()
+                            .filter(|(g, s, c, r, z)| {
+                                *g == guild_id
+                                    && status.map_or(is_open(*s), |status| status == *s)
+                                    && (category.is_none() || category == Some(*c))
+                                    && (reporter.is_none() || reporter == Some(*r))
+                                    && snoozed == *z
+                            })
+                            .count();
+                        let counted = bugs
+                            .iter()
+                            .filter(|(g, s, c, r, z)| {
+                                eval_where(
+                                    where_clause,
+                                    &[
+                                        ("guild_id", g.0.to_string()),
+                                        ("status", s.as_str().to_string()),
+                                        ("category", c.as_str().to_string()),
+                                        ("reporter_id", r.0.to_string()),
+                                        ("snoozed", z.to_string()),
+                                    ],
+                                )
+                            })
+                            .count();
+                        assert_eq!(counted, expected, "{:?}", filter);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,13 +1,19 @@
+use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use mysql_async::prelude::*;
 use serenity::client::Context;
 use serenity::framework::standard::CommandResult;
-use serenity::model::id::GuildId;
+use serenity::model::id::{ChannelId, GuildId};
 use serenity::prelude::TypeMapKey;
+use std::str::FromStr;
 use std::sync::Arc;
 
-use crate::constants::{TABLE_MC_SERVER_IP, TABLE_PREFIX};
-use crate::get_database_conn;
+use crate::constants::{
+    TABLE_MC_PLAYER_ALERTS, TABLE_MC_SERVER_IP, TABLE_MC_SERVER_LIST, TABLE_PREFIX,
+    TABLE_SERVER_STATUS_LOG,
+};
+use crate::database::DbError;
+use crate::{get_database_conn, try_get_database_conn};
 
 #[derive(Debug, Clone)]
 pub struct PrefixCache(Arc<DashMap<GuildId, String>>);
@@ -109,8 +115,8 @@ pub async fn get_minecraft_ip(ctx: &Context, server_id: GuildId) -> Option<Strin
     .ok()?
 }
 
-pub async fn set_minecraft_ip(ctx: &Context, server_id: GuildId, ip: &str) -> CommandResult {
-    let mut conn = get_database_conn!(ctx);
+pub async fn set_minecraft_ip(ctx: &Context, server_id: GuildId, ip: &str) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
 
     println!("Setting up ip to {}", ip);
 
@@ -132,8 +138,8 @@ pub async fn set_minecraft_ip(ctx: &Context, server_id: GuildId, ip: &str) -> Co
     Ok(())
 }
 
-pub async fn delete_minecraft_ip(ctx: &Context, server_id: GuildId) -> CommandResult {
-    let mut conn = get_database_conn!(ctx);
+pub async fn delete_minecraft_ip(ctx: &Context, server_id: GuildId) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
 
     let req = format!(
         "DELETE FROM {} WHERE server_id = :server_id LIMIT 1",
@@ -147,6 +153,455 @@ pub async fn delete_minecraft_ip(ctx: &Context, server_id: GuildId) -> CommandRe
         },
     )
     .await?;
+    if conn.affected_rows() == 0 {
+        return Err(DbError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// Removes every named server of the guild, fails with [`DbError::NotFound`]
+/// if there was none
+pub async fn delete_minecraft_servers(ctx: &Context, server_id: GuildId) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "DELETE FROM {} WHERE server_id = :server_id",
+            TABLE_MC_SERVER_LIST
+        ),
+        params! {
+            "server_id" => server_id.0
+        },
+    )
+    .await?;
+    if conn.affected_rows() == 0 {
+        return Err(DbError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// Returns the named servers registered in the guild, as `(name, ip)` pairs
+pub async fn list_minecraft_servers(
+    ctx: &Context,
+    server_id: GuildId,
+) -> Option<Vec<(String, String)>> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec(
+        format!(
+            "SELECT name, mc_ip FROM {} WHERE server_id = :server_id ORDER BY name",
+            TABLE_MC_SERVER_LIST
+        ),
+        params! {
+            "server_id" => server_id.0
+        },
+    )
+    .await
+    .ok()
+}
+
+pub async fn add_minecraft_server(
+    ctx: &Context,
+    server_id: GuildId,
+    name: &str,
+    ip: &str,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "REPLACE INTO {} (server_id, name, mc_ip) VALUES (:server_id, :name, :mc_ip)",
+            TABLE_MC_SERVER_LIST
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "name" => name,
+            "mc_ip" => ip,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Fails with [`DbError::NotFound`] if there is no server with that name
+pub async fn remove_minecraft_server(
+    ctx: &Context,
+    server_id: GuildId,
+    name: &str,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "DELETE FROM {} WHERE server_id = :server_id AND name = :name",
+            TABLE_MC_SERVER_LIST
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "name" => name,
+        },
+    )
+    .await?;
+
+    if conn.affected_rows() == 0 {
+        return Err(DbError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// Returns the registered Minecraft IP of every guild
+pub async fn list_all_minecraft_ips(ctx: &Context) -> Result<Vec<(GuildId, String)>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .query_map(
+            format!("SELECT server_id, mc_ip FROM {}", TABLE_MC_SERVER_IP),
+            |(server_id, ip)| (GuildId(server_id), ip),
+        )
+        .await?)
+}
+
+/// Returns the named servers of every guild, as `(guild, name, ip)` tuples
+pub async fn list_all_minecraft_servers(
+    ctx: &Context,
+) -> Result<Vec<(GuildId, String, String)>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .query_map(
+            format!(
+                "SELECT server_id, name, mc_ip FROM {}",
+                TABLE_MC_SERVER_LIST
+            ),
+            |(server_id, name, ip)| (GuildId(server_id), name, ip),
+        )
+        .await?)
+}
+
+pub async fn log_server_status(
+    ctx: &Context,
+    server_id: GuildId,
+    online: bool,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "INSERT INTO {} (server_id, checked_at, online) VALUES (:server_id, :checked_at, :online)",
+            TABLE_SERVER_STATUS_LOG
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "checked_at" => Utc::now().naive_utc(),
+            "online" => online,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Deletes the status checks older than `before`, for all guilds
+pub async fn prune_server_status_log(ctx: &Context, before: DateTime<Utc>) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "DELETE FROM {} WHERE checked_at < :before",
+            TABLE_SERVER_STATUS_LOG
+        ),
+        params! {
+            "before" => before.naive_utc(),
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Returns the number of status checks since `since`, and how many of them
+/// found the server online
+pub async fn get_server_uptime(
+    ctx: &Context,
+    server_id: GuildId,
+    since: DateTime<Utc>,
+) -> Result<(u64, u64), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    let counts: Option<(u64, Option<u64>)> = conn
+        .exec_first(
+            format!(
+                "SELECT COUNT(*), CAST(SUM(online) AS UNSIGNED) FROM {} \
+WHERE server_id = :server_id AND checked_at >= :since",
+                TABLE_SERVER_STATUS_LOG
+            ),
+            params! {
+                "server_id" => server_id.0,
+                "since" => since.naive_utc(),
+            },
+        )
+        .await?;
+
+    Ok(counts
+        .map(|(total, online)| (total, online.unwrap_or_default()))
+        .unwrap_or_default())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertDirection {
+    Above,
+    Below,
+}
+
+impl FromStr for AlertDirection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "above" => Ok(Self::Above),
+            "below" => Ok(Self::Below),
+            _ => Err(()),
+        }
+    }
+}
+
+impl AlertDirection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Above => "above",
+            Self::Below => "below",
+        }
+    }
+}
+
+impl std::fmt::Display for AlertDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Alert posted when the player count of the main server of a guild reaches a
+/// threshold, checked by the [status poller][crate::commands::servers::start_status_poller]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerAlert {
+    pub threshold: u32,
+    pub direction: AlertDirection,
+    pub channel_id: ChannelId,
+    /// The alert is removed once it has fired
+    pub once: bool,
+    /// The player count was last seen far enough from the threshold for the
+    /// alert to fire when it is reached
+    pub armed: bool,
+}
+
+impl PlayerAlert {
+    /// Players that must be gained or lost past the threshold to re-arm the
+    /// alert, so that a count hovering around it doesn't fire it repeatedly
+    fn margin(&self) -> u32 {
+        (self.threshold / 10).max(1)
+    }
+
+    /// Updates the alert with a new player count, returns whether it fires
+    pub fn update(&mut self, players: u32) -> bool {
+        let (reached, away) = match self.direction {
+            AlertDirection::Above => (
+                players >= self.threshold,
+                players.saturating_add(self.margin()) <= self.threshold,
+            ),
+            AlertDirection::Below => (
+                players <= self.threshold,
+                players >= self.threshold.saturating_add(self.margin()),
+            ),
+        };
+        if self.armed && reached {
+            self.armed = false;
+            true
+        } else {
+            self.armed |= away;
+            false
+        }
+    }
+}
+
+type PlayerAlertRow = (u64, u32, String, u64, bool, bool);
+
+fn player_alert_from_row(
+    (server_id, threshold, direction, channel_id, once, armed): PlayerAlertRow,
+) -> (GuildId, PlayerAlert) {
+    (
+        GuildId(server_id),
+        PlayerAlert {
+            threshold,
+            direction: direction
+                .parse()
+                .expect("Expected a valid alert direction from the database"),
+            channel_id: ChannelId(channel_id),
+            once,
+            armed,
+        },
+    )
+}
+
+pub async fn get_player_alerts(
+    ctx: &Context,
+    server_id: GuildId,
+) -> Result<Vec<PlayerAlert>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .exec_map(
+            format!(
+                "SELECT server_id, threshold, direction, channel_id, once, armed FROM {} \
+WHERE server_id = :server_id ORDER BY direction, threshold",
+                TABLE_MC_PLAYER_ALERTS
+            ),
+            params! {
+                "server_id" => server_id.0,
+            },
+            |row| player_alert_from_row(row).1,
+        )
+        .await?)
+}
+
+/// Returns the player count alerts of every guild
+pub async fn get_all_player_alerts(ctx: &Context) -> Result<Vec<(GuildId, PlayerAlert)>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .query_map(
+            format!(
+                "SELECT server_id, threshold, direction, channel_id, once, armed FROM {}",
+                TABLE_MC_PLAYER_ALERTS
+            ),
+            player_alert_from_row,
+        )
+        .await?)
+}
+
+/// Adds an alert, or replaces the one with the same threshold and direction
+pub async fn add_player_alert(
+    ctx: &Context,
+    server_id: GuildId,
+    alert: &PlayerAlert,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "REPLACE INTO {} (server_id, threshold, direction, channel_id, once, armed) \
+VALUES (:server_id, :threshold, :direction, :channel_id, :once, :armed)",
+            TABLE_MC_PLAYER_ALERTS
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "threshold" => alert.threshold,
+            "direction" => alert.direction.as_str(),
+            "channel_id" => alert.channel_id.0,
+            "once" => alert.once,
+            "armed" => alert.armed,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Fails with [`DbError::NotFound`] if there is no such alert
+pub async fn remove_player_alert(
+    ctx: &Context,
+    server_id: GuildId,
+    threshold: u32,
+    direction: AlertDirection,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "DELETE FROM {} WHERE server_id = :server_id AND threshold = :threshold \
+AND direction = :direction",
+            TABLE_MC_PLAYER_ALERTS
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "threshold" => threshold,
+            "direction" => direction.as_str(),
+        },
+    )
+    .await?;
+
+    if conn.affected_rows() == 0 {
+        return Err(DbError::NotFound);
+    }
 
     Ok(())
 }
+
+pub async fn set_player_alert_armed(
+    ctx: &Context,
+    server_id: GuildId,
+    alert: &PlayerAlert,
+) -> Result<(), DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "UPDATE {} SET armed = :armed WHERE server_id = :server_id \
+AND threshold = :threshold AND direction = :direction",
+            TABLE_MC_PLAYER_ALERTS
+        ),
+        params! {
+            "armed" => alert.armed,
+            "server_id" => server_id.0,
+            "threshold" => alert.threshold,
+            "direction" => alert.direction.as_str(),
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AlertDirection, PlayerAlert};
+    use serenity::model::id::ChannelId;
+
+    #[test]
+    fn test_player_alert() {
+        let mut alert = PlayerAlert {
+            threshold: 20,
+            direction: AlertDirection::Above,
+            channel_id: ChannelId(1),
+            once: false,
+            armed: false,
+        };
+        // not armed until the count is seen below the threshold
+        assert!(!alert.update(25));
+        assert!(!alert.update(19));
+        assert!(!alert.armed);
+        assert!(!alert.update(18));
+        assert!(alert.armed);
+        assert!(alert.update(20));
+        // hovering around the threshold doesn't fire again
+        assert!(!alert.update(19));
+        assert!(!alert.update(21));
+        assert!(!alert.update(17));
+        assert!(alert.update(22));
+
+        let mut alert = PlayerAlert {
+            threshold: 0,
+            direction: AlertDirection::Below,
+            channel_id: ChannelId(1),
+            once: true,
+            armed: false,
+        };
+        assert!(!alert.update(0));
+        assert!(!alert.update(1));
+        assert!(alert.update(0));
+        assert!(!alert.update(0));
+    }
+}
@@ -8,9 +8,23 @@ pub mod custom_commands;
 pub mod floppa;
 pub mod qa_data;
 pub mod roles;
+pub mod settings;
 
+use chrono::{DateTime, NaiveDateTime, Utc};
+use dashmap::DashMap;
+use mysql_async::prelude::*;
 use mysql_async::{OptsBuilder, Pool};
+use serenity::client::Context;
+use serenity::model::id::ChannelId;
 use serenity::prelude::TypeMapKey;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::constants::BUSY_NOTICE_COOLDOWN;
+use crate::try_get_database_conn;
+
+/// MySQL error code for a duplicate entry in a unique index
+const ER_DUP_ENTRY: u16 = 1062;
 
 #[derive(Debug, Clone)]
 pub struct DatabasePool(Pool);
@@ -28,7 +42,112 @@ impl std::ops::Deref for DatabasePool {
 }
 
 impl DatabasePool {
+    /// Every connection uses the UTC time zone, so that `TIMESTAMP` columns,
+    /// `CURRENT_TIMESTAMP` and `UTC_TIMESTAMP()` all agree, whatever the time
+    /// zone of the server. Read them with [`utc`].
     pub fn new(opts: OptsBuilder) -> Self {
-        Self(Pool::new(opts))
+        Self(Pool::new(opts.init(vec!["SET time_zone = '+00:00'"])))
+    }
+}
+
+/// Converts a `TIMESTAMP` value read from the database, which is in UTC
+pub fn utc(time: NaiveDateTime) -> DateTime<Utc> {
+    DateTime::from_utc(time, Utc)
+}
+
+/// Errors returned by the database functions, so that commands can tell a
+/// missing row apart from a database outage
+#[derive(Debug)]
+pub enum DbError {
+    /// The requested row does not exist
+    NotFound,
+    /// The row conflicts with an existing one
+    Conflict,
+    /// The database could not be reached
+    Connection(mysql_async::Error),
+    /// All the connections of the pool are in use
+    Busy,
+    /// The bug was [archived][crate::database::bug_reports::archive_bugs], it
+    /// can't be changed anymore
+    Archived,
+    /// Any other error of the database, or a row it returned that could not
+    /// be read
+    Other(mysql_async::Error),
+}
+
+impl std::fmt::Display for DbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DbError::NotFound => write!(f, "Not found in the database"),
+            DbError::Conflict => write!(f, "Already exists in the database"),
+            DbError::Connection(e) => write!(f, "Could not reach the database: {}", e),
+            DbError::Busy => write!(f, "Timed out waiting for a database connection"),
+            DbError::Archived => write!(f, "The bug is archived"),
+            DbError::Other(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DbError {}
+
+impl From<mysql_async::Error> for DbError {
+    fn from(error: mysql_async::Error) -> Self {
+        match error {
+            mysql_async::Error::Io(_) | mysql_async::Error::Driver(_) => DbError::Connection(error),
+            mysql_async::Error::Server(ref e) if e.code == ER_DUP_ENTRY => DbError::Conflict,
+            _ => DbError::Other(error),
+        }
+    }
+}
+
+/// Returns the `(table, column)` pairs of the bot's database
+pub async fn get_schema_columns(ctx: &Context) -> Result<Vec<(String, String)>, DbError> {
+    let mut conn = try_get_database_conn!(ctx);
+
+    Ok(conn
+        .query(
+            "SELECT TABLE_NAME, COLUMN_NAME FROM information_schema.COLUMNS \
+WHERE TABLE_SCHEMA = DATABASE()",
+        )
+        .await?)
+}
+
+/// Last "busy" reply sent in each channel, see [`db_failure!`][crate::db_failure]
+#[derive(Debug, Clone)]
+pub struct BusyNotices(Arc<DashMap<ChannelId, Instant>>);
+
+impl TypeMapKey for BusyNotices {
+    type Value = Self;
+}
+
+impl Default for BusyNotices {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BusyNotices {
+    pub fn new() -> Self {
+        Self(Arc::new(DashMap::new()))
+    }
+}
+
+/// Whether a "busy" reply can be sent in the channel, i.e. none was sent
+/// during the last [`BUSY_NOTICE_COOLDOWN`]. Records the reply if so.
+pub async fn busy_notice_due(ctx: &Context, channel_id: ChannelId) -> bool {
+    let notices = {
+        let data_read = ctx.data.read().await;
+        data_read
+            .get::<BusyNotices>()
+            .expect("Expected busy notices in the type map")
+            .clone()
+    };
+    let now = Instant::now();
+    let mut last = notices.0.entry(channel_id).or_insert(now);
+    if *last == now || now.duration_since(*last) >= BUSY_NOTICE_COOLDOWN {
+        *last = now;
+        true
+    } else {
+        false
     }
 }
@@ -0,0 +1,629 @@
+use dashmap::DashMap;
+use mysql_async::prelude::*;
+use serenity::builder::CreateEmbedAuthor;
+use serenity::client::Context;
+use serenity::framework::standard::CommandResult;
+use serenity::model::id::GuildId;
+use serenity::prelude::TypeMapKey;
+use serenity::utils::{parse_channel, parse_role, Colour};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::constants::{
+    BUGTRACKER_NAME, DEFAULT_BUG_PREFIX, EOA_DISCORD, MAX_JOIN_INSTRUCTIONS_LENGTH,
+    TABLE_GUILD_SETTINGS, TERMITE_IMAGE,
+};
+use crate::database::bug_reports::BugStatus;
+use crate::get_database_conn;
+use crate::i18n::{LOCALE, LOCALES};
+
+/// Whether [`failure!`][crate::failure] replies are deleted after a few seconds
+pub const AUTO_DELETE_FAILURES: &str = "auto_delete_failures";
+/// Legacy query port of the guild's Minecraft server, used when the server
+/// [looks offline][crate::api::minecraft::get_server_status_with_fallback]
+pub const QUERY_PORT: &str = "query_port";
+/// Whether [`!online`][crate::commands::servers::online] leaves out the names
+/// of the online players
+pub const HIDE_PLAYER_NAMES: &str = "hide_player_names";
+/// Whether [`!serverlist`][crate::commands::servers::serverlist] shows each
+/// server in its own embed, with its favicon
+pub const SERVERLIST_ICONS: &str = "serverlist_icons";
+/// Whether the guild's Minecraft server IP is never stored: `!ip set` is
+/// refused, and [`!online`][crate::commands::servers::online] needs the IP
+/// every time
+pub const DISABLE_IP_PERSISTENCE: &str = "disable_ip_persistence";
+/// Whether the threads holding long [`!online`][crate::commands::servers::online]
+/// player lists are deleted after a while
+pub const DELETE_PLAYER_THREADS: &str = "delete_player_threads";
+/// How to connect to the guild's Minecraft server, shown by
+/// [`!online`][crate::commands::servers::online] when the server is online
+pub const JOIN_INSTRUCTIONS: &str = "join_instructions";
+/// Colour of the [`!online`][crate::commands::servers::online] embed when the
+/// server is online, stored as `RRGGBB` hex
+pub const ONLINE_COLOUR: &str = "online_colour";
+/// Colour of the [`!online`][crate::commands::servers::online] embed when the
+/// server is offline or unknown, stored as `RRGGBB` hex
+pub const OFFLINE_COLOUR: &str = "offline_colour";
+/// Channel where the weekly [bug digest][crate::commands::bug_reports::digest] is posted
+pub const DIGEST_CHANNEL: &str = "digest_channel";
+/// Channel where the status board is posted with the 📌 button of
+/// [`!online`][crate::commands::servers::online], instead of the channel of the button
+pub const STATUS_CHANNEL: &str = "status_channel";
+/// Message of the status board kept up to date by the
+/// [status poller][crate::commands::servers::start_status_poller], stored as
+/// `channel_id:message_id`. Not editable, set with the 📌 button.
+pub const STATUS_MESSAGE: &str = "status_message";
+
+/// Author name of the bugtracker embeds
+pub const BRANDING_NAME: &str = "branding_name";
+/// Author icon URL of the bugtracker embeds
+pub const BRANDING_ICON: &str = "branding_icon";
+
+/// Prefix of the bug ids, e.g. `EoA` in `EoA-12`
+pub const BUG_PREFIX: &str = "bug_prefix";
+
+/// Role allowed to [report bugs][crate::commands::bug_reports::track], in
+/// addition to the admins
+pub const BUG_REPORTER_ROLE: &str = "bug_reporter_role";
+
+/// Number of days after which resolved bugs are
+/// [closed automatically][crate::commands::bug_reports::start_auto_close]
+pub const AUTO_CLOSE_DAYS: &str = "auto_close_days";
+
+/// Whether the thread started on a bug report's message is archived when the
+/// bug is resolved or closed with the [`!bug`][crate::commands::bug_reports::bug]
+/// buttons: `false`, `true`, or `lock` to also lock it
+pub const ARCHIVE_BUG_THREADS: &str = "archive_bug_threads";
+
+/// Colour override for a bug status, stored as `RRGGBB` hex
+pub fn status_colour(status: BugStatus) -> String {
+    format!("status_colour.{}", status.as_str())
+}
+
+/// Marker override for a bug status, stored as an emoji
+pub fn status_marker(status: BugStatus) -> String {
+    format!("status_marker.{}", status.as_str())
+}
+
+/// Settings that can be edited with [`!settings`][crate::commands::admin::settings],
+/// along with the [`status_colour`] and [`status_marker`] keys
+pub const EDITABLE_SETTINGS: [&str; 18] = [
+    LOCALE,
+    AUTO_DELETE_FAILURES,
+    HIDE_PLAYER_NAMES,
+    SERVERLIST_ICONS,
+    DELETE_PLAYER_THREADS,
+    DISABLE_IP_PERSISTENCE,
+    QUERY_PORT,
+    JOIN_INSTRUCTIONS,
+    ONLINE_COLOUR,
+    OFFLINE_COLOUR,
+    DIGEST_CHANNEL,
+    STATUS_CHANNEL,
+    BRANDING_NAME,
+    BRANDING_ICON,
+    BUG_PREFIX,
+    AUTO_CLOSE_DAYS,
+    BUG_REPORTER_ROLE,
+    ARCHIVE_BUG_THREADS,
+];
+
+/// Returns the stored key of an [editable setting][EDITABLE_SETTINGS], or
+/// `None` if there is no such setting
+pub fn setting_key(key: &str) -> Option<String> {
+    let key = key.to_lowercase();
+    if EDITABLE_SETTINGS.contains(&key.as_str()) {
+        return Some(key);
+    }
+    if let Some(status) = key.strip_prefix("status_colour.") {
+        return status.parse::<BugStatus>().ok().map(status_colour);
+    }
+    key.strip_prefix("status_marker.")
+        .and_then(|status| status.parse::<BugStatus>().ok())
+        .map(status_marker)
+}
+
+/// Checks a value for a setting returned by [`setting_key`], and returns the
+/// value to store. The error completes the sentence "The value ...".
+pub fn validate_setting(key: &str, value: &str) -> Result<String, &'static str> {
+    let value = value.trim();
+    Ok(match key {
+        LOCALE => {
+            let locale = value.to_lowercase();
+            if !LOCALES.contains(&locale.as_str()) {
+                return Err("must be one of the available languages");
+            }
+            locale
+        }
+        AUTO_DELETE_FAILURES
+        | HIDE_PLAYER_NAMES
+        | SERVERLIST_ICONS
+        | DELETE_PLAYER_THREADS
+        | DISABLE_IP_PERSISTENCE => match value {
+            "on" | "true" | "enable" => "true".to_string(),
+            "off" | "false" | "disable" => "false".to_string(),
+            _ => return Err("must be either `on` or `off`"),
+        },
+        ARCHIVE_BUG_THREADS => match value {
+            "on" | "true" | "enable" => "true".to_string(),
+            "off" | "false" | "disable" => "false".to_string(),
+            "lock" => "lock".to_string(),
+            _ => return Err("must be either `on`, `off` or `lock`"),
+        },
+        QUERY_PORT => match value.parse::<u16>() {
+            Ok(port) if port != 0 => port.to_string(),
+            _ => return Err("must be a port number"),
+        },
+        DIGEST_CHANNEL | STATUS_CHANNEL => {
+            match value.parse::<u64>().ok().or_else(|| parse_channel(value)) {
+                Some(channel_id) => channel_id.to_string(),
+                None => return Err("must be a channel mention or id"),
+            }
+        }
+        BUG_REPORTER_ROLE => match value.parse::<u64>().ok().or_else(|| parse_role(value)) {
+            Some(role_id) => role_id.to_string(),
+            None => return Err("must be a role mention or id"),
+        },
+        JOIN_INSTRUCTIONS => {
+            if value.is_empty() || value.chars().count() > MAX_JOIN_INSTRUCTIONS_LENGTH {
+                return Err("must be between 1 and 512 characters");
+            }
+            value.to_string()
+        }
+        BRANDING_NAME => {
+            if value.is_empty() || value.chars().count() > 256 {
+                return Err("must be between 1 and 256 characters");
+            }
+            value.to_string()
+        }
+        BRANDING_ICON => {
+            if !value.starts_with("https://") && !value.starts_with("http://") {
+                return Err("must be an image URL");
+            }
+            value.to_string()
+        }
+        BUG_PREFIX => {
+            if !(1..=10).contains(&value.len())
+                || !value.starts_with(|c: char| c.is_ascii_alphabetic())
+                || !value.chars().all(|c| c.is_ascii_alphanumeric())
+            {
+                return Err("must be 1 to 10 letters or digits, starting with a letter");
+            }
+            value.to_string()
+        }
+        AUTO_CLOSE_DAYS => match value.parse::<u32>() {
+            Ok(days) if (1..=3650).contains(&days) => days.to_string(),
+            _ => return Err("must be a number of days between 1 and 3650"),
+        },
+        ONLINE_COLOUR | OFFLINE_COLOUR => validate_colour(value)?,
+        _ if key.starts_with("status_colour.") => validate_colour(value)?,
+        _ if key.starts_with("status_marker.") => validate_marker(value)?,
+        _ => return Err("cannot be changed, this is not a known setting"),
+    })
+}
+
+/// Whether a character is in one of the unicode blocks holding emoji
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c,
+        '\u{a9}'
+            | '\u{ae}'
+            | '\u{203c}'
+            | '\u{2049}'
+            | '\u{2122}'
+            | '\u{2139}'
+            | '\u{2194}'..='\u{21aa}'
+            | '\u{2300}'..='\u{23ff}'
+            | '\u{24c2}'
+            | '\u{25aa}'..='\u{25fe}'
+            | '\u{2600}'..='\u{27bf}'
+            | '\u{2934}'
+            | '\u{2935}'
+            | '\u{2b00}'..='\u{2bff}'
+            | '\u{3030}'
+            | '\u{303d}'
+            | '\u{3297}'
+            | '\u{3299}'
+            | '\u{1f000}'..='\u{1faff}'
+    )
+}
+
+/// Accepts a custom emoji, a `:shortcode:` or unicode emoji
+pub fn validate_marker(value: &str) -> Result<String, &'static str> {
+    let custom = value
+        .strip_prefix("<a:")
+        .or_else(|| value.strip_prefix("<:"))
+        .and_then(|emoji| emoji.strip_suffix('>'))
+        .and_then(|emoji| emoji.split_once(':'))
+        .map_or(false, |(name, id)| {
+            !name.is_empty()
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && id.parse::<u64>().is_ok()
+        });
+    let shortcode = value
+        .strip_prefix(':')
+        .and_then(|emoji| emoji.strip_suffix(':'))
+        .map_or(false, |name| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-'))
+        });
+    // emoji sequences (flags, skin tones, families, keycaps) have several characters
+    let unicode = value.chars().count() <= 16
+        && value.chars().any(|c| is_emoji(c) || c == '\u{20e3}')
+        && value.chars().all(|c| {
+            is_emoji(c)
+                || matches!(
+                    c,
+                    '\u{200d}' | '\u{fe0e}' | '\u{fe0f}' | '\u{20e3}' | '\u{e0020}'..='\u{e007f}'
+                )
+                || (matches!(c, '0'..='9' | '#' | '*') && value.contains('\u{20e3}'))
+        });
+    if custom || shortcode || unicode {
+        Ok(value.to_string())
+    } else {
+        Err("must be a single emoji")
+    }
+}
+
+fn validate_colour(value: &str) -> Result<String, &'static str> {
+    let hex = value.trim_start_matches('#');
+    if hex.len() != 6 || u32::from_str_radix(hex, 16).is_err() {
+        return Err("must be a `#RRGGBB` colour");
+    }
+    Ok(hex.to_uppercase())
+}
+
+/// Returns the colour stored in a setting, or `default` if it is not set
+pub async fn get_colour_setting(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    key: &str,
+    default: Colour,
+) -> Colour {
+    match guild_id {
+        Some(guild_id) => get_setting(ctx, guild_id, key)
+            .await
+            .and_then(|colour| u32::from_str_radix(&colour, 16).ok())
+            .map_or(default, Colour),
+        None => default,
+    }
+}
+
+/// Author of the bugtracker embeds, set per guild with
+/// [`!bug branding`][crate::commands::bug_reports::bug_branding]
+#[derive(Debug, Clone)]
+pub struct Branding {
+    pub name: String,
+    pub icon_url: String,
+}
+
+impl Branding {
+    pub fn author<'a>(&self, a: &'a mut CreateEmbedAuthor) -> &'a mut CreateEmbedAuthor {
+        a.name(&self.name).icon_url(&self.icon_url)
+    }
+}
+
+/// Returns the branding of the guild, defaulting to the Eras of Arda one
+pub async fn get_branding(ctx: &Context, guild_id: Option<GuildId>) -> Branding {
+    let (name, icon_url) = match guild_id {
+        Some(guild_id) => (
+            get_setting(ctx, guild_id, BRANDING_NAME).await,
+            get_setting(ctx, guild_id, BRANDING_ICON).await,
+        ),
+        None => (None, None),
+    };
+    Branding {
+        name: name.unwrap_or_else(|| BUGTRACKER_NAME.to_string()),
+        icon_url: icon_url.unwrap_or_else(|| TERMITE_IMAGE.to_string()),
+    }
+}
+
+/// Prefix of the bug ids, set per guild with
+/// [`!bug prefix`][crate::commands::bug_reports::bug_prefix]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BugPrefix(pub String);
+
+impl BugPrefix {
+    /// Renders a bug id with the prefix, e.g. `EoA-12`
+    pub fn id(&self, bug_id: u64) -> String {
+        format!("{}-{}", self.0, bug_id)
+    }
+
+    /// Parses a bug id, with or without the prefix. The prefix is matched
+    /// case-insensitively.
+    pub fn parse(&self, input: &str) -> Option<u64> {
+        let input = input.trim();
+        let prefix_len = self.0.len() + 1;
+        let number = match (input.get(..prefix_len), input.get(prefix_len..)) {
+            (Some(prefix), Some(number))
+                if prefix.ends_with('-')
+                    && prefix[..prefix_len - 1].eq_ignore_ascii_case(&self.0) =>
+            {
+                number
+            }
+            _ => input,
+        };
+        number.parse().ok()
+    }
+}
+
+/// Returns the bug id prefix of the guild. Outside of guilds (in DMs), the
+/// prefix of the EoA Discord is used, since that is where the bugs live.
+pub async fn get_bug_prefix(ctx: &Context, guild_id: Option<GuildId>) -> BugPrefix {
+    BugPrefix(
+        get_setting(ctx, guild_id.unwrap_or(EOA_DISCORD), BUG_PREFIX)
+            .await
+            .unwrap_or_else(|| DEFAULT_BUG_PREFIX.to_string()),
+    )
+}
+
+/// Parses a bug id using the bug id prefix of the guild
+pub async fn parse_bug_id(ctx: &Context, guild_id: Option<GuildId>, input: &str) -> Option<u64> {
+    get_bug_prefix(ctx, guild_id).await.parse(input)
+}
+
+#[derive(Debug, Clone)]
+pub struct SettingsCache(Arc<DashMap<GuildId, HashMap<String, String>>>);
+
+impl TypeMapKey for SettingsCache {
+    type Value = Self;
+}
+
+impl std::ops::Deref for SettingsCache {
+    type Target = DashMap<GuildId, HashMap<String, String>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Default for SettingsCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SettingsCache {
+    pub fn new() -> Self {
+        Self(Arc::new(DashMap::new()))
+    }
+}
+
+async fn get_settings_cache(ctx: &Context) -> SettingsCache {
+    let data_read = ctx.data.read().await;
+    data_read
+        .get::<SettingsCache>()
+        .expect("Expected a settings cache in the type map")
+        .clone()
+}
+
+pub async fn get_setting(ctx: &Context, server_id: GuildId, key: &str) -> Option<String> {
+    if let Some(settings) = get_settings_cache(ctx).await.get(&server_id) {
+        return settings.get(key).cloned();
+    }
+
+    get_all_settings(ctx, server_id).await?.remove(key)
+}
+
+/// Returns every setting stored for the guild, loading them in the cache if
+/// needed
+pub async fn get_all_settings(
+    ctx: &Context,
+    server_id: GuildId,
+) -> Option<HashMap<String, String>> {
+    let settings_cache = get_settings_cache(ctx).await;
+
+    if let Some(settings) = settings_cache.get(&server_id) {
+        return Some(settings.clone());
+    }
+
+    let mut conn = get_database_conn!(ctx);
+
+    let settings: HashMap<String, String> = conn
+        .exec_map(
+            format!(
+                "SELECT setting_key, setting_value FROM {} WHERE server_id = :server_id",
+                TABLE_GUILD_SETTINGS
+            ),
+            params! {
+                "server_id" => server_id.0,
+            },
+            |(key, value)| (key, value),
+        )
+        .await
+        .ok()?
+        .into_iter()
+        .collect();
+
+    settings_cache.insert(server_id, settings.clone());
+    Some(settings)
+}
+
+/// Returns the guilds that have a value for the setting, with that value
+pub async fn get_guilds_with_setting(ctx: &Context, key: &str) -> Option<Vec<(GuildId, String)>> {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_map(
+        format!(
+            "SELECT server_id, setting_value FROM {} WHERE setting_key = :key",
+            TABLE_GUILD_SETTINGS
+        ),
+        params! {
+            "key" => key,
+        },
+        |(server_id, value)| (GuildId(server_id), value),
+    )
+    .await
+    .ok()
+}
+
+pub async fn get_bool_setting(ctx: &Context, server_id: GuildId, key: &str) -> bool {
+    get_setting(ctx, server_id, key).await.as_deref() == Some("true")
+}
+
+pub async fn set_setting(
+    ctx: &Context,
+    server_id: GuildId,
+    key: &str,
+    value: &str,
+) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "REPLACE INTO {} (server_id, setting_key, setting_value) VALUES (:server_id, :key, :value)",
+            TABLE_GUILD_SETTINGS
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "key" => key,
+            "value" => value,
+        },
+    )
+    .await?;
+
+    // guilds that are not cached yet will load the new value on first read
+    if let Some(mut settings) = get_settings_cache(ctx).await.get_mut(&server_id) {
+        settings.insert(key.to_string(), value.to_string());
+    }
+
+    Ok(())
+}
+
+pub async fn remove_setting(ctx: &Context, server_id: GuildId, key: &str) -> CommandResult {
+    let mut conn = get_database_conn!(ctx);
+
+    conn.exec_drop(
+        format!(
+            "DELETE FROM {} WHERE server_id = :server_id AND setting_key = :key",
+            TABLE_GUILD_SETTINGS
+        ),
+        params! {
+            "server_id" => server_id.0,
+            "key" => key,
+        },
+    )
+    .await?;
+
+    if let Some(mut settings) = get_settings_cache(ctx).await.get_mut(&server_id) {
+        settings.remove(key);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{setting_key, validate_setting, BugPrefix};
+
+    #[test]
+    fn test_bug_prefix_parse() {
+        let prefix = BugPrefix("EoA".to_string());
+        assert_eq!(prefix.id(12), "EoA-12");
+        for input in ["12", "EoA-12", "eoa-12", "EOA-12", " EoA-12 "] {
+            assert_eq!(prefix.parse(input), Some(12), "{}", input);
+        }
+        for input in ["", "EoA-", "EoA12", "LOTR-12", "EoA--12", "-12", "EoA-x"] {
+            assert_eq!(prefix.parse(input), None, "{}", input);
+        }
+
+        let prefix = BugPrefix("LOTR".to_string());
+        assert_eq!(prefix.parse("lotr-7"), Some(7));
+        assert_eq!(prefix.parse("EoA-7"), None);
+        // multi-byte input shorter than the prefix doesn't panic
+        assert_eq!(prefix.parse("é"), None);
+        assert_eq!(prefix.parse("ééé-1"), None);
+    }
+
+    #[test]
+    fn test_validate_setting() {
+        assert_eq!(setting_key("LOCALE").as_deref(), Some("locale"));
+        assert_eq!(
+            setting_key("status_colour.med").as_deref(),
+            Some("status_colour.medium")
+        );
+        for key in ["prefix", "status_colour", "status_colour.unknown"] {
+            assert_eq!(setting_key(key), None, "{}", key);
+        }
+
+        let ok = |value: &str| Ok(value.to_string());
+        assert_eq!(validate_setting("locale", "FR"), ok("fr"));
+        assert_eq!(validate_setting("auto_delete_failures", "on"), ok("true"));
+        assert_eq!(validate_setting("hide_player_names", "off"), ok("false"));
+        assert_eq!(validate_setting("serverlist_icons", "on"), ok("true"));
+        assert_eq!(
+            validate_setting("delete_player_threads", "off"),
+            ok("false")
+        );
+        assert_eq!(validate_setting("query_port", "25565"), ok("25565"));
+        assert_eq!(validate_setting("archive_bug_threads", "lock"), ok("lock"));
+        assert_eq!(validate_setting("online_colour", "#0072b2"), ok("0072B2"));
+        assert_eq!(
+            validate_setting("join_instructions", " Use Forge 1.7.10 "),
+            ok("Use Forge 1.7.10")
+        );
+        assert_eq!(
+            validate_setting("digest_channel", "<#405091134327619587>"),
+            ok("405091134327619587")
+        );
+        assert_eq!(
+            validate_setting("branding_name", "  My Mod  "),
+            ok("My Mod")
+        );
+        assert_eq!(validate_setting("bug_prefix", "LOTR"), ok("LOTR"));
+        assert_eq!(validate_setting("auto_close_days", " 30 "), ok("30"));
+        assert_eq!(
+            validate_setting("bug_reporter_role", "<@&405091134327619587>"),
+            ok("405091134327619587")
+        );
+        assert_eq!(
+            validate_setting("status_colour.medium", "#ff00aa"),
+            ok("FF00AA")
+        );
+        assert_eq!(
+            setting_key("Status_Marker.critical").as_deref(),
+            Some("status_marker.critical")
+        );
+        for marker in [
+            "<:termite:839479605467152384>",
+            ":fire:",
+            "🔥",
+            "🏳️‍🌈",
+            "🇫🇷",
+            "👍🏽",
+            "#️⃣",
+            "⚠️",
+        ] {
+            assert_eq!(validate_setting("status_marker.high", marker), ok(marker));
+        }
+
+        for (key, value) in [
+            ("locale", "de"),
+            ("auto_delete_failures", "maybe"),
+            ("query_port", "70000"),
+            ("query_port", "0"),
+            ("join_instructions", ""),
+            ("digest_channel", "general"),
+            ("branding_name", ""),
+            ("branding_icon", "icon.png"),
+            ("bug_prefix", "1EoA"),
+            ("auto_close_days", "0"),
+            ("auto_close_days", "a month"),
+            ("bug_reporter_role", "@testers"),
+            ("archive_bug_threads", "maybe"),
+            ("offline_colour", "orange"),
+            ("status_colour.low", "red"),
+            ("status_marker.low", "red"),
+            ("status_marker.low", "<:termite:>"),
+            ("status_marker.low", "🔥 🔥"),
+            ("status_marker.low", "é"),
+            ("status_marker.low", "日本"),
+            ("status_marker.low", "1️"),
+            ("prefix", "?"),
+        ] {
+            assert!(validate_setting(key, value).is_err(), "{} {}", key, value);
+        }
+    }
+}
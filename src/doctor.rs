@@ -0,0 +1,248 @@
+//! Configuration checks, run by [`!doctor`][crate::commands::admin::doctor]
+//! and, if the `DOCTOR_ON_STARTUP` environment variable is set, when the bot
+//! first connects.
+//!
+//! Each check passes or fails with a hint to fix it. The schema check compares
+//! the database with `database_structure.sql`, so that a forgotten migration
+//! is caught before commands fail with cryptic query errors.
+
+use serenity::client::Context;
+use serenity::model::id::UserId;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::commands::bug_reports::TERMITE_EMOJI;
+use crate::constants::EOA_DISCORD;
+use crate::database::get_schema_columns;
+
+const DATABASE_STRUCTURE: &str = include_str!("../database_structure.sql");
+
+/// Environment variables read when the bot starts, or by some commands
+const REQUIRED_ENV_VARS: [&str; 10] = [
+    "DISCORD_TOKEN",
+    "APPLICATION_ID",
+    "DB_NAME",
+    "DB_USER",
+    "DB_PASSWORD",
+    "DB_SERVER",
+    "DB_PORT",
+    "GOOGLE_API_KEY",
+    "GOOGLE_CX",
+    "CURSEFORGE_API_KEY",
+];
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub passed: bool,
+    /// What was checked if it passed, how to fix it otherwise
+    pub details: String,
+}
+
+impl CheckResult {
+    fn pass(name: &'static str, details: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: true,
+            details: details.into(),
+        }
+    }
+
+    fn fail(name: &'static str, details: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: false,
+            details: details.into(),
+        }
+    }
+}
+
+/// Tables of a schema dump, with their columns
+fn expected_schema(sql: &str) -> Vec<(&str, Vec<&str>)> {
+    let mut tables = Vec::new();
+    let mut lines = sql.lines();
+    while let Some(line) = lines.next() {
+        let table = match line
+            .strip_prefix("CREATE TABLE `")
+            .and_then(|rest| rest.split_once('`'))
+        {
+            Some((table, _)) => table,
+            None => continue,
+        };
+        let columns = lines
+            .by_ref()
+            .take_while(|line| !line.starts_with(')'))
+            .filter_map(|line| line.trim_start().strip_prefix('`')?.split_once('`'))
+            .map(|(column, _)| column)
+            .collect();
+        tables.push((table, columns));
+    }
+    tables
+}
+
+/// Expected `table.column` names that are not in `actual`
+fn missing_columns(
+    expected: &[(&str, Vec<&str>)],
+    actual: &HashSet<(String, String)>,
+) -> Vec<String> {
+    expected
+        .iter()
+        .flat_map(|(table, columns)| {
+            columns
+                .iter()
+                .filter(|column| !actual.contains(&(table.to_string(), column.to_string())))
+                .map(move |column| format!("{}.{}", table, column))
+        })
+        .collect()
+}
+
+async fn check_database(ctx: &Context) -> Vec<CheckResult> {
+    let actual: HashSet<(String, String)> = match get_schema_columns(ctx).await {
+        Ok(columns) => columns.into_iter().collect(),
+        Err(e) => {
+            return vec![CheckResult::fail(
+                "Database",
+                format!(
+                    "{}. Check the DB_* environment variables and that the server is up.",
+                    e
+                ),
+            )]
+        }
+    };
+    let database = CheckResult::pass("Database", "Reachable");
+
+    let expected = expected_schema(DATABASE_STRUCTURE);
+    let missing = missing_columns(&expected, &actual);
+    let schema = if missing.is_empty() {
+        CheckResult::pass(
+            "Database schema",
+            format!("All {} tables are up to date", expected.len()),
+        )
+    } else {
+        let mut list = missing
+            .iter()
+            .take(10)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if missing.len() > 10 {
+            list.push_str(&format!(" and {} more", missing.len() - 10));
+        }
+        CheckResult::fail(
+            "Database schema",
+            format!(
+                "Missing {}. Apply the changes of `database_structure.sql` to the database.",
+                list
+            ),
+        )
+    };
+    vec![database, schema]
+}
+
+fn check_env_vars() -> CheckResult {
+    let missing: Vec<_> = REQUIRED_ENV_VARS
+        .iter()
+        .filter(|var| std::env::var(var).map_or(true, |value| value.trim().is_empty()))
+        .copied()
+        .collect();
+    if missing.is_empty() {
+        CheckResult::pass("Environment variables", "All set")
+    } else {
+        CheckResult::fail(
+            "Environment variables",
+            format!(
+                "{} not set. Add them to the bot's environment and restart it.",
+                missing.join(", ")
+            ),
+        )
+    }
+}
+
+async fn check_intents(ctx: &Context) -> CheckResult {
+    // listing members over HTTP needs the same privileged intent as the gateway
+    match EOA_DISCORD.members(ctx, Some(1), None::<UserId>).await {
+        Ok(_) => CheckResult::pass("Intents", "The Server Members intent is enabled"),
+        Err(e) => CheckResult::fail(
+            "Intents",
+            format!(
+                "Could not list the members of the LOTR Mod guild ({}). Enable the Server \
+Members intent in the Discord developer portal.",
+                e
+            ),
+        ),
+    }
+}
+
+async fn check_termite(ctx: &Context) -> CheckResult {
+    match EOA_DISCORD.emoji(ctx, TERMITE_EMOJI).await {
+        Ok(_) => CheckResult::pass("Termite emoji", "Found in the LOTR Mod guild"),
+        Err(e) => CheckResult::fail(
+            "Termite emoji",
+            format!(
+                "Not found in the LOTR Mod guild ({}). Upload it again and update `TERMITE_EMOJI`.",
+                e
+            ),
+        ),
+    }
+}
+
+pub async fn run_checks(ctx: &Context) -> Vec<CheckResult> {
+    let mut results = check_database(ctx).await;
+    results.push(check_env_vars());
+    results.push(check_intents(ctx).await);
+    results.push(check_termite(ctx).await);
+    results
+}
+
+static STARTUP_CHECKS_DONE: AtomicBool = AtomicBool::new(false);
+
+/// Prints the failed checks, once, if `DOCTOR_ON_STARTUP` is set
+pub fn run_startup_checks(ctx: Context) {
+    if std::env::var("DOCTOR_ON_STARTUP").is_err()
+        || STARTUP_CHECKS_DONE.swap(true, Ordering::Relaxed)
+    {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let failed: Vec<_> = run_checks(&ctx)
+            .await
+            .into_iter()
+            .filter(|result| !result.passed)
+            .collect();
+        if failed.is_empty() {
+            println!("All the configuration checks passed");
+        }
+        for result in failed {
+            println!(
+                "=== WARNING ===\nConfiguration check failed: {}\n{}\n=== END ===",
+                result.name, result.details
+            );
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_schema() {
+        let schema = expected_schema(DATABASE_STRUCTURE);
+        let (_, columns) = schema
+            .iter()
+            .find(|(table, _)| *table == "bug_reports")
+            .unwrap();
+        assert!(columns.contains(&"status_since"));
+        assert!(schema.iter().any(|(table, _)| *table == "mc_server_ip"));
+
+        let actual = [("mc_server_ip", "server_id"), ("mc_server_ip", "mc_ip")]
+            .iter()
+            .map(|(table, column)| (table.to_string(), column.to_string()))
+            .collect();
+        assert_eq!(
+            missing_columns(&[("mc_server_ip", vec!["server_id", "port"])], &actual),
+            ["mc_server_ip.port"]
+        );
+    }
+}
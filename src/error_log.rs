@@ -0,0 +1,180 @@
+//! Reports of unhandled errors to a log channel
+//!
+//! The command errors caught by the [`after_hook`][crate::check::after_hook],
+//! and the errors of the slash commands and buttons handled in the
+//! [event handler][crate::event_handler], all go through [`report_error`].
+//! Besides being printed, they are posted as a compact embed in the error log
+//! channel, if one is set with the `ERROR_LOG_CHANNEL` environment variable
+//! or the [`!errorlog`][crate::commands::admin::errorlog] command.
+//!
+//! At most [`ERROR_LOG_RATE_LIMIT`] errors are posted every
+//! [`ERROR_LOG_WINDOW`], so that an error storm doesn't spam the channel: the
+//! next report mentions how many errors were left out.
+
+use serenity::client::Context;
+use serenity::model::prelude::*;
+use serenity::prelude::TypeMapKey;
+use serenity::utils::{colours, parse_channel};
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::constants::{ERROR_LOG_RATE_LIMIT, ERROR_LOG_WINDOW};
+
+#[derive(Debug, Default)]
+struct State {
+    channel: Option<ChannelId>,
+    window_start: Option<Instant>,
+    reported: u32,
+    suppressed: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ErrorLog(Arc<Mutex<State>>);
+
+impl TypeMapKey for ErrorLog {
+    type Value = Self;
+}
+
+impl ErrorLog {
+    /// Reads the channel from the `ERROR_LOG_CHANNEL` environment variable, as
+    /// an id or a mention
+    pub fn from_env() -> Self {
+        let log = Self::default();
+        if let Ok(channel) = std::env::var("ERROR_LOG_CHANNEL") {
+            match channel
+                .trim()
+                .parse::<u64>()
+                .ok()
+                .or_else(|| parse_channel(channel.trim()))
+            {
+                Some(channel_id) => log.set_channel(Some(ChannelId(channel_id))),
+                None => println!(
+                    "The ERROR_LOG_CHANNEL environment variable is not a channel: {:?}",
+                    channel
+                ),
+            }
+        }
+        log
+    }
+
+    pub fn channel(&self) -> Option<ChannelId> {
+        self.0.lock().unwrap().channel
+    }
+
+    pub fn set_channel(&self, channel: Option<ChannelId>) {
+        self.0.lock().unwrap().channel = channel;
+    }
+
+    /// Whether a new error can be posted at `now`. If so, returns the channel
+    /// and the number of errors left out since the last report.
+    fn admit(&self, now: Instant) -> Option<(ChannelId, u32)> {
+        let mut state = self.0.lock().unwrap();
+        let channel = state.channel?;
+        match state.window_start {
+            Some(start) if now.duration_since(start) < ERROR_LOG_WINDOW => (),
+            _ => {
+                state.window_start = Some(now);
+                state.reported = 0;
+            }
+        }
+        if state.reported < ERROR_LOG_RATE_LIMIT {
+            state.reported += 1;
+            Some((channel, std::mem::take(&mut state.suppressed)))
+        } else {
+            state.suppressed += 1;
+            None
+        }
+    }
+}
+
+fn truncate(text: &str, max_len: usize) -> String {
+    if text.chars().count() > max_len {
+        text.chars()
+            .take(max_len - 3)
+            .chain("...".chars())
+            .collect()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Posts an unhandled error of `source` (a command name, or an interaction) in
+/// the error log channel, if any. The caller is still responsible for
+/// printing it.
+pub async fn report_error(
+    ctx: &Context,
+    source: &str,
+    guild_id: Option<GuildId>,
+    user: &User,
+    error: &dyn Display,
+) {
+    let error_log = match ctx.data.read().await.get::<ErrorLog>() {
+        Some(error_log) => error_log.clone(),
+        None => return,
+    };
+    let (channel, suppressed) = match error_log.admit(Instant::now()) {
+        Some(admitted) => admitted,
+        None => return,
+    };
+
+    let guild = match guild_id {
+        Some(guild_id) => match ctx.cache.guild_field(guild_id, |g| g.name.clone()) {
+            Some(name) => format!("{} ({})", name, guild_id),
+            None => guild_id.to_string(),
+        },
+        None => "Direct messages".to_string(),
+    };
+    let error = format!("```\n{}\n```", truncate(&error.to_string(), 1000));
+
+    if let Err(e) = channel
+        .send_message(ctx, |m| {
+            m.embed(|e| {
+                e.colour(colours::branding::RED);
+                e.title(format!("Error in `{}`", truncate(source, 200)));
+                e.field("Guild", guild, true);
+                e.field("User", format!("{} ({})", user.tag(), user.id), true);
+                e.field("Error", error, false);
+                if suppressed > 0 {
+                    e.footer(|f| {
+                        f.text(format!(
+                            "{} more error{} not reported",
+                            suppressed,
+                            if suppressed > 1 { "s" } else { "" }
+                        ))
+                    });
+                }
+                e
+            })
+            .allowed_mentions(|a| a.empty_parse())
+        })
+        .await
+    {
+        println!("Could not post to the error log channel {}: {}", channel, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_log_rate_limit() {
+        let log = ErrorLog::default();
+        let now = Instant::now();
+        // no channel: nothing is reported
+        assert_eq!(log.admit(now), None);
+
+        log.set_channel(Some(ChannelId(1)));
+        for _ in 0..ERROR_LOG_RATE_LIMIT {
+            assert_eq!(log.admit(now), Some((ChannelId(1), 0)));
+        }
+        assert_eq!(log.admit(now), None);
+        assert_eq!(log.admit(now), None);
+
+        // the next window reports the errors that were left out
+        let later = now + ERROR_LOG_WINDOW;
+        assert_eq!(log.admit(later), Some((ChannelId(1), 2)));
+        assert_eq!(log.admit(later), Some((ChannelId(1), 0)));
+    }
+}
@@ -9,6 +9,8 @@ use serenity::model::interactions::message_component::{
 use serenity::model::prelude::*;
 use serenity::utils::colours;
 
+use crate::check::in_maintenance;
+use crate::database::settings::get_bug_prefix;
 use crate::utils::InteractionEasyResponse;
 
 pub struct Handler;
@@ -16,10 +18,29 @@ pub struct Handler;
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
-        ctx.set_activity(Activity::playing(
-            "The Eras of Arda Community: creating addons and submods for the Lord of the Rings Mod.",
-        ))
-        .await;
+        // re-applied on every ready event, as reconnections reset it
+        crate::commands::admin::apply_presence(&ctx).await;
+
+        crate::commands::admin::start_presence_refresh(ctx.clone());
+        crate::doctor::run_startup_checks(ctx.clone());
+        crate::commands::bug_reports::start_weekly_digest(ctx.clone());
+        crate::commands::bug_reports::start_snooze_watcher(ctx.clone());
+        crate::commands::bug_reports::start_auto_close(ctx.clone());
+        crate::commands::servers::start_status_poller(ctx.clone());
+
+        if let Err(e) = crate::commands::bug_reports::register_bug_slash_command(&ctx).await {
+            println!(
+                "=== ERROR ===\nCould not register the /bug slash command: {}\n=== END ===",
+                e
+            );
+        }
+
+        if let Err(e) = crate::commands::help::register_help_slash_command(&ctx).await {
+            println!(
+                "=== ERROR ===\nCould not register the /help slash command: {}\n=== END ===",
+                e
+            );
+        }
 
         if let Err(e) = OWNER_ID
             .to_user(&ctx)
@@ -158,11 +179,68 @@ impl EventHandler for Handler {
             return;
         }
 
+        if let Err(e) =
+            crate::commands::bug_reports::handle_status_reply(&ctx, &message, guild_id).await
+        {
+            println!(
+                "=== ERROR ===\nCould not change a bug status from a reply\nError: {}\n=== END ===",
+                e
+            );
+            crate::error_log::report_error(
+                &ctx,
+                "bug status reply",
+                Some(guild_id),
+                &message.author,
+                &e,
+            )
+            .await;
+        }
+
         crate::qa_answers::handle_message(&ctx, &message, guild_id).await;
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::MessageComponent(
+        if let Interaction::ApplicationCommand(command) = &interaction {
+            if command.data.name == "bug" {
+                if let Err(e) =
+                    crate::commands::bug_reports::handle_bug_slash_command(&ctx, command).await
+                {
+                    println!(
+                        "=== ERROR ===\nError in slash command /bug by {} {:?}\nError: {}\n=== END ===",
+                        command.user.tag(),
+                        command.user.id,
+                        e
+                    );
+                    crate::error_log::report_error(
+                        &ctx,
+                        "/bug",
+                        command.guild_id,
+                        &command.user,
+                        &e,
+                    )
+                    .await;
+                }
+            } else if command.data.name == "help" {
+                if let Err(e) =
+                    crate::commands::help::handle_help_slash_command(&ctx, command).await
+                {
+                    println!(
+                        "=== ERROR ===\nError in slash command /help by {} {:?}\nError: {}\n=== END ===",
+                        command.user.tag(),
+                        command.user.id,
+                        e
+                    );
+                    crate::error_log::report_error(
+                        &ctx,
+                        "/help",
+                        command.guild_id,
+                        &command.user,
+                        &e,
+                    )
+                    .await;
+                }
+            }
+        } else if let Interaction::MessageComponent(
             component_interaction @ MessageComponentInteraction {
                 user,
                 data:
@@ -175,11 +253,56 @@ impl EventHandler for Handler {
             },
         ) = &interaction
         {
-            if let Some(bug_id) = custom_id
+            if custom_id.starts_with(crate::commands::bug_reports::BUGLIST_CUSTOM_ID_PREFIX) {
+                if let Err(e) = crate::commands::bug_reports::handle_buglist_interaction(
+                    &ctx,
+                    component_interaction,
+                )
+                .await
+                {
+                    println!(
+                        "=== ERROR ===\nCould not handle bug list navigation {}\nError: {}\n=== END ===",
+                        custom_id, e
+                    );
+                    crate::error_log::report_error(
+                        &ctx,
+                        "bug list navigation",
+                        component_interaction.guild_id,
+                        user,
+                        &e,
+                    )
+                    .await;
+                }
+            } else if custom_id == crate::commands::servers::STATUS_PIN_CUSTOM_ID {
+                if let Err(e) =
+                    crate::commands::servers::handle_status_pin(&ctx, component_interaction).await
+                {
+                    println!(
+                        "=== ERROR ===\nCould not pin the server status\nError: {}\n=== END ===",
+                        e
+                    );
+                    crate::error_log::report_error(
+                        &ctx,
+                        "server status pin",
+                        component_interaction.guild_id,
+                        user,
+                        &e,
+                    )
+                    .await;
+                }
+            } else if (custom_id.starts_with("bug_unsubscribe__")
+                || custom_id.starts_with("bug_subscribe__"))
+                && in_maintenance(&ctx).await
+            {
+                component_interaction
+                    .say_ephemeral(&ctx, ":x: The bot is in maintenance mode.")
+                    .await;
+            } else if let Some(bug_id) = custom_id
                 .strip_prefix("bug_unsubscribe__")
                 .map(|s| s.parse::<u64>().ok())
                 .flatten()
             {
+                let prefix = get_bug_prefix(&ctx, component_interaction.guild_id).await;
                 if crate::database::bug_reports::is_notified_user(&ctx, bug_id, user.id).await
                     != Some(true)
                 {
@@ -187,10 +310,10 @@ impl EventHandler for Handler {
                         .say_ephemeral(
                             &ctx,
                             format!(
-                                ":x: You are not subscribed to bug EoA-{}.
+                                ":x: You are not subscribed to bug {}.
 
 To see all your active notifications type  `!bug notifications`",
-                                bug_id
+                                prefix.id(bug_id)
                             ),
                         )
                         .await
@@ -199,10 +322,10 @@ To see all your active notifications type  `!bug notifications`",
                 {
                     println!(
                         "=== ERROR ===\nCould not remove {} {:?} \
-from EoA-{} notifications\nError: {}\n=== END ===",
+from {} notifications\nError: {}\n=== END ===",
                         user.tag(),
                         user.id,
-                        bug_id,
+                        prefix.id(bug_id),
                         e
                     );
                 } else {
@@ -210,10 +333,10 @@ from EoA-{} notifications\nError: {}\n=== END ===",
                         .say_ephemeral(
                             &ctx,
                             format!(
-                                "You have successfully been unsubscribed from bug EoA-{}.
+                                "You have successfully been unsubscribed from bug {}.
 
 To see all your active notifications type  `!bug notifications`",
-                                bug_id
+                                prefix.id(bug_id)
                             ),
                         )
                         .await;
@@ -223,6 +346,7 @@ To see all your active notifications type  `!bug notifications`",
                 .map(|s| s.parse::<u64>().ok())
                 .flatten()
             {
+                let prefix = get_bug_prefix(&ctx, component_interaction.guild_id).await;
                 if crate::database::bug_reports::is_notified_user(&ctx, bug_id, user.id).await
                     != Some(false)
                 {
@@ -230,22 +354,27 @@ To see all your active notifications type  `!bug notifications`",
                         .say_ephemeral(
                             &ctx,
                             format!(
-                                ":x: You are already subscribed to bug EoA-{}.
+                                ":x: You are already subscribed to bug {}.
 
 To see all your active notifications type  `!bug notifications`",
-                                bug_id
+                                prefix.id(bug_id)
                             ),
                         )
                         .await
-                } else if let Err(e) =
-                    crate::database::bug_reports::add_notified_user(&ctx, bug_id, user.id).await
+                } else if let Err(e) = crate::database::bug_reports::add_notified_user(
+                    &ctx,
+                    bug_id,
+                    component_interaction.guild_id.unwrap_or(EOA_DISCORD),
+                    user.id,
+                )
+                .await
                 {
                     println!(
                         "=== ERROR ===\nCould not add {} {:?} \
-to EoA-{} notifications\nError: {}\n=== END ===",
+to {} notifications\nError: {}\n=== END ===",
                         user.tag(),
                         user.id,
-                        bug_id,
+                        prefix.id(bug_id),
                         e
                     );
                 } else {
@@ -253,10 +382,10 @@ to EoA-{} notifications\nError: {}\n=== END ===",
                         .say_ephemeral(
                             &ctx,
                             format!(
-                                "You have successfully been subscribed to bug EoA-{}.
+                                "You have successfully been subscribed to bug {}.
 
 To see all your active notifications type  `!bug notifications`",
-                                bug_id
+                                prefix.id(bug_id)
                             ),
                         )
                         .await;
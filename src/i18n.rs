@@ -0,0 +1,183 @@
+//! Translations for the most common bot responses
+//!
+//! Each guild can pick a [locale][LOCALE] using the
+//! [`!locale`][crate::commands::admin::locale] command. Responses are
+//! translated with the [`t!`][crate::t] macro:
+//! ```ignore
+//! failure!(ctx, msg, t!(ctx, msg, "bug.not_found", prefix.id(bug_id)));
+//! ```
+//! Translation strings use `{}` placeholders, filled in order with the macro
+//! arguments. Keys that are missing from a locale, and unknown locales, fall
+//! back to English.
+
+use serenity::client::Context;
+use serenity::model::id::GuildId;
+use std::fmt::Display;
+
+use crate::database::settings::get_setting;
+
+/// Guild setting holding the locale code
+pub const LOCALE: &str = "locale";
+
+/// Locale used when a guild has none set, or when a translation is missing
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Supported locale codes
+pub const LOCALES: [&str; 2] = ["en", "fr"];
+
+fn en(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "ip.not_set" => {
+            "No registered Minecraft IP for this server. Set one using  `!ip set <server ip>`."
+        }
+        "online.online" => "Server online!",
+        "online.offline" => "Server offline...",
+        "online.unknown_host" => "Unknown server",
+        "online.unknown_host.details" => "Couldn't resolve that host, check the IP for typos.",
+        "online.unavailable" => "Server status unavailable",
+        "online.unavailable.details" => {
+            "The status API is having trouble, try again in a few minutes."
+        }
+        "online.players" => "Players: {}/{}",
+        "online.last_update" => "Last update",
+        "bug.missing_id" => "The first argument must be a bug id.",
+        "bug.invalid_id" => "`{}` is not a valid bug id!",
+        "bug.not_found" => "The bug {} does not exist!",
+        "bug.archived" => "{} is archived and can't be changed.",
+        "bug.missing_title" => "You must specify a new title for {}",
+        "bug.renamed" => "Successfully changed the title of {} to **{}**",
+        "bug.resolved" => "{} has been marked as resolved.",
+        "bug.closed" => "{} has been marked as closed.",
+        "bug.status_changed" => "Status changed for {} from `{}` to `{}`!",
+        "bug.submit_failed" => "Could not submit the bug report!",
+        "db.unavailable" => "The database is unavailable, try again in a few minutes.",
+        "db.busy" => "The bot is busy right now, try again in a moment.",
+        _ => return None,
+    })
+}
+
+fn fr(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "ip.not_set" => {
+            "Aucune IP Minecraft enregistrée pour ce serveur. Ajoutez-en une avec  \
+`!ip set <ip du serveur>`."
+        }
+        "online.online" => "Serveur en ligne !",
+        "online.offline" => "Serveur hors ligne...",
+        "online.unknown_host" => "Serveur inconnu",
+        "online.unknown_host.details" => {
+            "Impossible de résoudre cet hôte, vérifiez que l'IP est correcte."
+        }
+        "online.unavailable" => "Statut du serveur indisponible",
+        "online.unavailable.details" => {
+            "L'API de statut rencontre des problèmes, réessayez dans quelques minutes."
+        }
+        "online.players" => "Joueurs : {}/{}",
+        "online.last_update" => "Dernière mise à jour",
+        "bug.missing_id" => "Le premier argument doit être un identifiant de bug.",
+        "bug.invalid_id" => "`{}` n'est pas un identifiant de bug valide !",
+        "bug.not_found" => "Le bug {} n'existe pas !",
+        "bug.archived" => "{} est archivé et ne peut plus être modifié.",
+        "bug.missing_title" => "Vous devez indiquer un nouveau titre pour {}",
+        "bug.renamed" => "Le titre de {} a bien été changé en **{}**",
+        "bug.resolved" => "{} a été marqué comme résolu.",
+        "bug.closed" => "{} a été marqué comme fermé.",
+        "bug.status_changed" => "Statut de {} changé de `{}` à `{}` !",
+        "bug.submit_failed" => "Impossible d'enregistrer le rapport de bug !",
+        "db.unavailable" => "La base de données est indisponible, réessayez dans quelques minutes.",
+        "db.busy" => "Le bot est occupé pour le moment, réessayez dans un instant.",
+        _ => return None,
+    })
+}
+
+/// Translates `key` in `locale`, replacing each `{}` placeholder with the
+/// next argument.
+pub fn translate(locale: &str, key: &str, args: &[&dyn Display]) -> String {
+    let template = match locale {
+        "fr" => fr(key),
+        _ => None,
+    }
+    .or_else(|| en(key));
+
+    let template = match template {
+        Some(template) => template,
+        None => {
+            println!(
+                "=== ERROR ===\nMissing translation key {}\n=== END ===",
+                key
+            );
+            return key.to_string();
+        }
+    };
+
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    let mut result = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        if let Some(arg) = args.next() {
+            result.push_str(&arg.to_string());
+        }
+        result.push_str(part);
+    }
+    result
+}
+
+pub async fn get_locale(ctx: &Context, guild_id: Option<GuildId>) -> String {
+    match guild_id {
+        Some(guild_id) => get_setting(ctx, guild_id, LOCALE)
+            .await
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string()),
+        None => DEFAULT_LOCALE.to_string(),
+    }
+}
+
+/// Translates a response key in the locale of the message's guild.
+///
+/// See the [module documentation][crate::i18n] for details.
+#[macro_export]
+macro_rules! t {
+    ($ctx:ident, $msg:ident, $key:literal $(, $arg:expr)* $(,)?) => {
+        $crate::i18n::translate(
+            &$crate::i18n::get_locale($ctx, $msg.guild_id).await,
+            $key,
+            &[$(&$arg as &dyn std::fmt::Display),*],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::translate;
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(
+            translate("en", "online.players", &[&3, &20]),
+            "Players: 3/20"
+        );
+        assert_eq!(
+            translate("fr", "online.players", &[&3, &20]),
+            "Joueurs : 3/20"
+        );
+        // unknown locales fall back to English
+        assert_eq!(
+            translate("xx", "bug.not_found", &[&"EoA-12"]),
+            "The bug EoA-12 does not exist!"
+        );
+        // unknown keys are returned as is
+        assert_eq!(translate("fr", "missing.key", &[]), "missing.key");
+        assert_eq!(
+            translate("en", "bug.invalid_id", &[&"EoA-x"]),
+            "`EoA-x` is not a valid bug id!"
+        );
+        // the arguments fill the placeholders in order
+        assert_eq!(
+            translate("fr", "bug.renamed", &[&"EoA-12", &"Lits cassés"]),
+            "Le titre de EoA-12 a bien été changé en **Lits cassés**"
+        );
+        assert_eq!(
+            translate("en", "bug.status_changed", &[&"EoA-12", &"low", &"high"]),
+            "Status changed for EoA-12 from `low` to `high`!"
+        );
+    }
+}
@@ -13,43 +13,60 @@ pub mod check;
 pub mod commands;
 pub mod constants;
 pub mod database;
+pub mod doctor;
+pub mod error_log;
 pub mod event_handler;
+pub mod i18n;
+pub mod metrics;
 pub mod qa_answers;
 pub mod role_cache;
+pub mod shutdown;
 pub mod utils;
 
 use mysql_async::OptsBuilder;
 use serenity::client::ClientBuilder;
-use serenity::framework::standard::{macros::group, StandardFramework};
+use serenity::framework::standard::{macros::group, CommandGroup, StandardFramework};
 use serenity::http::client::Http;
 use serenity::model::gateway::GatewayIntents;
 use serenity::prelude::*;
 use std::env;
 use std::sync::Arc;
 
-use api::ReqwestClient;
-use check::{after_hook, dispatch_error_hook};
+use api::{minecraft::ServerStatusRequests, proxy::Proxy, ReqwestClient};
+use check::{
+    after_hook, before_hook, dispatch_error_hook, not_owner_hook, CommandGate, Maintenance,
+};
 use commands::{
     admin::*, announcements::*, bug_reports::*, custom_commands::*, general::*, help::*, meme::*,
     qa_setup::*, roles::*, servers::*, wiki::*,
 };
-use constants::{BOT_ID, OWNER_ID};
+use constants::{BOT_ID, OWNER_ID, SHUTDOWN_TIMEOUT};
 use database::{
     config::{get_prefix, PrefixCache},
     qa_data::QaChannelsCache,
-    DatabasePool,
+    settings::SettingsCache,
+    BusyNotices, DatabasePool,
 };
+use error_log::ErrorLog;
 use event_handler::Handler;
 use role_cache::RoleCache;
+use shutdown::Shutdown;
 
 #[group]
+#[description = "General commands"]
 #[commands(
-    help, renewed, legacy, curseforge, prefix, forge, coremod, invite, server_ip, online, donate, facebook,
-    discord, user_info, role, listroles
+    help, renewed, legacy, curseforge, prefix, forge, coremod, invite, donate, facebook, discord,
+    user_info, role, listroles
 )]
 struct General;
 
 #[group]
+#[description = "Minecraft server commands"]
+#[commands(server_ip, online, compare, serverlist, uptime)]
+struct Servers;
+
+#[group]
+#[description = "Q&A commands"]
 #[commands(
     qa_moderator,
     qa_answer_channel,
@@ -63,28 +80,62 @@ struct General;
 struct QA;
 
 #[group]
+#[description = "Meme commands"]
 #[commands(floppa, aeugh, dagohon, colour)]
 struct Meme;
 
 #[group]
+#[description = "Wiki commands"]
 #[commands(wiki, lotrmod, tolkien, minecraft)]
 struct Wiki;
 
 #[group]
-#[commands(track, buglist, bug, resolve)]
+#[description = "Bugtracker commands"]
+#[commands(track, buglist, bug, resolve, digest)]
 struct BugReports;
 
 #[group]
+#[description = "Admin commands"]
 #[commands(
-    admin, floppadd, blacklist, announce, floppadmin, listguilds, define, shutdown
+    admin,
+    floppadd,
+    blacklist,
+    announce,
+    autodelete,
+    locale,
+    settings,
+    perms,
+    floppadmin,
+    listguilds,
+    define,
+    maintenance,
+    presence,
+    errorlog,
+    doctor,
+    shutdown
 )]
 struct Moderation;
 
 #[group]
+#[description = "Custom commands"]
 #[commands(custom_command)]
 #[default_command(custom_command)]
 struct CustomCommand;
 
+/// Command groups, in the order the framework tries them. Also listed by
+/// [`!help all`][commands::help::help_all].
+pub static COMMAND_GROUPS: [&CommandGroup; 8] = [
+    &MEME_GROUP,
+    &WIKI_GROUP,
+    &MODERATION_GROUP,
+    &BUGREPORTS_GROUP,
+    &GENERAL_GROUP,
+    &SERVERS_GROUP,
+    &QA_GROUP,
+    // Must go last
+    &CUSTOMCOMMAND_GROUP,
+];
+
 #[derive(Clone)]
 pub struct FrameworkKey(Arc<StandardFramework>);
 
@@ -114,6 +165,14 @@ async fn main() {
     // get environment variables for bot login
     // Discord token & application id
     let token = env::var("DISCORD_TOKEN").expect("Expected a token in the environment");
+    if let Err(e) = utils::check_token_format(&token) {
+        println!(
+            "The DISCORD_TOKEN environment variable is malformed: the token {}",
+            e
+        );
+        std::process::exit(1);
+    }
+    let token = token.strip_prefix("Bot ").unwrap_or(&token).to_string();
     let application_id: u64 = env::var("APPLICATION_ID")
         .expect("Expected an application id in the environment")
         .parse()
@@ -136,17 +195,41 @@ async fn main() {
         OptsBuilder::default()
             .user(Some(db_user))
             .db_name(Some(db_name))
-            .ip_or_hostname(db_server)
+            .ip_or_hostname(db_server.clone())
             .pass(Some(db_password))
             .tcp_port(db_port),
     );
 
-    // reqwest client for API calls
-    let reqwest_client = ReqwestClient::new();
+    // fail now rather than on the first command if the credentials are wrong
+    if let Err(e) = pool.get_conn().await {
+        println!(
+            "Couldn't connect to the database at {}:{}: {}",
+            db_server, db_port, e
+        );
+        std::process::exit(1);
+    }
+
+    // reqwest client for API calls, through the optional proxy
+    let proxy = match Proxy::from_env() {
+        Ok(proxy) => proxy,
+        Err(e) => {
+            println!("The PROXY_URL environment variable is invalid: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if let Some(proxy) = &proxy {
+        println!("Sending outbound requests through the proxy {}", proxy);
+    }
+    let reqwest_client = ReqwestClient::with_proxy(proxy);
 
     let role_cache = RoleCache::new();
     let prefix_cache = PrefixCache::new();
+    let settings_cache = SettingsCache::new();
     let qa_channels_cache = QaChannelsCache::new();
+    let server_status_requests = ServerStatusRequests::new();
+    let command_gate = CommandGate::from_env();
+    let shutdown = Shutdown::new();
+    let error_log = ErrorLog::from_env();
 
     // initialize bot framework
     let framework = StandardFramework::new()
@@ -167,18 +250,18 @@ async fn main() {
         })
         // failed checks handler
         .on_dispatch_error(dispatch_error_hook)
-        .after(after_hook)
-        // command groups
-        .group(&MEME_GROUP)
-        .group(&WIKI_GROUP)
-        .group(&MODERATION_GROUP)
-        .group(&BUGREPORTS_GROUP)
-        .group(&GENERAL_GROUP)
-        .group(&QA_GROUP)
-        // Must go last
-        .group(&CUSTOMCOMMAND_GROUP)
+        // commands disabled through the environment
+        .before(before_hook)
+        .after(after_hook);
+    // command groups
+    let framework = COMMAND_GROUPS
+        .iter()
+        .fold(framework, |framework, &group| framework.group(group))
         // rate limiting some commands
         .bucket("basic", |b| b.delay(2).time_span(10).limit(3))
+        .await
+        // at most 5 new bug reports per minute, to limit damage from a compromised account
+        .bucket("track", |b| b.time_span(60).limit(5).check(not_owner_hook))
         .await;
 
     let mut http = Http::new(reqwest_client.inner(), &format!("Bot {}", &token));
@@ -192,10 +275,18 @@ async fn main() {
         .framework_arc(framework.as_arc())
         .intents(GatewayIntents::non_privileged() | GatewayIntents::GUILD_MEMBERS)
         .type_map_insert::<DatabasePool>(pool)
+        .type_map_insert::<BusyNotices>(BusyNotices::new())
         .type_map_insert::<ReqwestClient>(reqwest_client)
         .type_map_insert::<RoleCache>(role_cache)
         .type_map_insert::<PrefixCache>(prefix_cache)
+        .type_map_insert::<SettingsCache>(settings_cache)
         .type_map_insert::<QaChannelsCache>(qa_channels_cache)
+        .type_map_insert::<ServerStatusRequests>(server_status_requests)
+        .type_map_insert::<CommandGate>(command_gate)
+        .type_map_insert::<Maintenance>(false)
+        .type_map_insert::<Presence>(Presence::Default)
+        .type_map_insert::<ErrorLog>(error_log)
+        .type_map_insert::<Shutdown>(shutdown.clone())
         .type_map_insert::<FrameworkKey>(framework)
         .await
         .expect("Error creating client");
@@ -204,9 +295,13 @@ async fn main() {
         // Ctrl+C listener
 
         let shard_manager = client.shard_manager.clone();
+        let shutdown = shutdown.clone();
         tokio::spawn(async move {
             tokio::signal::ctrl_c().await.unwrap();
             println!("Shutting down...");
+            if !shutdown.shutdown(SHUTDOWN_TIMEOUT).await {
+                println!("Some background work did not complete in time");
+            }
             shard_manager.clone().lock().await.shutdown_all().await;
         });
     }
@@ -223,10 +318,15 @@ async fn main() {
                 .await
                 .unwrap();
             println!("Shutting down...");
+            if !shutdown.shutdown(SHUTDOWN_TIMEOUT).await {
+                println!("Some background work did not complete in time");
+            }
             shard_manager.lock().await.shutdown_all().await;
         });
     }
 
+    metrics::start_metrics_server();
+
     // start listening for events by starting a single shard
     if let Err(why) = client.start().await {
         // basic error logging
@@ -0,0 +1,165 @@
+//! Prometheus metrics for the monitoring stack
+//!
+//! Only recorded when the bot is built with the `metrics` feature. If the
+//! `METRICS_PORT` environment variable is also set, [`start_metrics_server`]
+//! serves them in the Prometheus text format on that port. Without the
+//! feature, all the functions of this module do nothing.
+//!
+//! Timers observe the elapsed time when they are dropped:
+//! ```ignore
+//! let _timer = crate::metrics::time_api("minecraft");
+//! ```
+
+#[cfg(feature = "metrics")]
+mod imp {
+    use once_cell::sync::Lazy;
+    use prometheus::{
+        register_histogram, register_histogram_vec, register_int_counter_vec, register_int_gauge,
+        Encoder, Histogram, HistogramTimer, HistogramVec, IntCounterVec, IntGauge, TextEncoder,
+    };
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    static COMMANDS: Lazy<IntCounterVec> = Lazy::new(|| {
+        register_int_counter_vec!(
+            "bot_commands_total",
+            "Command invocations, by command and result",
+            &["command", "result"]
+        )
+        .expect("Could not register the command counter")
+    });
+
+    static DB_CONNECTION: Lazy<Histogram> = Lazy::new(|| {
+        register_histogram!(
+            "bot_db_connection_seconds",
+            "Time to get a connection from the database pool"
+        )
+        .expect("Could not register the database histogram")
+    });
+
+    static API_REQUESTS: Lazy<HistogramVec> = Lazy::new(|| {
+        register_histogram_vec!(
+            "bot_api_request_seconds",
+            "Duration of the external API requests, by API",
+            &["api"]
+        )
+        .expect("Could not register the API histogram")
+    });
+
+    static COLLECTORS: Lazy<IntGauge> = Lazy::new(|| {
+        register_int_gauge!(
+            "bot_active_collectors",
+            "Interaction and reply collectors currently waiting"
+        )
+        .expect("Could not register the collector gauge")
+    });
+
+    pub fn record_command(command: &str, success: bool) {
+        COMMANDS
+            .with_label_values(&[command, if success { "ok" } else { "error" }])
+            .inc();
+    }
+
+    pub fn time_db_connection() -> HistogramTimer {
+        DB_CONNECTION.start_timer()
+    }
+
+    pub fn time_api(api: &str) -> HistogramTimer {
+        API_REQUESTS.with_label_values(&[api]).start_timer()
+    }
+
+    /// Decrements the active collector gauge when dropped
+    pub struct CollectorGuard(());
+
+    impl Drop for CollectorGuard {
+        fn drop(&mut self) {
+            COLLECTORS.dec();
+        }
+    }
+
+    pub fn collector_started() -> CollectorGuard {
+        COLLECTORS.inc();
+        CollectorGuard(())
+    }
+
+    /// Serves the metrics on `METRICS_PORT`, if it is set to a valid port.
+    /// Every request gets the metrics, whatever its path.
+    pub fn start_metrics_server() {
+        let port: u16 = match std::env::var("METRICS_PORT").map(|port| port.parse()) {
+            Ok(Ok(port)) => port,
+            Ok(Err(e)) => {
+                println!(
+                    "=== ERROR ===\nMETRICS_PORT is not a valid port, metrics are not served: {}\n=== END ===",
+                    e
+                );
+                return;
+            }
+            Err(_) => return,
+        };
+
+        tokio::spawn(async move {
+            let listener = match TcpListener::bind(("0.0.0.0", port)).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    println!(
+                        "=== ERROR ===\nCould not bind the metrics server: {}\n=== END ===",
+                        e
+                    );
+                    return;
+                }
+            };
+            println!("Serving metrics on port {}", port);
+
+            loop {
+                let mut stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(_) => continue,
+                };
+                tokio::spawn(async move {
+                    // the request itself doesn't matter
+                    let mut buf = [0; 1024];
+                    let _ = stream.read(&mut buf).await;
+
+                    let mut body = Vec::new();
+                    let encoder = TextEncoder::new();
+                    if encoder.encode(&prometheus::gather(), &mut body).is_err() {
+                        return;
+                    }
+                    let header = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+Connection: close\r\n\r\n",
+                        encoder.format_type(),
+                        body.len()
+                    );
+                    let _ = stream.write_all(header.as_bytes()).await;
+                    let _ = stream.write_all(&body).await;
+                });
+            }
+        });
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+mod imp {
+    pub struct Timer;
+
+    pub struct CollectorGuard;
+
+    pub fn record_command(_: &str, _: bool) {}
+
+    pub fn time_db_connection() -> Timer {
+        Timer
+    }
+
+    pub fn time_api(_: &str) -> Timer {
+        Timer
+    }
+
+    pub fn collector_started() -> CollectorGuard {
+        CollectorGuard
+    }
+
+    pub fn start_metrics_server() {}
+}
+
+pub use imp::*;
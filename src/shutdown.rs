@@ -0,0 +1,160 @@
+//! Coordinated shutdown of the background tasks
+//!
+//! On Ctrl+C, SIGTERM or [`!shutdown`][crate::commands::admin::shutdown],
+//! [`Shutdown::shutdown`] stops the background tasks from starting new work,
+//! then waits for the work already in flight (database writes, notification
+//! DMs) to complete before the shards are shut down.
+//!
+//! Background tasks wait on their interval through [`Shutdown::tick`], and
+//! one-off tasks hold a [`WorkGuard`] while they run:
+//! ```ignore
+//! let _work = match get_shutdown(&ctx).await.track() {
+//!     Some(work) => work,
+//!     None => return,
+//! };
+//! ```
+
+use serenity::client::Context;
+use serenity::prelude::TypeMapKey;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Interval;
+
+#[derive(Debug, Default)]
+struct State {
+    started: AtomicBool,
+    started_notify: Notify,
+    in_flight: AtomicUsize,
+    idle_notify: Notify,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Shutdown(Arc<State>);
+
+impl TypeMapKey for Shutdown {
+    type Value = Self;
+}
+
+/// Marks some work as in flight until it is dropped
+#[derive(Debug)]
+pub struct WorkGuard(Arc<State>);
+
+impl Drop for WorkGuard {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.idle_notify.notify_waiters();
+        }
+    }
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.0.started.load(Ordering::Acquire)
+    }
+
+    /// Completes once the shutdown has started
+    pub async fn started(&self) {
+        loop {
+            // registered before the check, so that the notification can't be missed
+            let notified = self.0.started_notify.notified();
+            if self.is_started() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Tracks some new work, or returns `None` if the shutdown has started and
+    /// the work should not be done.
+    pub fn track(&self) -> Option<WorkGuard> {
+        self.0.in_flight.fetch_add(1, Ordering::AcqRel);
+        let guard = WorkGuard(self.0.clone());
+        // checked after the increment, so that `shutdown` waits for this work
+        // or the work is dropped
+        if self.is_started() {
+            return None;
+        }
+        Some(guard)
+    }
+
+    /// Waits for the next tick of `interval`, and tracks the work done for it.
+    /// Returns `None` once the shutdown has started, the task should then stop.
+    pub async fn tick(&self, interval: &mut Interval) -> Option<WorkGuard> {
+        tokio::select! {
+            _ = interval.tick() => self.track(),
+            _ = self.started() => None,
+        }
+    }
+
+    /// Stops new work from starting, and waits at most `timeout` for the work
+    /// in flight. Returns whether all the work completed in time.
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.0.started.store(true, Ordering::Release);
+        self.0.started_notify.notify_waiters();
+
+        tokio::time::timeout(timeout, async {
+            loop {
+                let notified = self.0.idle_notify.notified();
+                if self.0.in_flight.load(Ordering::Acquire) == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+}
+
+pub async fn get_shutdown(ctx: &Context) -> Shutdown {
+    ctx.data
+        .read()
+        .await
+        .get::<Shutdown>()
+        .expect("Expected a shutdown handle in the type map")
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown() {
+        let shutdown = Shutdown::new();
+        let work = shutdown.track().unwrap();
+
+        let waiter = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.started().await }
+        });
+
+        let mut draining = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.shutdown(Duration::from_secs(5)).await }
+        });
+        waiter.await.unwrap();
+        // no new work once the shutdown has started
+        assert!(shutdown.track().is_none());
+        // the work in flight is waited for
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), &mut draining)
+                .await
+                .is_err()
+        );
+
+        drop(work);
+        assert!(draining.await.unwrap());
+
+        // work still in flight makes the shutdown time out
+        let shutdown = Shutdown::new();
+        let _work = shutdown.track().unwrap();
+        assert!(!shutdown.shutdown(Duration::from_millis(10)).await);
+    }
+}
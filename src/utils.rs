@@ -1,8 +1,10 @@
 use serde::de::DeserializeOwned;
 use serenity::client::Context;
 use serenity::model::prelude::*;
+use serenity::prelude::HttpError;
 
-use crate::constants::MAX_JSON_FILE_SIZE;
+use crate::constants::{FAILURE_DELETION_DELAY, MAX_JSON_FILE_SIZE};
+use crate::database::settings::{get_bool_setting, AUTO_DELETE_FAILURES};
 
 /// Custom error for unwrapping `msg.guild_id`
 #[derive(Debug)]
@@ -110,6 +112,7 @@ macro_rules! get_database_conn {
                 .expect("Expected a database pool in the type map")
                 .clone()
         };
+        let _timer = $crate::metrics::time_db_connection();
         pool.get_conn()
             .await
             .ok()
@@ -117,6 +120,52 @@ macro_rules! get_database_conn {
     }};
 }
 
+/// Same as [`get_database_conn!`], but returns a
+/// [`DbError`][crate::database::DbError] instead of panicking when no
+/// connection is available.
+#[macro_export]
+macro_rules! try_get_database_conn {
+    ($ctx:ident) => {{
+        let pool = {
+            let data_read = $ctx.data.read().await;
+            data_read
+                .get::<$crate::database::DatabasePool>()
+                .expect("Expected a database pool in the type map")
+                .clone()
+        };
+        let _timer = $crate::metrics::time_db_connection();
+        match tokio::time::timeout($crate::constants::DB_ACQUIRE_TIMEOUT, pool.get_conn()).await {
+            Ok(conn) => conn.map_err($crate::database::DbError::from)?,
+            Err(_) => {
+                println!(
+                    "=== WARNING ===\nTimed out waiting for a database connection, \
+the pool may be too small\n=== END ==="
+                );
+                return Err($crate::database::DbError::Busy.into());
+            }
+        }
+    }};
+}
+
+/// Replies to a failed database call. If the connection pool is exhausted,
+/// asks to try again in a moment, at most once per channel every
+/// [`BUSY_NOTICE_COOLDOWN`][crate::constants::BUSY_NOTICE_COOLDOWN]; the
+/// command message only gets a reaction otherwise.
+#[macro_export]
+macro_rules! db_failure {
+    ($ctx:ident, $msg:ident, $error:expr) => {{
+        if let $crate::database::DbError::Busy = $error {
+            if $crate::database::busy_notice_due($ctx, $msg.channel_id).await {
+                $crate::failure!($ctx, $msg, $crate::t!($ctx, $msg, "db.busy"));
+            } else {
+                $crate::failure!($ctx, $msg);
+            }
+        } else {
+            $crate::failure!($ctx, $msg, $crate::t!($ctx, $msg, "db.unavailable"));
+        }
+    }};
+}
+
 #[macro_export]
 macro_rules! success {
     ($ctx:ident, $msg:ident) => {
@@ -138,12 +187,12 @@ macro_rules! failure {
         $msg.react($ctx, serenity::model::prelude::ReactionType::from('❌')).await?;
     };
     ($ctx:ident, $msg:ident, $single_message:expr) => {{
-        $msg.reply($ctx, $single_message).await?;
+        let failure_message = $msg.reply($ctx, $single_message).await?;
+        $crate::utils::schedule_failure_deletion(&$ctx, $msg.guild_id, failure_message).await;
         $crate::failure!($ctx, $msg);
     }};
     ($ctx:ident, $msg:ident, $($error:tt)*) => {{
-        $msg.reply($ctx, format!($($error)*)).await?;
-        $crate::failure!($ctx, $msg);
+        $crate::failure!($ctx, $msg, format!($($error)*));
     }};
 }
 
@@ -163,6 +212,44 @@ macro_rules! warn {
     };
 }
 
+/// Awaits a Discord call, and retries it up to
+/// [`DISCORD_RETRIES`][crate::constants::DISCORD_RETRIES] times if it fails
+/// with a [transient error][is_transient_error]. Only use it for calls that can
+/// safely be sent twice, like interaction responses and message edits.
+/// ```ignore
+/// retry_discord!("updating the bug embed", interaction.create_interaction_response(ctx, f))?;
+/// ```
+#[macro_export]
+macro_rules! retry_discord {
+    ($what:expr, $call:expr) => {{
+        let mut retries = 0;
+        loop {
+            match $call.await {
+                Err(e)
+                    if retries < $crate::constants::DISCORD_RETRIES
+                        && $crate::utils::is_transient_error(&e) =>
+                {
+                    println!(
+                        "=== WARNING ===\nTransient error while {}, retrying: {}\n=== END ===",
+                        $what, e
+                    );
+                    tokio::time::sleep($crate::constants::DISCORD_RETRY_DELAY * 2u32.pow(retries))
+                        .await;
+                    retries += 1;
+                }
+                Err(e) if retries > 0 => {
+                    println!(
+                        "=== ERROR ===\nGave up {} after {} retries: {}\n=== END ===",
+                        $what, retries, e
+                    );
+                    break Err(e);
+                }
+                result => break result,
+            }
+        }
+    }};
+}
+
 #[macro_export]
 macro_rules! is_admin {
     ($ctx:ident, $msg:ident) => {
@@ -175,6 +262,48 @@ macro_rules! is_admin {
     };
 }
 
+/// Deletes a [`failure!`][crate::failure] reply after [`FAILURE_DELETION_DELAY`]
+/// if the guild has enabled the
+/// [`AUTO_DELETE_FAILURES`][crate::database::settings::AUTO_DELETE_FAILURES]
+/// setting.
+pub async fn schedule_failure_deletion(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    failure_message: Message,
+) {
+    let guild_id = match guild_id {
+        Some(guild_id) => guild_id,
+        None => return,
+    };
+    if !get_bool_setting(ctx, guild_id, AUTO_DELETE_FAILURES).await {
+        return;
+    }
+
+    let http = ctx.http.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(FAILURE_DELETION_DELAY).await;
+        if let Err(e) = failure_message.delete(&http).await {
+            println!(
+                "=== ERROR ===\nCould not delete failure message: {}\n=== END ===",
+                e
+            );
+        }
+    });
+}
+
+/// Whether a failed Discord call is worth retrying: server errors, and
+/// requests that did not get a response at all.
+pub fn is_transient_error(error: &serenity::Error) -> bool {
+    match error {
+        serenity::Error::Http(e) => match e.as_ref() {
+            HttpError::UnsuccessfulRequest(response) => response.status_code.is_server_error(),
+            HttpError::Request(_) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 /// Checks a [`User`]'s permissions.
 ///
 /// Returns `true` if `user` has any of the [permissions][Permissions] `perm` in the
@@ -203,51 +332,169 @@ pub async fn has_permission(
     false
 }
 
+/// Converts the Minecraft formatting codes of a MOTD to Markdown.
+///
+/// Colour codes and `§r` close the current formatting, as in Minecraft.
+/// Discord can't render obfuscated text (`§k`), so it is put in a spoiler.
 pub fn parse_motd(motd: impl ToString) -> String {
+    /// Closes the open formatting, dropping the markers with no text after them
+    fn close(res: &mut String, stack: &mut Vec<(&str, usize)>) {
+        while let Some(&(marker, end)) = stack.last() {
+            if res.len() != end {
+                break;
+            }
+            res.truncate(end - marker.len());
+            stack.pop();
+        }
+        if !stack.is_empty() {
+            stack.drain(..).rev().for_each(|(s, _)| res.push_str(s));
+            res.push('\u{200B}');
+        }
+    }
+
     let motd = motd.to_string();
     let mut res = String::with_capacity(motd.len());
-    let mut stack: Vec<&str> = Vec::new();
+    // open markers, with the length of `res` right after each of them
+    let mut stack: Vec<(&str, usize)> = Vec::new();
     let mut is_token = false;
     for c in motd.chars() {
         if c == '§' {
             is_token = true;
         } else if is_token {
             is_token = false;
-            match c {
-                '0'..='9' | 'a'..='f' | 'k' | 'r' => {
-                    if !stack.is_empty() {
-                        stack.drain(..).rev().for_each(|s| res.push_str(s));
-                        res.push('\u{200B}');
-                    }
-                }
-                'l' => {
-                    stack.push("**");
-                    res.push_str("**");
-                }
-
-                'n' => {
-                    stack.push("__");
-                    res.push_str("__");
-                }
-                'm' => {
-                    stack.push("~~");
-                    res.push_str("~~");
-                }
-                'o' => {
-                    stack.push("*");
-                    res.push('*');
+            let marker = match c {
+                '0'..='9' | 'a'..='f' | 'r' => {
+                    close(&mut res, &mut stack);
+                    continue;
                 }
+                'k' => "||",
+                'l' => "**",
+                'n' => "__",
+                'm' => "~~",
+                'o' => "*",
                 _ => {
                     res.push('§');
-                    res.push(c)
+                    res.push(c);
+                    continue;
                 }
+            };
+            if !stack.iter().any(|&(s, _)| s == marker) {
+                res.push_str(marker);
+                stack.push((marker, res.len()));
             }
         } else {
             res.push(c);
         }
     }
-    stack.drain(..).rev().for_each(|t| res.push_str(t));
-    res
+    close(&mut res, &mut stack);
+    res.trim_end_matches('\u{200B}').to_string()
+}
+
+/// Formats a duration in days and hours, e.g. `12 days` or `1 day 3 hours`.
+///
+/// Hours are omitted past a week, as they are not relevant anymore.
+pub fn format_duration(duration: chrono::Duration) -> String {
+    fn plural(n: i64, unit: &str) -> String {
+        format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" })
+    }
+
+    let days = duration.num_days();
+    let hours = duration.num_hours() % 24;
+    match (days, hours) {
+        (0, 0) => "less than an hour".into(),
+        (0, h) => plural(h, "hour"),
+        (d, 0) => plural(d, "day"),
+        (d, _) if d >= 7 => plural(d, "day"),
+        (d, h) => format!("{} {}", plural(d, "day"), plural(h, "hour")),
+    }
+}
+
+/// Formats a time as Discord timestamp markdown, shown in the timezone and
+/// locale of each viewer. `style` is one of Discord's styles, like `d` for a
+/// short date, `f` for a date and time, or `R` for a relative time.
+pub fn discord_timestamp(time: &chrono::DateTime<chrono::Utc>, style: char) -> String {
+    format!("<t:{}:{}>", time.timestamp(), style)
+}
+
+/// Makes user text safe to echo in a reply: backticks are escaped, and a zero
+/// width space after each `@` keeps mentions from pinging or rendering.
+pub fn sanitize_echo(text: &str) -> String {
+    text.replace('`', "\\`").replace('@', "@\u{200b}")
+}
+
+/// A mod version in the `vX.Y.Z` scheme, e.g. `v41.2`. Missing numbers count
+/// as zero, so `v41` and `v41.0.0` are the same version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ModVersion(u32, u32, u32);
+
+#[derive(Debug, Clone, Copy)]
+pub struct ParseVersionError;
+
+impl std::str::FromStr for ModVersion {
+    type Err = ParseVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix(|c| c == 'v' || c == 'V').unwrap_or(s);
+        let mut numbers = [0; 3];
+        let mut parts = s.split('.');
+        for (i, part) in parts.by_ref().take(3).enumerate() {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ParseVersionError);
+            }
+            numbers[i] = part.parse().map_err(|_| ParseVersionError)?;
+        }
+        if parts.next().is_some() {
+            return Err(ParseVersionError);
+        }
+        Ok(Self(numbers[0], numbers[1], numbers[2]))
+    }
+}
+
+/// Number of single character insertions, deletions, substitutions and swaps
+/// of adjacent characters to turn `a` into `b`
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // distances[i][j] is the distance between a[..i] and b[..j]
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+/// Checks the shape of a Discord bot token: three non-empty parts of URL-safe
+/// base64, separated by dots, optionally prefixed with `Bot `. The error
+/// completes the sentence "The token ...".
+pub fn check_token_format(token: &str) -> Result<(), &'static str> {
+    let token = token.strip_prefix("Bot ").unwrap_or(token);
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 || parts.iter().any(|part| part.is_empty()) {
+        return Err("must have three parts separated by dots");
+    }
+    if !token
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    {
+        return Err("must only contain letters, digits, `-`, `_` and dots");
+    }
+    Ok(())
 }
 
 pub fn to_json_safe_string(s: impl ToString) -> String {
@@ -476,9 +723,103 @@ Error: {}
     }
 }
 
+use serenity::collector::CollectComponentInteraction;
+use serenity::model::interactions::message_component::ButtonStyle;
+
+use crate::constants::CONFIRMATION_TIMEOUT;
+
+/// Asks the author of `msg` to confirm an action with Confirm/Cancel buttons.
+///
+/// Other users pressing the buttons are told that they can't. Returns `false`
+/// if the author cancels, or does not answer within [`CONFIRMATION_TIMEOUT`].
+/// The buttons are disabled once answered.
+pub async fn confirm(
+    ctx: &Context,
+    msg: &Message,
+    prompt: impl ToString,
+) -> Result<bool, serenity::Error> {
+    macro_rules! confirm_buttons {
+        ($disabled:expr) => {
+            |c| {
+                c.create_action_row(|a| {
+                    a.create_button(|b| {
+                        b.style(ButtonStyle::Danger)
+                            .label("Confirm")
+                            .custom_id("confirm")
+                            .disabled($disabled)
+                    });
+                    a.create_button(|b| {
+                        b.style(ButtonStyle::Secondary)
+                            .label("Cancel")
+                            .custom_id("cancel")
+                            .disabled($disabled)
+                    })
+                })
+            }
+        };
+    }
+
+    let prompt = prompt.to_string();
+    let mut prompt_message = msg
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(&prompt)
+                .reference_message(msg)
+                .allowed_mentions(|a| a.empty_parse())
+                .components(confirm_buttons!(false))
+        })
+        .await?;
+
+    let _collector = crate::metrics::collector_started();
+    while let Some(interaction) = CollectComponentInteraction::new(ctx)
+        .timeout(CONFIRMATION_TIMEOUT)
+        .channel_id(msg.channel_id)
+        .message_id(prompt_message.id)
+        .await
+    {
+        if interaction.user.id != msg.author.id {
+            interaction
+                .say_ephemeral(ctx, "Only the user of the command can answer!")
+                .await;
+            continue;
+        }
+
+        let confirmed = interaction.data.custom_id == "confirm";
+        crate::retry_discord!(
+            "answering a confirmation",
+            interaction.create_interaction_response(ctx, |r| {
+                r.kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|d| {
+                        d.content(format!(
+                            "{}\n_{}_",
+                            prompt,
+                            if confirmed {
+                                "Confirmed."
+                            } else {
+                                "Cancelled."
+                            }
+                        ))
+                        .components(confirm_buttons!(true))
+                    })
+            })
+        )?;
+        return Ok(confirmed);
+    }
+
+    // no answer before the timeout
+    crate::retry_discord!(
+        "disabling the confirmation buttons",
+        prompt_message.edit(ctx, |m| m.components(confirm_buttons!(true)))
+    )?;
+    Ok(false)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::to_json_safe_string;
+    use super::{
+        check_token_format, discord_timestamp, edit_distance, format_duration, parse_motd,
+        sanitize_echo, to_json_safe_string, ModVersion,
+    };
 
     #[test]
     fn test_json_safe_string() {
@@ -486,4 +827,114 @@ mod tests {
 
         assert_eq!(to_json_safe_string(s), "\\\"holà\\\"\\n}");
     }
+
+    #[test]
+    fn test_sanitize_echo() {
+        assert_eq!(sanitize_echo("Crash in `Foo`"), "Crash in \\`Foo\\`");
+        assert_eq!(
+            sanitize_echo("@everyone <@222868216920145920>"),
+            "@\u{200b}everyone <@\u{200b}222868216920145920>"
+        );
+        assert_eq!(sanitize_echo("Plain title"), "Plain title");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        use chrono::Duration;
+
+        assert_eq!(format_duration(Duration::minutes(30)), "less than an hour");
+        assert_eq!(format_duration(Duration::hours(5)), "5 hours");
+        assert_eq!(format_duration(Duration::hours(25)), "1 day 1 hour");
+        assert_eq!(format_duration(Duration::days(2)), "2 days");
+        assert_eq!(format_duration(Duration::hours(40 * 24 + 3)), "40 days");
+    }
+
+    #[test]
+    fn test_discord_timestamp() {
+        use chrono::{NaiveDate, TimeZone, Utc};
+
+        let time = Utc.from_utc_datetime(&NaiveDate::from_ymd(2022, 3, 4).and_hms(12, 30, 0));
+        assert_eq!(discord_timestamp(&time, 'R'), "<t:1646397000:R>");
+        assert_eq!(
+            discord_timestamp(&crate::database::utc(time.naive_utc()), 'd'),
+            "<t:1646397000:d>"
+        );
+    }
+
+    #[test]
+    fn test_mod_version() {
+        let v = |s: &str| s.parse::<ModVersion>().unwrap();
+
+        assert_eq!(v("v41.2"), ModVersion(41, 2, 0));
+        assert_eq!(v("41.2.1"), ModVersion(41, 2, 1));
+        assert_eq!(v(" V41 "), v("v41.0.0"));
+        assert!(v("v41.2") < v("v41.10"));
+        assert!(v("v41.2") < v("v41.2.1"));
+        assert!(v("v9.9.9") < v("v10"));
+
+        for invalid in [
+            "",
+            "v",
+            "v41.",
+            "v41..2",
+            "41.2.3.4",
+            "v41.2-beta",
+            "vv41",
+            "Update",
+        ] {
+            assert!(invalid.parse::<ModVersion>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("high", "high"), 0);
+        assert_eq!(edit_distance("hig", "high"), 1);
+        assert_eq!(edit_distance("hihg", "high"), 1);
+        assert_eq!(edit_distance("higj", "high"), 1);
+        assert_eq!(edit_distance("", "low"), 3);
+        assert_eq!(edit_distance("closed", "close"), 1);
+        assert_eq!(edit_distance("medium", "mid"), 4);
+    }
+
+    #[test]
+    fn test_parse_motd() {
+        assert_eq!(parse_motd("A Minecraft Server"), "A Minecraft Server");
+        assert_eq!(parse_motd("§lEras §r§aof Arda"), "**Eras **\u{200B}of Arda");
+        assert_eq!(parse_motd("§ksecret§r"), "||secret||");
+        assert_eq!(
+            parse_motd("Password: §ksecret§r, shh"),
+            "Password: ||secret||\u{200B}, shh"
+        );
+        // obfuscation stops at colour codes, like other formatting
+        assert_eq!(parse_motd("§l§kboth§cred"), "**||both||**\u{200B}red");
+        // unclosed formatting is closed at the end
+        assert_eq!(parse_motd("§ksecret"), "||secret||");
+        // empty runs leave no markers behind
+        assert_eq!(parse_motd("§k§rtext"), "text");
+        assert_eq!(parse_motd("§l§ctext"), "text");
+        assert_eq!(parse_motd("§k§ksecret§r"), "||secret||");
+        assert_eq!(parse_motd("§xnot a code"), "§xnot a code");
+    }
+
+    #[test]
+    fn test_check_token_format() {
+        for token in [
+            "NzgwODU4MzkxMzgzNjM4MDU3.X-hvzA.Ovy4MCQywSkoMRRclStW4xAYK7I",
+            "Bot NzgwODU4MzkxMzgzNjM4MDU3.X-hvzA.Ovy4MCQywSkoMRRclStW4xAYK7I",
+        ] {
+            assert!(check_token_format(token).is_ok(), "{:?}", token);
+        }
+        for token in [
+            "",
+            "Bot ",
+            "Bot  NzgwODU4MzkxMzgzNjM4MDU3.X-hvzA.Ovy4MCQywSkoMRRclStW4xAYK7I",
+            "NzgwODU4MzkxMzgzNjM4MDU3.X-hvzA",
+            "NzgwODU4MzkxMzgzNjM4MDU3..Ovy4MCQywSkoMRRclStW4xAYK7I",
+            "NzgwODU4MzkxMzgzNjM4MDU3.X-hvzA.Ovy4MCQywSkoMRRclStW4xAYK7I\n",
+            "\"NzgwODU4MzkxMzgzNjM4MDU3.X-hvzA.Ovy4MCQywSkoMRRclStW4xAYK7I\"",
+        ] {
+            assert!(check_token_format(token).is_err(), "{:?}", token);
+        }
+    }
 }